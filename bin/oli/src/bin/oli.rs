@@ -69,26 +69,79 @@ async fn main() -> Result<()> {
             let cmd = oli::commands::cli::cli(new_cmd("oli")?);
             oli::commands::cli::main(&cmd.get_matches()).await?;
         }
+        Some("obench") => {
+            let cmd = oli::commands::bench::cli(new_cmd("obench")?);
+            oli::commands::bench::main(&cmd.get_matches()).await?;
+        }
         Some("ocat") => {
             let cmd = oli::commands::cat::cli(new_cmd("ocat")?);
             oli::commands::cat::main(&cmd.get_matches()).await?;
         }
+        Some("oconfig") => {
+            let cmd = oli::commands::config::cli(new_cmd("oconfig")?);
+            oli::commands::config::main(&cmd.get_matches()).await?;
+        }
         Some("ocp") => {
             let cmd = oli::commands::cp::cli(new_cmd("ocp")?);
             oli::commands::cp::main(&cmd.get_matches()).await?;
         }
+        Some("odu") => {
+            let cmd = oli::commands::du::cli(new_cmd("odu")?);
+            oli::commands::du::main(&cmd.get_matches()).await?;
+        }
+        Some("ofind") => {
+            let cmd = oli::commands::find::cli(new_cmd("ofind")?);
+            oli::commands::find::main(&cmd.get_matches()).await?;
+        }
+        Some("ohash") => {
+            let cmd = oli::commands::hash::cli(new_cmd("ohash")?);
+            oli::commands::hash::main(&cmd.get_matches()).await?;
+        }
         Some("ols") => {
             let cmd = oli::commands::ls::cli(new_cmd("ols")?);
             oli::commands::ls::main(&cmd.get_matches()).await?;
         }
+        Some("omkdir") => {
+            let cmd = oli::commands::mkdir::cli(new_cmd("omkdir")?);
+            oli::commands::mkdir::main(&cmd.get_matches()).await?;
+        }
+        #[cfg(target_os = "linux")]
+        Some("omount") => {
+            let cmd = oli::commands::mount::cli(new_cmd("omount")?);
+            oli::commands::mount::main(&cmd.get_matches()).await?;
+        }
+        Some("omv") => {
+            let cmd = oli::commands::mv::cli(new_cmd("omv")?);
+            oli::commands::mv::main(&cmd.get_matches()).await?;
+        }
+        Some("oput") => {
+            let cmd = oli::commands::put::cli(new_cmd("oput")?);
+            oli::commands::put::main(&cmd.get_matches()).await?;
+        }
         Some("orm") => {
             let cmd = oli::commands::rm::cli(new_cmd("orm")?);
             oli::commands::rm::main(&cmd.get_matches()).await?;
         }
+        Some("oserve") => {
+            let cmd = oli::commands::serve::cli(new_cmd("oserve")?);
+            oli::commands::serve::main(&cmd.get_matches()).await?;
+        }
+        Some("oshell") => {
+            let cmd = oli::commands::shell::cli(new_cmd("oshell")?);
+            oli::commands::shell::main(&cmd.get_matches()).await?;
+        }
         Some("ostat") => {
             let cmd = oli::commands::stat::cli(new_cmd("ostat")?);
             oli::commands::stat::main(&cmd.get_matches()).await?;
         }
+        Some("osync") => {
+            let cmd = oli::commands::sync::cli(new_cmd("osync")?);
+            oli::commands::sync::main(&cmd.get_matches()).await?;
+        }
+        Some("otee") => {
+            let cmd = oli::commands::tee::cli(new_cmd("otee")?);
+            oli::commands::tee::main(&cmd.get_matches()).await?;
+        }
         Some(v) => {
             println!("{v} is not supported")
         }
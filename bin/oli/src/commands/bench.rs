@@ -0,0 +1,406 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use clap::value_parser;
+use clap::Arg;
+use clap::ArgAction;
+use clap::ArgMatches;
+use clap::Command;
+use futures::Future;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use opendal::raw::oio;
+use opendal::Operator;
+
+use super::find::parse_size;
+use crate::config::Config;
+use crate::format::human_size;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let target = args
+        .get_one::<String>("target")
+        .ok_or_else(|| anyhow!("missing target"))?;
+    let (op, path) = cfg.parse_location(target)?;
+
+    let get_size = |name: &str| -> Result<u64> {
+        parse_size(
+            args.get_one::<String>(name)
+                .ok_or_else(|| anyhow!("missing {}", name))?,
+        )
+    };
+    let opts = BenchOptions {
+        file_size: get_size("file-size")?,
+        range_size: get_size("range-size")?,
+        object_size: get_size("object-size")?,
+        ops: *args
+            .get_one::<u64>("ops")
+            .ok_or_else(|| anyhow!("missing ops"))? as usize,
+        jobs: *args
+            .get_one::<u64>("jobs")
+            .ok_or_else(|| anyhow!("missing jobs"))? as usize,
+    };
+    if opts.range_size > opts.file_size {
+        return Err(anyhow!("range size must not be larger than file size"));
+    }
+    let workloads = match args.get_many::<String>("workload") {
+        Some(vs) => vs.map(|v| v.parse()).collect::<Result<Vec<_>>>()?,
+        None => Workload::ALL.to_vec(),
+    };
+
+    // Use a unique prefix so that existing data will never be touched.
+    let prefix = format!(
+        "{}oli-bench-{}/",
+        if path.is_empty() || path.ends_with('/') {
+            path.clone()
+        } else {
+            format!("{path}/")
+        },
+        uuid::Uuid::new_v4()
+    );
+    println!("Benchmarking {target} under {prefix}");
+
+    let res = run(&op, &prefix, &workloads, &opts).await;
+    // Clean up test data even if the benchmark failed.
+    op.remove_all(&prefix).await?;
+    res
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("benchmark the throughput and latency of a backend")
+        .arg(
+            Arg::new("target")
+                .required(true)
+                .help("Location to run benchmark, test data will be written under it"),
+        )
+        .arg(
+            Arg::new("workload")
+                .long("workload")
+                .short('w')
+                .help("Workloads to run, all workloads will be run if not set")
+                .value_parser(["write", "list", "read", "range-read"])
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("file-size")
+                .long("file-size")
+                .help("Size of the file used by read workloads")
+                .default_value("64M"),
+        )
+        .arg(
+            Arg::new("range-size")
+                .long("range-size")
+                .help("Size of each range in range-read workload")
+                .default_value("4K"),
+        )
+        .arg(
+            Arg::new("object-size")
+                .long("object-size")
+                .help("Size of each object in write workload")
+                .default_value("4K"),
+        )
+        .arg(
+            Arg::new("ops")
+                .long("ops")
+                .short('n')
+                .help("Number of operations of each workload")
+                .default_value("100")
+                .value_parser(value_parser!(u64).range(1..)),
+        )
+        .arg(
+            Arg::new("jobs")
+                .long("jobs")
+                .short('j')
+                .help("Number of concurrent operations")
+                .default_value("4")
+                .value_parser(value_parser!(u64).range(1..)),
+        )
+}
+
+struct BenchOptions {
+    file_size: u64,
+    range_size: u64,
+    object_size: u64,
+    ops: usize,
+    jobs: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Workload {
+    /// Write small objects.
+    Write,
+    /// List the objects written by write workload.
+    List,
+    /// Read the whole file sequentially.
+    Read,
+    /// Read random ranges of the file.
+    RangeRead,
+}
+
+impl Workload {
+    const ALL: [Workload; 4] = [
+        Workload::Write,
+        Workload::List,
+        Workload::Read,
+        Workload::RangeRead,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Workload::Write => "write",
+            Workload::List => "list",
+            Workload::Read => "read",
+            Workload::RangeRead => "range-read",
+        }
+    }
+}
+
+impl FromStr for Workload {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Workload::ALL
+            .into_iter()
+            .find(|w| w.name() == s)
+            .ok_or_else(|| anyhow!("unknown workload: {}", s))
+    }
+}
+
+async fn run(
+    op: &Operator,
+    prefix: &str,
+    workloads: &[Workload],
+    opts: &BenchOptions,
+) -> Result<()> {
+    let objects_dir = format!("{prefix}objects/");
+    let file_path = format!("{prefix}file");
+    let (mut objects_ready, mut file_ready) = (false, false);
+
+    for workload in workloads {
+        let report = match workload {
+            Workload::Write => {
+                let content = vec![0; opts.object_size as usize];
+                objects_ready = true;
+                bench(opts, |i| {
+                    let (path, content) = (format!("{objects_dir}{i}"), content.clone());
+                    async move {
+                        op.write(&path, content).await?;
+                        Ok(opts.object_size)
+                    }
+                })
+                .await?
+            }
+            Workload::List => {
+                if !objects_ready {
+                    prepare_objects(op, &objects_dir, opts).await?;
+                    objects_ready = true;
+                }
+                bench(opts, |_| async {
+                    let mut entries = 0;
+                    let mut ds = op.list(&objects_dir).await?;
+                    while ds.try_next().await?.is_some() {
+                        entries += 1;
+                    }
+                    // List has no content, count entries instead.
+                    Ok(entries)
+                })
+                .await?
+            }
+            Workload::Read => {
+                if !file_ready {
+                    prepare_file(op, &file_path, opts.file_size).await?;
+                    file_ready = true;
+                }
+                bench(opts, |_| async {
+                    Ok(op.read(&file_path).await?.len() as u64)
+                })
+                .await?
+            }
+            Workload::RangeRead => {
+                if !file_ready {
+                    prepare_file(op, &file_path, opts.file_size).await?;
+                    file_ready = true;
+                }
+                let max_offset = opts.file_size - opts.range_size;
+                let mut rng = Rng::new();
+                let offsets: Vec<u64> = (0..opts.ops)
+                    .map(|_| rng.next_u64() % (max_offset + 1))
+                    .collect();
+                bench(opts, |i| {
+                    let range = offsets[i]..offsets[i] + opts.range_size;
+                    async { Ok(op.range_read(&file_path, range).await?.len() as u64) }
+                })
+                .await?
+            }
+        };
+
+        let unit = if *workload == Workload::List {
+            format!("{:.1} entries/s", report.amount as f64 / report.secs())
+        } else {
+            format!(
+                "{}/s",
+                human_size((report.amount as f64 / report.secs()) as u64)
+            )
+        };
+        println!(
+            "{:<10} ops: {}, elapsed: {:.3}s, {:.1} ops/s, {}, latency p50: {:?}, p90: {:?}, p99: {:?}, max: {:?}",
+            workload.name(),
+            report.latencies.len(),
+            report.secs(),
+            report.latencies.len() as f64 / report.secs(),
+            unit,
+            percentile(&report.latencies, 0.5),
+            percentile(&report.latencies, 0.9),
+            percentile(&report.latencies, 0.99),
+            percentile(&report.latencies, 1.0),
+        );
+    }
+    Ok(())
+}
+
+struct Report {
+    /// Latencies of each operation in ascending order.
+    latencies: Vec<Duration>,
+    /// Bytes transferred or entries listed.
+    amount: u64,
+    elapsed: Duration,
+}
+
+impl Report {
+    fn secs(&self) -> f64 {
+        self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Run `opts.ops` operations with `opts.jobs` concurrency.
+async fn bench<F, Fut>(opts: &BenchOptions, f: F) -> Result<Report>
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = Result<u64>>,
+{
+    let start = Instant::now();
+    let results: Vec<(Duration, u64)> = futures::stream::iter(0..opts.ops)
+        .map(|i| {
+            let fut = f(i);
+            async move {
+                let start = Instant::now();
+                let amount = fut.await?;
+                Ok::<_, anyhow::Error>((start.elapsed(), amount))
+            }
+        })
+        .buffer_unordered(opts.jobs)
+        .try_collect()
+        .await?;
+    let elapsed = start.elapsed();
+
+    let mut latencies: Vec<_> = results.iter().map(|(d, _)| *d).collect();
+    latencies.sort_unstable();
+    Ok(Report {
+        latencies,
+        amount: results.iter().map(|(_, n)| n).sum(),
+        elapsed,
+    })
+}
+
+async fn prepare_objects(op: &Operator, dir: &str, opts: &BenchOptions) -> Result<()> {
+    let content = vec![0; opts.object_size as usize];
+    futures::stream::iter(0..opts.ops)
+        .map(|i| {
+            let (path, content) = (format!("{dir}{i}"), content.clone());
+            async move { op.write(&path, content).await }
+        })
+        .buffer_unordered(opts.jobs)
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(())
+}
+
+async fn prepare_file(op: &Operator, path: &str, size: u64) -> Result<()> {
+    let mut w = op.writer(path).await?;
+    let mut written = 0;
+    while written < size {
+        let n = (oio::DEFAULT_APPEND_CHUNK_SIZE as u64).min(size - written);
+        w.append(vec![0; n as usize]).await?;
+        written += n;
+    }
+    w.close().await?;
+    Ok(())
+}
+
+/// Returns the latency at given percentile, `latencies` must be sorted.
+fn percentile(latencies: &[Duration], p: f64) -> Duration {
+    if latencies.is_empty() {
+        return Duration::ZERO;
+    }
+    let idx = ((latencies.len() - 1) as f64 * p).round() as usize;
+    latencies[idx]
+}
+
+/// A tiny xorshift generator to pick random ranges, no need to be secure.
+struct Rng(u64);
+
+impl Rng {
+    fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        Rng(seed | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let latencies: Vec<_> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&latencies, 0.5), Duration::from_millis(51));
+        assert_eq!(percentile(&latencies, 0.99), Duration::from_millis(99));
+        assert_eq!(percentile(&latencies, 1.0), Duration::from_millis(100));
+        assert_eq!(percentile(&[], 0.5), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_parse_workload() {
+        assert_eq!(
+            "range-read".parse::<Workload>().unwrap(),
+            Workload::RangeRead
+        );
+        assert!("unknown".parse::<Workload>().is_err());
+    }
+}
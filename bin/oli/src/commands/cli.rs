@@ -22,11 +22,25 @@ use clap::Command;
 
 pub async fn main(args: &ArgMatches) -> Result<()> {
     match args.subcommand() {
+        Some(("bench", sub_args)) => super::bench::main(sub_args).await?,
         Some(("cat", sub_args)) => super::cat::main(sub_args).await?,
+        Some(("config", sub_args)) => super::config::main(sub_args).await?,
         Some(("cp", sub_args)) => super::cp::main(sub_args).await?,
+        Some(("du", sub_args)) => super::du::main(sub_args).await?,
+        Some(("find", sub_args)) => super::find::main(sub_args).await?,
+        Some(("hash", sub_args)) => super::hash::main(sub_args).await?,
         Some(("ls", sub_args)) => super::ls::main(sub_args).await?,
+        Some(("mkdir", sub_args)) => super::mkdir::main(sub_args).await?,
+        #[cfg(target_os = "linux")]
+        Some(("mount", sub_args)) => super::mount::main(sub_args).await?,
+        Some(("mv", sub_args)) => super::mv::main(sub_args).await?,
+        Some(("put", sub_args)) => super::put::main(sub_args).await?,
         Some(("rm", sub_args)) => super::rm::main(sub_args).await?,
+        Some(("serve", sub_args)) => super::serve::main(sub_args).await?,
+        Some(("shell", sub_args)) => super::shell::main(sub_args).await?,
         Some(("stat", sub_args)) => super::stat::main(sub_args).await?,
+        Some(("sync", sub_args)) => super::sync::main(sub_args).await?,
+        Some(("tee", sub_args)) => super::tee::main(sub_args).await?,
         _ => return Err(anyhow!("not handled")),
     }
 
@@ -38,10 +52,28 @@ fn new_cmd(name: &'static str) -> Command {
 }
 
 pub fn cli(cmd: Command) -> Command {
-    cmd.about("OpenDAL Command Line Interface")
+    let cmd = cmd
+        .about("OpenDAL Command Line Interface")
+        .subcommand(super::bench::cli(new_cmd("bench")))
         .subcommand(super::cat::cli(new_cmd("cat")))
+        .subcommand(super::config::cli(new_cmd("config")))
         .subcommand(super::cp::cli(new_cmd("cp")))
+        .subcommand(super::du::cli(new_cmd("du")))
+        .subcommand(super::find::cli(new_cmd("find")))
+        .subcommand(super::hash::cli(new_cmd("hash")))
         .subcommand(super::ls::cli(new_cmd("ls")))
+        .subcommand(super::mkdir::cli(new_cmd("mkdir")))
+        .subcommand(super::mv::cli(new_cmd("mv")))
+        .subcommand(super::put::cli(new_cmd("put")))
         .subcommand(super::rm::cli(new_cmd("rm")))
+        .subcommand(super::serve::cli(new_cmd("serve")))
+        .subcommand(super::shell::cli(new_cmd("shell")))
         .subcommand(super::stat::cli(new_cmd("stat")))
+        .subcommand(super::sync::cli(new_cmd("sync")))
+        .subcommand(super::tee::cli(new_cmd("tee")));
+
+    #[cfg(target_os = "linux")]
+    let cmd = cmd.subcommand(super::mount::cli(new_cmd("mount")));
+
+    cmd
 }
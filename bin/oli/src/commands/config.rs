@@ -0,0 +1,140 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use clap::Arg;
+use clap::ArgAction;
+use clap::ArgMatches;
+use clap::Command;
+
+use crate::config::Config;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    // Only profiles in config file should be managed, env ones are ignored.
+    let mut cfg = Config::load_from_file(config_path)?;
+
+    match args.subcommand() {
+        Some(("add", sub_args)) => {
+            let name = sub_args
+                .get_one::<String>("name")
+                .ok_or_else(|| anyhow!("missing name"))?;
+            let mut opts = HashMap::new();
+            for kv in sub_args.get_many::<String>("options").into_iter().flatten() {
+                let (k, v) = kv
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("invalid option '{}', expect key=value", kv))?;
+                opts.insert(k.to_string(), v.to_string());
+            }
+            if !opts.contains_key("type") {
+                return Err(anyhow!("missing option 'type' for profile '{}'", name));
+            }
+            cfg.set_profile(name, opts);
+            // Make sure the profile is valid before saving.
+            cfg.parse_location(&format!("{name}://"))?;
+            cfg.save_to_file(config_path)?;
+            println!("Profile added: {name}");
+        }
+        Some(("list", _)) => {
+            for name in cfg.profile_names() {
+                println!("{name}");
+            }
+        }
+        Some(("show", sub_args)) => {
+            let name = sub_args
+                .get_one::<String>("name")
+                .ok_or_else(|| anyhow!("missing name"))?;
+            let profile = cfg
+                .profile(name)
+                .ok_or_else(|| anyhow!("unknown profile: {}", name))?;
+            let mut keys: Vec<_> = profile.keys().collect();
+            keys.sort_unstable();
+            for k in keys {
+                let v = if is_secret(k) { "******" } else { &profile[k] };
+                println!("{k} = {v}");
+            }
+        }
+        Some(("remove", sub_args)) => {
+            let name = sub_args
+                .get_one::<String>("name")
+                .ok_or_else(|| anyhow!("missing name"))?;
+            if !cfg.remove_profile(name) {
+                return Err(anyhow!("unknown profile: {}", name));
+            }
+            cfg.save_to_file(config_path)?;
+            println!("Profile removed: {name}");
+        }
+        _ => return Err(anyhow!("not handled")),
+    }
+
+    Ok(())
+}
+
+/// Check if the option holds credentials that should not be printed.
+fn is_secret(key: &str) -> bool {
+    let key = key.to_lowercase();
+    ["secret", "password", "token", "sas", "credential"]
+        .iter()
+        .any(|s| key.contains(s))
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("manage profiles in config file")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("add")
+                .about("add or replace a profile")
+                .arg(Arg::new("name").required(true))
+                .arg(
+                    Arg::new("options")
+                        .required(true)
+                        .help("Options of profile like `type=s3 bucket=test`")
+                        .action(ArgAction::Append),
+                ),
+        )
+        .subcommand(Command::new("list").about("list all profiles"))
+        .subcommand(
+            Command::new("show")
+                .about("show options of a profile")
+                .arg(Arg::new("name").required(true)),
+        )
+        .subcommand(
+            Command::new("remove")
+                .about("remove a profile")
+                .arg(Arg::new("name").required(true)),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_secret() {
+        assert!(is_secret("secret_access_key"));
+        assert!(is_secret("sas_token"));
+        assert!(is_secret("Password"));
+        assert!(!is_secret("bucket"));
+        assert!(!is_secret("access_key_id"));
+    }
+}
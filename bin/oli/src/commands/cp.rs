@@ -15,18 +15,27 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::anyhow;
 use anyhow::Result;
+use clap::value_parser;
 use clap::Arg;
 use clap::ArgAction;
 use clap::ArgMatches;
 use clap::Command;
+use futures::AsyncReadExt;
+use futures::StreamExt;
 use futures::TryStreamExt;
+use opendal::raw::oio;
 use opendal::Metakey;
+use opendal::Operator;
 
+use super::hash::verify_file;
 use crate::config::Config;
 
 pub async fn main(args: &ArgMatches) -> Result<()> {
@@ -35,6 +44,14 @@ pub async fn main(args: &ArgMatches) -> Result<()> {
         .ok_or_else(|| anyhow!("missing config path"))?;
     let cfg = Config::load(config_path)?;
     let recursive = args.get_flag("recursive");
+    let jobs = *args
+        .get_one::<u64>("jobs")
+        .ok_or_else(|| anyhow!("missing jobs"))? as usize;
+    let chunk_size = *args
+        .get_one::<u64>("chunk-size")
+        .ok_or_else(|| anyhow!("missing chunk size"))?;
+    let checkpoint = args.get_one::<PathBuf>("checkpoint");
+    let verify = args.get_flag("verify");
 
     let src = args
         .get_one::<String>("source")
@@ -46,33 +63,81 @@ pub async fn main(args: &ArgMatches) -> Result<()> {
         .ok_or_else(|| anyhow!("missing target"))?;
     let (dst_op, dst_path) = cfg.parse_location(dst)?;
 
+    let native = is_same_backend(&cfg, src, dst)? && src_op.info().can_copy();
+
     if !recursive {
-        let mut dst_w = dst_op.writer(&dst_path).await?;
-        let reader = src_op.reader(&src_path).await?;
-        let buf_reader = futures::io::BufReader::with_capacity(8 * 1024 * 1024, reader);
-        futures::io::copy_buf(buf_reader, &mut dst_w).await?;
-        // flush data
-        dst_w.close().await?;
+        let opts = CopyOptions {
+            native,
+            jobs,
+            chunk_size,
+        };
+        copy_file(&src_op, &src_path, &dst_op, &dst_path, &opts).await?;
+        if verify {
+            verify_file(&src_op, &src_path, &dst_op, &dst_path).await?;
+        }
         return Ok(());
     }
 
+    // Files are copied concurrently, so chunked copy is not needed here,
+    // otherwise there will be `jobs * jobs` transfers at the same time.
+    let opts = CopyOptions {
+        native,
+        jobs: 1,
+        chunk_size: u64::MAX,
+    };
+
+    // Files that have been copied by previous runs, see `checkpoint_key`.
+    let copied: HashSet<String> = match checkpoint {
+        Some(fp) if fp.exists() => fs::read_to_string(fp)?
+            .lines()
+            .map(|v| v.to_string())
+            .collect(),
+        _ => HashSet::new(),
+    };
+    let mut checkpoint_file = match checkpoint {
+        Some(fp) => Some(fs::OpenOptions::new().create(true).append(true).open(fp)?),
+        None => None,
+    };
+
     let dst_root = Path::new(&dst_path);
+    let mut files = Vec::new();
     let mut ds = src_op.scan(&src_path).await?;
     while let Some(de) = ds.try_next().await? {
         let meta = src_op.metadata(&de, Metakey::Mode).await?;
         if meta.mode().is_dir() {
             continue;
         }
-
         let fp = de.path().strip_prefix(&src_path).expect("invalid path");
-        let reader = src_op.reader(de.path()).await?;
-        let buf_reader = futures::io::BufReader::with_capacity(8 * 1024 * 1024, reader);
+        let target = dst_root.join(fp).to_string_lossy().to_string();
+        if copied.contains(&checkpoint_key(&dst_op, de.path(), &target)) {
+            println!("Skipping {}", de.path());
+            continue;
+        }
+        files.push((de.path().to_string(), target));
+    }
 
-        let mut writer = dst_op.writer(&dst_root.join(fp).to_string_lossy()).await?;
+    let mut results = futures::stream::iter(files)
+        .map(|(path, target)| {
+            let (src_op, dst_op, opts) = (&src_op, &dst_op, &opts);
+            async move {
+                println!("Copying {path}");
+                copy_file(src_op, &path, dst_op, &target, opts).await?;
+                if verify {
+                    verify_file(src_op, &path, dst_op, &target).await?;
+                }
+                Ok::<_, anyhow::Error>(checkpoint_key(dst_op, &path, &target))
+            }
+        })
+        .buffer_unordered(jobs);
+    while let Some(key) = results.try_next().await? {
+        if let Some(f) = checkpoint_file.as_mut() {
+            writeln!(f, "{key}")?;
+        }
+    }
 
-        println!("Copying {}", de.path());
-        futures::io::copy_buf(buf_reader, &mut writer).await?;
-        writer.close().await?;
+    // All files have been copied, the checkpoint is useless now.
+    if let Some(fp) = checkpoint {
+        fs::remove_file(fp)?;
     }
     Ok(())
 }
@@ -89,4 +154,153 @@ pub fn cli(cmd: Command) -> Command {
                 .help("Copy files under source recursively to destination")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("jobs")
+                .long("jobs")
+                .short('j')
+                .help("Number of files or chunks to copy concurrently")
+                .default_value("1")
+                .value_parser(value_parser!(u64).range(1..)),
+        )
+        .arg(
+            Arg::new("chunk-size")
+                .long("chunk-size")
+                .help("Files larger than this size in bytes will be copied in parallel chunks, only used without --recursive, at least 1 MiB")
+                .default_value("16777216")
+                .value_parser(value_parser!(u64).range(1024 * 1024..)),
+        )
+        .arg(
+            Arg::new("checkpoint")
+                .long("checkpoint")
+                .help("Record copied files into this file to resume an interrupted recursive copy")
+                .requires("recursive")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("verify")
+                .long("verify")
+                .help("Compare checksums of source and destination after copying")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+pub(crate) struct CopyOptions {
+    /// Use native copy of the backend instead of streaming data.
+    pub native: bool,
+    /// Number of chunks to fetch concurrently.
+    pub jobs: usize,
+    /// Files larger than chunk size will be fetched in chunks if
+    /// `jobs` is larger than 1.
+    pub chunk_size: u64,
+}
+
+/// Check if both locations are served by the same profile so that data
+/// could be copied without going through local.
+///
+/// All options of profiles are compared, because backends with the same
+/// name and root could still have different endpoints or credentials.
+pub(crate) fn is_same_backend(cfg: &Config, src: &str, dst: &str) -> Result<bool> {
+    Ok(cfg.parse_profile(src)?.0 == cfg.parse_profile(dst)?.0)
+}
+
+/// Key of a copied file in checkpoint.
+///
+/// Destination is included so that a checkpoint will not skip files while
+/// copying to another destination.
+fn checkpoint_key(dst_op: &Operator, path: &str, target: &str) -> String {
+    let info = dst_op.info();
+    format!(
+        "{path}\t{}://{}{}{target}",
+        info.scheme(),
+        info.name(),
+        info.root()
+    )
+}
+
+/// Copy a single file from source to destination.
+pub(crate) async fn copy_file(
+    src_op: &Operator,
+    src_path: &str,
+    dst_op: &Operator,
+    dst_path: &str,
+    opts: &CopyOptions,
+) -> Result<()> {
+    if opts.native {
+        src_op.copy(src_path, dst_path).await?;
+        return Ok(());
+    }
+
+    let size = if opts.jobs > 1 {
+        src_op.stat(src_path).await?.content_length()
+    } else {
+        0
+    };
+
+    let mut dst_w = dst_op.writer(dst_path).await?;
+    let res = async {
+        // Data is appended in chunks of `AppendBuffer` no matter how it's
+        // fetched, so that services like s3 will not get too small parts.
+        let mut buf = oio::AppendBuffer::new();
+        if size <= opts.chunk_size {
+            let mut r = src_op.reader(src_path).await?;
+            while let Some(bs) = r.try_next().await? {
+                if let Some(bs) = buf.push(bs) {
+                    dst_w.append(bs).await?;
+                }
+            }
+        } else {
+            // Fetch chunks concurrently while appending them in order.
+            let mut chunks = futures::stream::iter((0..size).step_by(opts.chunk_size as usize))
+                .map(|offset| async move {
+                    let end = (offset + opts.chunk_size).min(size);
+                    let mut r = src_op.range_reader(src_path, offset..end).await?;
+                    let mut buf = Vec::with_capacity((end - offset) as usize);
+                    r.read_to_end(&mut buf).await?;
+                    Ok::<_, anyhow::Error>(buf)
+                })
+                .buffered(opts.jobs);
+            while let Some(bs) = chunks.try_next().await? {
+                if let Some(bs) = buf.push(bs.into()) {
+                    dst_w.append(bs).await?;
+                }
+            }
+        }
+        if let Some(bs) = buf.finish() {
+            dst_w.append(bs).await?;
+        }
+        dst_w.close().await?;
+        Ok(())
+    }
+    .await;
+
+    if res.is_err() {
+        // The partial upload will be left on destination if not aborted.
+        let _ = dst_w.abort().await;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use opendal::services;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_copy_file_in_chunks() -> Result<()> {
+        let src_op = Operator::new(services::Memory::default())?.finish();
+        let dst_op = Operator::new(services::Memory::default())?.finish();
+        let content = "0123456789".repeat(10);
+        src_op.write("src", content.clone()).await?;
+
+        let opts = CopyOptions {
+            native: false,
+            jobs: 4,
+            chunk_size: 7,
+        };
+        copy_file(&src_op, "src", &dst_op, "dst", &opts).await?;
+
+        assert_eq!(dst_op.read("dst").await?, content.as_bytes());
+        Ok(())
+    }
 }
@@ -0,0 +1,73 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+use futures::TryStreamExt;
+use opendal::Metakey;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::format::output_args;
+use crate::format::Output;
+
+#[derive(Serialize)]
+struct Usage {
+    path: String,
+    size: u64,
+    files: u64,
+}
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let target = args
+        .get_one::<String>("target")
+        .ok_or_else(|| anyhow!("missing target"))?;
+    let (op, path) = cfg.parse_location(target)?;
+    let output = Output::from_args(args);
+
+    let (mut size, mut files) = (0, 0);
+    let mut ds = op.scan(&path).await?;
+    while let Some(de) = ds.try_next().await? {
+        let meta = op
+            .metadata(&de, Metakey::Mode | Metakey::ContentLength)
+            .await?;
+        if meta.mode().is_file() {
+            size += meta.content_length();
+            files += 1;
+        }
+    }
+
+    let usage = Usage { path, size, files };
+    output.print(&usage, |v| {
+        format!("{}\t{} files\t{}", output.size(v.size), v.files, v.path)
+    })
+}
+
+pub fn cli(cmd: Command) -> Command {
+    output_args(cmd.about("summarize the size of objects under dir"))
+        .arg(Arg::new("target").required(true))
+}
@@ -0,0 +1,249 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+use futures::TryStreamExt;
+use globset::Glob;
+use globset::GlobMatcher;
+use opendal::Metadata;
+use opendal::Metakey;
+
+use crate::config::Config;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let now = Utc::now();
+    let predicate = Predicate {
+        name: args
+            .get_one::<String>("name")
+            .map(|v| Glob::new(v).map(|g| g.compile_matcher()))
+            .transpose()?,
+        file_type: args.get_one::<String>("type").cloned(),
+        min_size: args
+            .get_one::<String>("min-size")
+            .map(|v| parse_size(v))
+            .transpose()?,
+        max_size: args
+            .get_one::<String>("max-size")
+            .map(|v| parse_size(v))
+            .transpose()?,
+        newer: args
+            .get_one::<String>("newer")
+            .map(|v| parse_time_before(now, v))
+            .transpose()?,
+        older: args
+            .get_one::<String>("older")
+            .map(|v| parse_time_before(now, v))
+            .transpose()?,
+    };
+
+    let target = args
+        .get_one::<String>("target")
+        .ok_or_else(|| anyhow!("missing target"))?;
+    let (op, path) = cfg.parse_location(target)?;
+
+    let mut ds = op.scan(&path).await?;
+    while let Some(de) = ds.try_next().await? {
+        let meta = op
+            .metadata(
+                &de,
+                Metakey::Mode | Metakey::ContentLength | Metakey::LastModified,
+            )
+            .await?;
+        if predicate.is_match(de.name(), &meta) {
+            println!("{}", de.path());
+        }
+    }
+    Ok(())
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("find objects matching the given predicates")
+        .arg(Arg::new("target").required(true))
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .help("Match the object name against the glob"),
+        )
+        .arg(
+            Arg::new("type")
+                .long("type")
+                .help("Match the object type, `f` for file and `d` for dir")
+                .value_parser(["f", "d"]),
+        )
+        .arg(
+            Arg::new("min-size")
+                .long("min-size")
+                .help("Match files not smaller than the size, like `10M`"),
+        )
+        .arg(
+            Arg::new("max-size")
+                .long("max-size")
+                .help("Match files not larger than the size, like `1G`"),
+        )
+        .arg(
+            Arg::new("newer")
+                .long("newer")
+                .help("Match objects modified within the duration, like `7d`"),
+        )
+        .arg(
+            Arg::new("older")
+                .long("older")
+                .help("Match objects modified before the duration, like `30m`"),
+        )
+}
+
+struct Predicate {
+    name: Option<GlobMatcher>,
+    file_type: Option<String>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    newer: Option<DateTime<Utc>>,
+    older: Option<DateTime<Utc>>,
+}
+
+impl Predicate {
+    fn is_match(&self, name: &str, meta: &Metadata) -> bool {
+        if let Some(glob) = &self.name {
+            if !glob.is_match(name.trim_end_matches('/')) {
+                return false;
+            }
+        }
+        match self.file_type.as_deref() {
+            Some("f") if !meta.mode().is_file() => return false,
+            Some("d") if !meta.mode().is_dir() => return false,
+            _ => {}
+        }
+
+        // Size and time predicates only make sense for files.
+        let has_file_predicate = self.min_size.is_some()
+            || self.max_size.is_some()
+            || self.newer.is_some()
+            || self.older.is_some();
+        if !has_file_predicate {
+            return true;
+        }
+        if !meta.mode().is_file() {
+            return false;
+        }
+
+        let size = meta.content_length();
+        if self.min_size.map_or(false, |v| size < v) || self.max_size.map_or(false, |v| size > v) {
+            return false;
+        }
+        match meta.last_modified() {
+            Some(lm) => {
+                self.newer.map_or(true, |v| lm >= v) && self.older.map_or(true, |v| lm <= v)
+            }
+            None => self.newer.is_none() && self.older.is_none(),
+        }
+    }
+}
+
+/// Parse size like `1024`, `10K`, `5M` or `1G` into bytes.
+pub(crate) fn parse_size(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let (num, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(idx) => s.split_at(idx),
+        None => (s, ""),
+    };
+    let num: u64 = num.parse().map_err(|_| anyhow!("invalid size: {s}"))?;
+    let unit: u64 = match unit
+        .to_ascii_uppercase()
+        .trim_end_matches("IB")
+        .trim_end_matches('B')
+    {
+        "" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        "T" => 1 << 40,
+        _ => return Err(anyhow!("invalid size unit: {s}")),
+    };
+    num.checked_mul(unit)
+        .ok_or_else(|| anyhow!("size is too large: {s}"))
+}
+
+/// Parse duration like `30s`, `10m`, `2h` or `7d`.
+/// Parse the duration and return the time that is the duration before `now`.
+fn parse_time_before(now: DateTime<Utc>, s: &str) -> Result<DateTime<Utc>> {
+    now.checked_sub_signed(parse_duration(s)?)
+        .ok_or_else(|| anyhow!("duration is too large: {s}"))
+}
+
+pub(crate) fn parse_duration(s: &str) -> Result<chrono::Duration> {
+    let s = s.trim();
+    let (num, unit) =
+        s.split_at(s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len());
+    let num: u64 = num.parse().map_err(|_| anyhow!("invalid duration: {s}"))?;
+    let unit: u64 = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(anyhow!("invalid duration unit: {s}")),
+    };
+    let secs = num
+        .checked_mul(unit)
+        .ok_or_else(|| anyhow!("duration is too large: {s}"))?;
+    Ok(chrono::Duration::from_std(Duration::from_secs(secs))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("10K").unwrap(), 10 * 1024);
+        assert_eq!(parse_size("5MiB").unwrap(), 5 * 1024 * 1024);
+        assert_eq!(parse_size("1g").unwrap(), 1024 * 1024 * 1024);
+        assert!(parse_size("1X").is_err());
+        assert!(parse_size("M").is_err());
+        assert!(parse_size("16777216T").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30").unwrap().num_seconds(), 30);
+        assert_eq!(parse_duration("10m").unwrap().num_seconds(), 600);
+        assert_eq!(parse_duration("7d").unwrap().num_seconds(), 7 * 86400);
+        assert!(parse_duration("1w").is_err());
+        assert!(parse_duration("18446744073709551615d").is_err());
+
+        let now = Utc::now();
+        assert_eq!(
+            parse_time_before(now, "1h").unwrap(),
+            now - chrono::Duration::hours(1)
+        );
+        assert!(parse_time_before(now, "100000000d").is_err());
+    }
+}
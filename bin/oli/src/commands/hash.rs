@@ -0,0 +1,273 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use base64::engine::general_purpose;
+use base64::Engine;
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+use futures::TryStreamExt;
+use md5::Digest;
+use md5::Md5;
+use opendal::Metadata;
+use opendal::Operator;
+use sha2::Sha256;
+
+use crate::config::Config;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let algorithm = args
+        .get_one::<String>("algorithm")
+        .ok_or_else(|| anyhow!("missing algorithm"))?
+        .parse::<Algorithm>()?;
+
+    let target = args
+        .get_one::<String>("target")
+        .ok_or_else(|| anyhow!("missing target"))?;
+    let (op, path) = cfg.parse_location(target)?;
+
+    let digest = hash_file(&op, &path, algorithm).await?;
+    println!("{digest}  {target}");
+    Ok(())
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("compute checksum of object")
+        .arg(Arg::new("target").required(true))
+        .arg(
+            Arg::new("algorithm")
+                .long("algorithm")
+                .short('a')
+                .help("Checksum algorithm to use")
+                .value_parser(["md5", "sha256", "crc32c"])
+                .default_value("md5"),
+        )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Algorithm {
+    Md5,
+    Sha256,
+    Crc32c,
+}
+
+impl FromStr for Algorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "md5" => Ok(Algorithm::Md5),
+            "sha256" => Ok(Algorithm::Sha256),
+            "crc32c" => Ok(Algorithm::Crc32c),
+            v => Err(anyhow!("unsupported algorithm: {}", v)),
+        }
+    }
+}
+
+enum Hasher {
+    Md5(Md5),
+    Sha256(Sha256),
+    Crc32c(u32),
+}
+
+impl Hasher {
+    fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Md5 => Hasher::Md5(Md5::new()),
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algorithm::Crc32c => Hasher::Crc32c(0),
+        }
+    }
+
+    fn update(&mut self, bs: &[u8]) {
+        match self {
+            Hasher::Md5(h) => h.update(bs),
+            Hasher::Sha256(h) => h.update(bs),
+            Hasher::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, bs),
+        }
+    }
+
+    /// Returns the digest in lowercase hex.
+    fn finish(self) -> String {
+        match self {
+            Hasher::Md5(h) => to_hex(&h.finalize()),
+            Hasher::Sha256(h) => to_hex(&h.finalize()),
+            Hasher::Crc32c(crc) => to_hex(&crc.to_be_bytes()),
+        }
+    }
+}
+
+fn to_hex(bs: &[u8]) -> String {
+    bs.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Compute the checksum of file by streaming its content.
+pub(crate) async fn hash_file(op: &Operator, path: &str, algorithm: Algorithm) -> Result<String> {
+    let mut hasher = Hasher::new(algorithm);
+    let mut r = op.reader(path).await?;
+    while let Some(bs) = r.try_next().await? {
+        hasher.update(&bs);
+    }
+    Ok(hasher.finish())
+}
+
+/// Get md5 of the file provided by the backend in lowercase hex.
+///
+/// Only `Content-MD5` is trusted. Etags are never used even if they look
+/// like md5 digests, because they are not md5 of the content for multipart
+/// uploads, encrypted objects and many services.
+fn known_md5(meta: &Metadata) -> Option<String> {
+    let v = meta.content_md5()?;
+    if let Ok(bs) = general_purpose::STANDARD.decode(v) {
+        if bs.len() == 16 {
+            return Some(to_hex(&bs));
+        }
+    }
+    if is_md5_hex(v) {
+        return Some(v.to_lowercase());
+    }
+    None
+}
+
+fn is_md5_hex(v: &str) -> bool {
+    v.len() == 32 && v.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Verify that destination has the same content as source.
+///
+/// `Content-MD5` provided by backends is used if possible, otherwise the
+/// content of files will be read to compute md5.
+pub(crate) async fn verify_file(
+    src_op: &Operator,
+    src_path: &str,
+    dst_op: &Operator,
+    dst_path: &str,
+) -> Result<()> {
+    let src_meta = src_op.stat(src_path).await?;
+    let dst_meta = dst_op.stat(dst_path).await?;
+    if src_meta.content_length() != dst_meta.content_length() {
+        return Err(anyhow!(
+            "checksum mismatch: {} has {} bytes but {} has {} bytes",
+            src_path,
+            src_meta.content_length(),
+            dst_path,
+            dst_meta.content_length()
+        ));
+    }
+
+    let src_md5 = match known_md5(&src_meta) {
+        Some(v) => v,
+        None => hash_file(src_op, src_path, Algorithm::Md5).await?,
+    };
+    let dst_md5 = match known_md5(&dst_meta) {
+        Some(v) => v,
+        None => hash_file(dst_op, dst_path, Algorithm::Md5).await?,
+    };
+    if src_md5 != dst_md5 {
+        return Err(anyhow!(
+            "checksum mismatch: {} has md5 {} but {} has md5 {}",
+            src_path,
+            src_md5,
+            dst_path,
+            dst_md5
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use opendal::services;
+    use opendal::EntryMode;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hash_file() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("hello", "hello").await?;
+
+        let cases = [
+            (Algorithm::Md5, "5d41402abc4b2a76b9719d911017c592"),
+            (
+                Algorithm::Sha256,
+                "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+            ),
+            (Algorithm::Crc32c, "9a71bb4c"),
+        ];
+        for (algorithm, expected) in cases {
+            assert_eq!(hash_file(&op, "hello", algorithm).await?, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_known_md5() {
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_md5("XUFAKrxLKna5cZ2REBfFkg==".to_string())
+            .with_etag("\"ignored\"".to_string());
+        assert_eq!(
+            known_md5(&meta).as_deref(),
+            Some("5d41402abc4b2a76b9719d911017c592")
+        );
+
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_md5("5D41402ABC4B2A76B9719D911017C592".to_string());
+        assert_eq!(
+            known_md5(&meta).as_deref(),
+            Some("5d41402abc4b2a76b9719d911017c592")
+        );
+
+        // Etags are not trusted even if they look like md5.
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_md5(String::new())
+            .with_etag("\"5d41402abc4b2a76b9719d911017c592\"".to_string());
+        assert_eq!(known_md5(&meta), None);
+
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_md5(String::new())
+            .with_etag("\"5d41402abc4b2a76b9719d911017c592-2\"".to_string());
+        assert_eq!(known_md5(&meta), None);
+    }
+
+    #[tokio::test]
+    async fn test_verify_file() -> Result<()> {
+        let src_op = Operator::new(services::Memory::default())?.finish();
+        let dst_op = Operator::new(services::Memory::default())?.finish();
+        src_op.write("file", "hello").await?;
+
+        dst_op.write("file", "hello").await?;
+        verify_file(&src_op, "file", &dst_op, "file").await?;
+
+        dst_op.write("file", "world").await?;
+        let err = verify_file(&src_op, "file", &dst_op, "file")
+            .await
+            .expect_err("must mismatch");
+        assert!(err.to_string().contains("checksum mismatch"));
+        Ok(())
+    }
+}
@@ -24,8 +24,12 @@ use clap::ArgAction;
 use clap::ArgMatches;
 use clap::Command;
 use futures::TryStreamExt;
+use opendal::Metakey;
 
 use crate::config::Config;
+use crate::format::output_args;
+use crate::format::Entry;
+use crate::format::Output;
 
 pub async fn main(args: &ArgMatches) -> Result<()> {
     let config_path = args
@@ -34,34 +38,56 @@ pub async fn main(args: &ArgMatches) -> Result<()> {
     let cfg = Config::load(config_path)?;
 
     let recursive = args.get_flag("recursive");
+    let long = args.get_flag("long");
+    let output = Output::from_args(args);
 
     let target = args
         .get_one::<String>("target")
         .ok_or_else(|| anyhow!("missing target"))?;
     let (op, path) = cfg.parse_location(target)?;
 
-    if !recursive {
-        let mut ds = op.list(&path).await?;
-        while let Some(de) = ds.try_next().await? {
-            println!("{}", de.name());
+    let mut ds = if recursive {
+        op.scan(&path).await?
+    } else {
+        op.list(&path).await?
+    };
+    let mut printer = output.printer();
+    while let Some(de) = ds.try_next().await? {
+        let name = if recursive { de.path() } else { de.name() };
+        if !long {
+            let meta = op.metadata(&de, Metakey::Mode).await?;
+            printer.print(Entry::new(name, &meta), |v| v.path.clone())?;
+            continue;
         }
-        return Ok(());
-    }
 
-    let mut ds = op.scan(&path).await?;
-    while let Some(de) = ds.try_next().await? {
-        println!("{}", de.path());
+        let meta = op
+            .metadata(
+                &de,
+                Metakey::Mode | Metakey::ContentLength | Metakey::LastModified | Metakey::Etag,
+            )
+            .await?;
+        printer.print(Entry::long(name, &meta), |v| v.to_long_text(&output))?;
     }
-    Ok(())
+    printer.finish()
 }
 
 pub fn cli(cmd: Command) -> Command {
-    cmd.about("ls").arg(Arg::new("target").required(true)).arg(
-        Arg::new("recursive")
-            .required(false)
-            .long("recursive")
-            .short('r')
-            .help("List recursively")
-            .action(ArgAction::SetTrue),
-    )
+    output_args(cmd.about("ls"))
+        .arg(Arg::new("target").required(true))
+        .arg(
+            Arg::new("recursive")
+                .required(false)
+                .long("recursive")
+                .short('r')
+                .help("List recursively")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("long")
+                .required(false)
+                .long("long")
+                .short('l')
+                .help("Show mode, size, last modified and etag")
+                .action(ArgAction::SetTrue),
+        )
 }
@@ -33,9 +33,23 @@
 //! }
 //! ```
 
+pub mod bench;
 pub mod cat;
 pub mod cli;
+pub mod config;
 pub mod cp;
+pub mod du;
+pub mod find;
+pub mod hash;
 pub mod ls;
+pub mod mkdir;
+#[cfg(target_os = "linux")]
+pub mod mount;
+pub mod mv;
+pub mod put;
 pub mod rm;
+pub mod serve;
+pub mod shell;
 pub mod stat;
+pub mod sync;
+pub mod tee;
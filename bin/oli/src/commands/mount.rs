@@ -0,0 +1,1133 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use clap::value_parser;
+use clap::Arg;
+use clap::ArgAction;
+use clap::ArgMatches;
+use clap::Command;
+use fuser::FileAttr;
+use fuser::FileType;
+use fuser::Filesystem;
+use fuser::MountOption;
+use fuser::ReplyAttr;
+use fuser::ReplyCreate;
+use fuser::ReplyData;
+use fuser::ReplyDirectory;
+use fuser::ReplyEmpty;
+use fuser::ReplyEntry;
+use fuser::ReplyOpen;
+use fuser::ReplyWrite;
+use fuser::Request;
+use fuser::TimeOrNow;
+use futures::AsyncReadExt;
+use futures::Future;
+use futures::TryStreamExt;
+use libc::c_int;
+use opendal::raw::oio;
+use opendal::EntryMode;
+use opendal::ErrorKind;
+use opendal::Metadata;
+use opendal::Metakey;
+use opendal::Operator;
+use tokio::runtime::Handle;
+
+use crate::config::Config;
+use crate::utils::normalize_dir;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let read_only = args.get_flag("read-only");
+    let ttl = *args
+        .get_one::<u64>("cache-ttl")
+        .ok_or_else(|| anyhow!("missing cache ttl"))?;
+
+    let target = args
+        .get_one::<String>("target")
+        .ok_or_else(|| anyhow!("missing target"))?;
+    let (op, path) = cfg.parse_location(target)?;
+    let mountpoint = args
+        .get_one::<PathBuf>("mountpoint")
+        .ok_or_else(|| anyhow!("missing mountpoint"))?;
+
+    let fs = OpFs::new(
+        op,
+        normalize_dir(path),
+        Handle::current(),
+        read_only,
+        Duration::from_secs(ttl),
+    );
+    let mut options = vec![
+        MountOption::FSName(target.to_string()),
+        MountOption::Subtype("oli".to_string()),
+        MountOption::DefaultPermissions,
+    ];
+    if read_only {
+        options.push(MountOption::RO);
+    }
+
+    let session = fuser::spawn_mount2(fs, mountpoint, &options)?;
+    println!(
+        "Mounted {target} at {}, press Ctrl-C to unmount",
+        mountpoint.display()
+    );
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            res = &mut ctrl_c => {
+                res?;
+                break;
+            }
+            // The filesystem could be unmounted by others like `fusermount -u`.
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {
+                if session.guard.is_finished() {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Unmount and wait for the session to exit.
+    tokio::task::block_in_place(|| session.join());
+    Ok(())
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("mount location as a local filesystem via FUSE")
+        .arg(Arg::new("target").required(true))
+        .arg(
+            Arg::new("mountpoint")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("read-only")
+                .long("read-only")
+                .help("Mount the filesystem as read-only")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("cache-ttl")
+                .long("cache-ttl")
+                .help("Seconds to cache metadata of files and dirs")
+                .default_value("1")
+                .value_parser(value_parser!(u64)),
+        )
+}
+
+/// Inodes maps inode numbers to paths relative to the mounted dir.
+///
+/// Paths of dirs don't have the trailing `/`, and the root is the empty path.
+struct Inodes {
+    paths: HashMap<u64, String>,
+    inos: HashMap<String, u64>,
+    next: u64,
+}
+
+impl Inodes {
+    const ROOT: u64 = fuser::FUSE_ROOT_ID;
+
+    fn new() -> Self {
+        Inodes {
+            paths: HashMap::from([(Self::ROOT, String::new())]),
+            inos: HashMap::from([(String::new(), Self::ROOT)]),
+            next: Self::ROOT + 1,
+        }
+    }
+
+    fn path(&self, ino: u64) -> Option<&str> {
+        self.paths.get(&ino).map(String::as_str)
+    }
+
+    /// Get the inode of path, allocate a new one if not exist.
+    fn ino(&mut self, path: &str) -> u64 {
+        if let Some(ino) = self.inos.get(path) {
+            return *ino;
+        }
+        let ino = self.next;
+        self.next += 1;
+        self.paths.insert(ino, path.to_string());
+        self.inos.insert(path.to_string(), ino);
+        ino
+    }
+
+    fn remove(&mut self, path: &str) {
+        if let Some(ino) = self.inos.remove(path) {
+            self.paths.remove(&ino);
+        }
+    }
+
+    /// Paths of `path` itself and all entries under it.
+    fn descendants(&self, path: &str) -> Vec<String> {
+        let prefix = format!("{path}/");
+        self.inos
+            .keys()
+            .filter(|v| *v == path || v.starts_with(&prefix))
+            .cloned()
+            .collect()
+    }
+
+    /// Move inodes of `from` and all entries under it to `to`, the old
+    /// inodes of `to` are dropped.
+    fn rename(&mut self, from: &str, to: &str) {
+        for path in self.descendants(to) {
+            self.remove(&path);
+        }
+        for path in self.descendants(from) {
+            if let Some(ino) = self.inos.remove(&path) {
+                let path = format!("{to}{}", &path[from.len()..]);
+                self.paths.insert(ino, path.clone());
+                self.inos.insert(path, ino);
+            }
+        }
+    }
+}
+
+fn join_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{parent}/{name}")
+    }
+}
+
+enum FileHandle {
+    /// Content of file is buffered and written back on flush.
+    Writer(WriteBuffer),
+    /// Entries of dir fetched on opendir.
+    Dir(Vec<(u64, FileType, String)>),
+}
+
+/// WriteBuffer keeps content of a file being written in a temp file, so
+/// that large files and writes at large offsets will not take memory.
+struct WriteBuffer {
+    ino: u64,
+    file: File,
+    size: u64,
+    /// Content of the object will be loaded on first access, so that files
+    /// opened without `O_TRUNC` will not be read if never written.
+    loaded: bool,
+    dirty: bool,
+}
+
+impl WriteBuffer {
+    fn new(ino: u64) -> io::Result<Self> {
+        Ok(WriteBuffer {
+            ino,
+            file: tempfile::tempfile()?,
+            size: 0,
+            loaded: false,
+            dirty: false,
+        })
+    }
+
+    /// Load content of the object at path if not loaded.
+    fn load(&mut self, op: &Operator, rt: &Handle, path: &str) -> opendal::Result<()> {
+        if self.loaded {
+            return Ok(());
+        }
+        rt.block_on(async {
+            let mut r = op.reader(path).await?;
+            while let Some(bs) = r.try_next().await.map_err(io_error)? {
+                self.file.write_all_at(&bs, self.size).map_err(io_error)?;
+                self.size += bs.len() as u64;
+            }
+            Ok::<_, opendal::Error>(())
+        })?;
+        self.loaded = true;
+        Ok(())
+    }
+
+    fn read_at(&self, offset: u64, size: u32) -> io::Result<Vec<u8>> {
+        let start = offset.min(self.size);
+        let end = (start + size as u64).min(self.size);
+        let mut buf = vec![0; (end - start) as usize];
+        self.file.read_exact_at(&mut buf, start)?;
+        Ok(buf)
+    }
+
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> io::Result<()> {
+        self.file.write_all_at(data, offset)?;
+        self.size = self.size.max(offset + data.len() as u64);
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Truncate the file to size, content must be loaded unless size is 0.
+    fn truncate(&mut self, size: u64) -> io::Result<()> {
+        debug_assert!(self.loaded || size == 0, "content must be loaded");
+        self.file.set_len(size)?;
+        self.size = size;
+        self.loaded = true;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Write the content back to path if it's dirty.
+    fn flush(&mut self, op: &Operator, rt: &Handle, path: &str) -> opendal::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let chunk = oio::DEFAULT_APPEND_CHUNK_SIZE as u64;
+        rt.block_on(async {
+            if self.size <= chunk {
+                let bs = self.read_at(0, self.size as u32).map_err(io_error)?;
+                return op.write(path, bs).await;
+            }
+
+            let mut w = op.writer(path).await?;
+            let res = async {
+                let mut offset = 0;
+                while offset < self.size {
+                    let mut buf = vec![0; chunk.min(self.size - offset) as usize];
+                    self.file
+                        .read_exact_at(&mut buf, offset)
+                        .map_err(io_error)?;
+                    offset += buf.len() as u64;
+                    w.append(buf).await?;
+                }
+                w.close().await
+            }
+            .await;
+            if res.is_err() {
+                let _ = w.abort().await;
+            }
+            res
+        })?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+fn io_error(err: io::Error) -> opendal::Error {
+    opendal::Error::new(ErrorKind::Unexpected, &err.to_string())
+}
+
+/// Convert error of opendal into errno.
+fn errno(err: &opendal::Error) -> c_int {
+    match err.kind() {
+        ErrorKind::NotFound => libc::ENOENT,
+        ErrorKind::PermissionDenied => libc::EACCES,
+        ErrorKind::AlreadyExists => libc::EEXIST,
+        ErrorKind::IsADirectory => libc::EISDIR,
+        ErrorKind::NotADirectory => libc::ENOTDIR,
+        ErrorKind::Unsupported => libc::ENOSYS,
+        _ => libc::EIO,
+    }
+}
+
+/// Check the result and reply the errno if failed.
+macro_rules! try_reply {
+    ($res:expr, $reply:expr) => {
+        match $res {
+            Ok(v) => v,
+            Err(err) => {
+                log::debug!("fuse request failed: {err:?}");
+                $reply.error(errno(&err));
+                return;
+            }
+        }
+    };
+}
+
+/// Get the path of inode or reply `ENOENT`.
+macro_rules! path_or_reply {
+    ($fs:expr, $ino:expr, $reply:expr) => {
+        match $fs.inodes.path($ino) {
+            Some(path) => path.to_string(),
+            None => {
+                $reply.error(libc::ENOENT);
+                return;
+            }
+        }
+    };
+}
+
+/// Get the name as str or reply `EINVAL`.
+macro_rules! name_or_reply {
+    ($name:expr, $reply:expr) => {
+        match $name.to_str() {
+            Some(name) => name,
+            None => {
+                $reply.error(libc::EINVAL);
+                return;
+            }
+        }
+    };
+}
+
+/// OpFs exposes an [`Operator`] as a FUSE filesystem.
+///
+/// FUSE requests are handled in a dedicated thread, so async operations are
+/// run by blocking on the runtime handle.
+struct OpFs {
+    op: Operator,
+    /// The mounted dir in operator, empty or ends with `/`.
+    base: String,
+    rt: Handle,
+    read_only: bool,
+    ttl: Duration,
+    uid: u32,
+    gid: u32,
+
+    inodes: Inodes,
+    /// Cached attrs with the time they are fetched.
+    attrs: HashMap<u64, (FileAttr, Instant)>,
+    handles: HashMap<u64, FileHandle>,
+    next_fh: u64,
+}
+
+impl OpFs {
+    fn new(op: Operator, base: String, rt: Handle, read_only: bool, ttl: Duration) -> Self {
+        // SAFETY: getuid and getgid are always successful.
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        OpFs {
+            op,
+            base,
+            rt,
+            read_only,
+            ttl,
+            uid,
+            gid,
+            inodes: Inodes::new(),
+            attrs: HashMap::new(),
+            handles: HashMap::new(),
+            next_fh: 1,
+        }
+    }
+
+    fn block_on<F: Future>(&self, f: F) -> F::Output {
+        self.rt.block_on(f)
+    }
+
+    fn file_path(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+
+    fn dir_path(&self, path: &str) -> String {
+        if path.is_empty() && self.base.is_empty() {
+            "/".to_string()
+        } else if path.is_empty() {
+            self.base.clone()
+        } else {
+            format!("{}{}/", self.base, path)
+        }
+    }
+
+    fn add_handle(&mut self, handle: FileHandle) -> u64 {
+        let fh = self.next_fh;
+        self.next_fh += 1;
+        self.handles.insert(fh, handle);
+        fh
+    }
+
+    fn to_attr(&self, ino: u64, meta: &Metadata) -> FileAttr {
+        let (kind, perm, size) = match meta.mode() {
+            EntryMode::DIR => (FileType::Directory, 0o755, 0),
+            _ => (FileType::RegularFile, 0o644, meta.content_length()),
+        };
+        let mtime = meta
+            .last_modified()
+            .map(SystemTime::from)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        FileAttr {
+            ino,
+            size,
+            blocks: (size + 511) / 512,
+            atime: mtime,
+            mtime,
+            ctime: mtime,
+            crtime: mtime,
+            kind,
+            perm: if self.read_only { perm & 0o555 } else { perm },
+            nlink: if kind == FileType::Directory { 2 } else { 1 },
+            uid: self.uid,
+            gid: self.gid,
+            rdev: 0,
+            blksize: 4096,
+            flags: 0,
+        }
+    }
+
+    fn cache_attr(&mut self, ino: u64, meta: &Metadata) -> FileAttr {
+        let attr = self.to_attr(ino, meta);
+        self.attrs.insert(ino, (attr, Instant::now()));
+        attr
+    }
+
+    /// Stat the path as a file first, and then as a dir.
+    fn stat(&self, path: &str) -> opendal::Result<Metadata> {
+        if path.is_empty() {
+            return self.block_on(self.op.stat(&self.dir_path(path)));
+        }
+        match self.block_on(self.op.stat(&self.file_path(path))) {
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                self.block_on(self.op.stat(&self.dir_path(path)))
+            }
+            res => res,
+        }
+    }
+
+    /// Get attr of the inode, cached attr will be used if not expired.
+    fn attr(&mut self, ino: u64, path: &str) -> opendal::Result<FileAttr> {
+        let mut attr = match self.attrs.get(&ino) {
+            Some((attr, at)) if at.elapsed() < self.ttl => *attr,
+            _ => {
+                let meta = self.stat(path)?;
+                self.cache_attr(ino, &meta)
+            }
+        };
+        // Files being written should report the buffered size.
+        for handle in self.handles.values() {
+            if let FileHandle::Writer(wb) = handle {
+                if wb.ino == ino && wb.loaded {
+                    attr.size = wb.size;
+                    attr.blocks = (attr.size + 511) / 512;
+                }
+            }
+        }
+        Ok(attr)
+    }
+
+    /// Get the write buffer of handle with the path of its file, content
+    /// of the file will be loaded.
+    fn loaded_buffer(&mut self, fh: u64) -> opendal::Result<Option<&mut WriteBuffer>> {
+        let path = match self.handles.get(&fh) {
+            Some(FileHandle::Writer(wb)) => match self.inodes.path(wb.ino) {
+                Some(path) => self.file_path(path),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        match self.handles.get_mut(&fh) {
+            Some(FileHandle::Writer(wb)) => {
+                wb.load(&self.op, &self.rt, &path)?;
+                Ok(Some(wb))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Write the buffered content of the handle back if it's dirty.
+    fn flush_handle(&mut self, fh: u64) -> opendal::Result<()> {
+        let (ino, path) = match self.handles.get(&fh) {
+            Some(FileHandle::Writer(wb)) if wb.dirty => match self.inodes.path(wb.ino) {
+                Some(path) => (wb.ino, self.file_path(path)),
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        if let Some(FileHandle::Writer(wb)) = self.handles.get_mut(&fh) {
+            wb.flush(&self.op, &self.rt, &path)?;
+        }
+        self.attrs.remove(&ino);
+        Ok(())
+    }
+
+    /// Move the object from `from` to `to`, both of them are full paths.
+    async fn move_object(&self, from: &str, to: &str) -> opendal::Result<()> {
+        let info = self.op.info();
+        if info.can_rename() {
+            return self.op.rename(from, to).await;
+        }
+        if info.can_copy() {
+            self.op.copy(from, to).await?;
+        } else {
+            let mut r = self.op.reader(from).await?;
+            let mut w = self.op.writer(to).await?;
+            let res = async {
+                let mut buf = oio::AppendBuffer::new();
+                while let Some(bs) = r.try_next().await.map_err(io_error)? {
+                    if let Some(bs) = buf.push(bs) {
+                        w.append(bs).await?;
+                    }
+                }
+                if let Some(bs) = buf.finish() {
+                    w.append(bs).await?;
+                }
+                w.close().await
+            }
+            .await;
+            if res.is_err() {
+                let _ = w.abort().await;
+                return res;
+            }
+        }
+        self.op.delete(from).await
+    }
+
+    fn rename_file(&self, from: &str, to: &str) -> opendal::Result<()> {
+        let (from, to) = (self.file_path(from), self.file_path(to));
+        self.block_on(self.move_object(&from, &to))
+    }
+
+    /// Rename the dir by moving all entries under it, old dirs will be
+    /// removed after all files moved.
+    fn rename_dir(&self, from: &str, to: &str) -> opendal::Result<()> {
+        let (from, to) = (self.dir_path(from), self.dir_path(to));
+        self.block_on(async {
+            let mut entries = Vec::new();
+            let mut ds = self.op.scan(&from).await?;
+            while let Some(de) = ds.try_next().await? {
+                if de.path() != from {
+                    entries.push(de.path().to_string());
+                }
+            }
+            // Parents will be sorted before their children.
+            entries.sort();
+
+            self.op.create_dir(&to).await?;
+            for path in &entries {
+                let target = format!("{to}{}", &path[from.len()..]);
+                if path.ends_with('/') {
+                    self.op.create_dir(&target).await?;
+                } else {
+                    self.move_object(path, &target).await?;
+                }
+            }
+            for path in entries.iter().rev().filter(|v| v.ends_with('/')) {
+                self.op.delete(path).await?;
+            }
+            self.op.delete(&from).await
+        })
+    }
+}
+
+impl Filesystem for OpFs {
+    fn lookup(&mut self, _req: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let parent = path_or_reply!(self, parent, reply);
+        let path = join_path(&parent, name_or_reply!(name, reply));
+
+        let ino = self.inodes.ino(&path);
+        match self.attr(ino, &path) {
+            Ok(attr) => reply.entry(&self.ttl, &attr, 0),
+            Err(err) => {
+                if err.kind() == ErrorKind::NotFound {
+                    self.inodes.remove(&path);
+                }
+                reply.error(errno(&err))
+            }
+        }
+    }
+
+    fn getattr(&mut self, _req: &Request<'_>, ino: u64, reply: ReplyAttr) {
+        let path = path_or_reply!(self, ino, reply);
+        let attr = try_reply!(self.attr(ino, &path), reply);
+        reply.attr(&self.ttl, &attr);
+    }
+
+    fn setattr(
+        &mut self,
+        _req: &Request<'_>,
+        ino: u64,
+        _mode: Option<u32>,
+        _uid: Option<u32>,
+        _gid: Option<u32>,
+        size: Option<u64>,
+        _atime: Option<TimeOrNow>,
+        _mtime: Option<TimeOrNow>,
+        _ctime: Option<SystemTime>,
+        fh: Option<u64>,
+        _crtime: Option<SystemTime>,
+        _chgtime: Option<SystemTime>,
+        _bkuptime: Option<SystemTime>,
+        _flags: Option<u32>,
+        reply: ReplyAttr,
+    ) {
+        let path = path_or_reply!(self, ino, reply);
+
+        // Only truncate is supported, other attrs are ignored.
+        if let Some(size) = size {
+            if self.read_only {
+                reply.error(libc::EROFS);
+                return;
+            }
+            let is_writer = matches!(
+                fh.and_then(|fh| self.handles.get(&fh)),
+                Some(FileHandle::Writer(_))
+            );
+            if is_writer {
+                let fh = fh.expect("fh must be set for writer");
+                if size == 0 {
+                    if let Some(FileHandle::Writer(wb)) = self.handles.get_mut(&fh) {
+                        try_reply!(wb.truncate(0).map_err(io_error), reply);
+                    }
+                } else if let Some(wb) = try_reply!(self.loaded_buffer(fh), reply) {
+                    try_reply!(wb.truncate(size).map_err(io_error), reply);
+                }
+            } else {
+                let fp = self.file_path(&path);
+                let mut wb = try_reply!(WriteBuffer::new(ino).map_err(io_error), reply);
+                if size > 0 {
+                    try_reply!(wb.load(&self.op, &self.rt, &fp), reply);
+                }
+                try_reply!(wb.truncate(size).map_err(io_error), reply);
+                try_reply!(wb.flush(&self.op, &self.rt, &fp), reply);
+                self.attrs.remove(&ino);
+            }
+        }
+
+        let attr = try_reply!(self.attr(ino, &path), reply);
+        reply.attr(&self.ttl, &attr);
+    }
+
+    fn mkdir(
+        &mut self,
+        _req: &Request<'_>,
+        parent: u64,
+        name: &OsStr,
+        _mode: u32,
+        _umask: u32,
+        reply: ReplyEntry,
+    ) {
+        if self.read_only {
+            reply.error(libc::EROFS);
+            return;
+        }
+        let parent = path_or_reply!(self, parent, reply);
+        let path = join_path(&parent, name_or_reply!(name, reply));
+
+        try_reply!(
+            self.block_on(self.op.create_dir(&self.dir_path(&path))),
+            reply
+        );
+        let ino = self.inodes.ino(&path);
+        let attr = self.cache_attr(ino, &Metadata::new(EntryMode::DIR));
+        reply.entry(&self.ttl, &attr, 0);
+    }
+
+    fn unlink(&mut self, _req: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEmpty) {
+        if self.read_only {
+            reply.error(libc::EROFS);
+            return;
+        }
+        let parent = path_or_reply!(self, parent, reply);
+        let path = join_path(&parent, name_or_reply!(name, reply));
+
+        try_reply!(self.block_on(self.op.delete(&self.file_path(&path))), reply);
+        if let Some(ino) = self.inodes.inos.get(&path) {
+            self.attrs.remove(ino);
+        }
+        self.inodes.remove(&path);
+        reply.ok();
+    }
+
+    fn rmdir(&mut self, _req: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEmpty) {
+        if self.read_only {
+            reply.error(libc::EROFS);
+            return;
+        }
+        let parent = path_or_reply!(self, parent, reply);
+        let path = join_path(&parent, name_or_reply!(name, reply));
+        let dir = self.dir_path(&path);
+
+        let is_empty = try_reply!(
+            self.block_on(async {
+                let mut ds = self.op.list(&dir).await?;
+                while let Some(de) = ds.try_next().await? {
+                    if de.path() != dir {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }),
+            reply
+        );
+        if !is_empty {
+            reply.error(libc::ENOTEMPTY);
+            return;
+        }
+
+        try_reply!(self.block_on(self.op.delete(&dir)), reply);
+        if let Some(ino) = self.inodes.inos.get(&path) {
+            self.attrs.remove(ino);
+        }
+        self.inodes.remove(&path);
+        reply.ok();
+    }
+
+    fn rename(
+        &mut self,
+        _req: &Request<'_>,
+        parent: u64,
+        name: &OsStr,
+        newparent: u64,
+        newname: &OsStr,
+        _flags: u32,
+        reply: ReplyEmpty,
+    ) {
+        if self.read_only {
+            reply.error(libc::EROFS);
+            return;
+        }
+        let parent = path_or_reply!(self, parent, reply);
+        let from = join_path(&parent, name_or_reply!(name, reply));
+        let newparent = path_or_reply!(self, newparent, reply);
+        let to = join_path(&newparent, name_or_reply!(newname, reply));
+
+        let ino = self.inodes.ino(&from);
+        let attr = try_reply!(self.attr(ino, &from), reply);
+        if attr.kind == FileType::Directory {
+            try_reply!(self.rename_dir(&from, &to), reply);
+            // Attrs of all entries under the dir are outdated.
+            self.attrs.clear();
+        } else {
+            try_reply!(self.rename_file(&from, &to), reply);
+            if let Some(ino) = self.inodes.inos.get(&to) {
+                self.attrs.remove(ino);
+            }
+            self.attrs.remove(&ino);
+        }
+        self.inodes.rename(&from, &to);
+        reply.ok();
+    }
+
+    fn open(&mut self, _req: &Request<'_>, ino: u64, flags: i32, reply: ReplyOpen) {
+        // Reads are served by range reader directly, no handle is needed.
+        if flags & libc::O_ACCMODE == libc::O_RDONLY {
+            reply.opened(0, 0);
+            return;
+        }
+        if self.read_only {
+            reply.error(libc::EROFS);
+            return;
+        }
+
+        let mut wb = try_reply!(WriteBuffer::new(ino).map_err(io_error), reply);
+        if flags & libc::O_TRUNC != 0 {
+            try_reply!(wb.truncate(0).map_err(io_error), reply);
+        }
+        let fh = self.add_handle(FileHandle::Writer(wb));
+        reply.opened(fh, 0);
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request<'_>,
+        ino: u64,
+        fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        let offset = offset as u64;
+        // Content not loaded yet is still the same as the object.
+        if let Some(FileHandle::Writer(wb)) = self.handles.get(&fh) {
+            if wb.loaded {
+                let bs = try_reply!(wb.read_at(offset, size).map_err(io_error), reply);
+                reply.data(&bs);
+                return;
+            }
+        }
+
+        let path = path_or_reply!(self, ino, reply);
+        let attr = try_reply!(self.attr(ino, &path), reply);
+        if offset >= attr.size {
+            reply.data(&[]);
+            return;
+        }
+        let end = (offset + size as u64).min(attr.size);
+        let bs = try_reply!(
+            self.block_on(async {
+                let mut r = self
+                    .op
+                    .range_reader(&self.file_path(&path), offset..end)
+                    .await?;
+                let mut bs = Vec::with_capacity((end - offset) as usize);
+                r.read_to_end(&mut bs)
+                    .await
+                    .map_err(|err| opendal::Error::new(ErrorKind::Unexpected, &err.to_string()))?;
+                Ok(bs)
+            }),
+            reply
+        );
+        reply.data(&bs);
+    }
+
+    fn write(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        offset: i64,
+        data: &[u8],
+        _write_flags: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyWrite,
+    ) {
+        if offset < 0 {
+            reply.error(libc::EINVAL);
+            return;
+        }
+        match try_reply!(self.loaded_buffer(fh), reply) {
+            Some(wb) => {
+                try_reply!(wb.write_at(offset as u64, data).map_err(io_error), reply);
+                reply.written(data.len() as u32);
+            }
+            None => reply.error(libc::EBADF),
+        }
+    }
+
+    fn flush(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        _lock_owner: u64,
+        reply: ReplyEmpty,
+    ) {
+        try_reply!(self.flush_handle(fh), reply);
+        reply.ok();
+    }
+
+    fn release(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        _flush: bool,
+        reply: ReplyEmpty,
+    ) {
+        let res = self.flush_handle(fh);
+        self.handles.remove(&fh);
+        try_reply!(res, reply);
+        reply.ok();
+    }
+
+    fn fsync(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        _datasync: bool,
+        reply: ReplyEmpty,
+    ) {
+        try_reply!(self.flush_handle(fh), reply);
+        reply.ok();
+    }
+
+    fn opendir(&mut self, _req: &Request<'_>, ino: u64, _flags: i32, reply: ReplyOpen) {
+        let path = path_or_reply!(self, ino, reply);
+        let dir = self.dir_path(&path);
+
+        let children = try_reply!(
+            self.block_on(async {
+                let mut children = Vec::new();
+                let mut ds = self.op.list(&dir).await?;
+                while let Some(de) = ds.try_next().await? {
+                    // Some services will return the dir itself.
+                    if de.path() == dir {
+                        continue;
+                    }
+                    let meta = self
+                        .op
+                        .metadata(
+                            &de,
+                            Metakey::Mode | Metakey::ContentLength | Metakey::LastModified,
+                        )
+                        .await?;
+                    children.push((de.name().trim_end_matches('/').to_string(), meta));
+                }
+                Ok(children)
+            }),
+            reply
+        );
+
+        let parent_ino = match path.rsplit_once('/') {
+            Some((parent, _)) => self.inodes.ino(parent),
+            None => Inodes::ROOT,
+        };
+        let mut entries = vec![
+            (ino, FileType::Directory, ".".to_string()),
+            (parent_ino, FileType::Directory, "..".to_string()),
+        ];
+        for (name, meta) in children {
+            let child = self.inodes.ino(&join_path(&path, &name));
+            let attr = self.cache_attr(child, &meta);
+            entries.push((child, attr.kind, name));
+        }
+
+        let fh = self.add_handle(FileHandle::Dir(entries));
+        reply.opened(fh, 0);
+    }
+
+    fn readdir(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        offset: i64,
+        mut reply: ReplyDirectory,
+    ) {
+        let entries = match self.handles.get(&fh) {
+            Some(FileHandle::Dir(entries)) => entries,
+            _ => {
+                reply.error(libc::EBADF);
+                return;
+            }
+        };
+        for (i, (ino, kind, name)) in entries.iter().enumerate().skip(offset as usize) {
+            // The offset is used by kernel to request the next entry.
+            if reply.add(*ino, (i + 1) as i64, *kind, name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+
+    fn releasedir(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        _flags: i32,
+        reply: ReplyEmpty,
+    ) {
+        self.handles.remove(&fh);
+        reply.ok();
+    }
+
+    fn create(
+        &mut self,
+        _req: &Request<'_>,
+        parent: u64,
+        name: &OsStr,
+        _mode: u32,
+        _umask: u32,
+        _flags: i32,
+        reply: ReplyCreate,
+    ) {
+        if self.read_only {
+            reply.error(libc::EROFS);
+            return;
+        }
+        let parent = path_or_reply!(self, parent, reply);
+        let path = join_path(&parent, name_or_reply!(name, reply));
+
+        // Create an empty file so that it's visible before flushed.
+        try_reply!(
+            self.block_on(self.op.write(&self.file_path(&path), Vec::new())),
+            reply
+        );
+        let ino = self.inodes.ino(&path);
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(0)
+            .with_last_modified(chrono::Utc::now());
+        let attr = self.cache_attr(ino, &meta);
+        let mut wb = try_reply!(WriteBuffer::new(ino).map_err(io_error), reply);
+        wb.loaded = true;
+        let fh = self.add_handle(FileHandle::Writer(wb));
+        reply.created(&self.ttl, &attr, 0, fh, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inodes() {
+        let mut inodes = Inodes::new();
+        assert_eq!(inodes.path(Inodes::ROOT), Some(""));
+
+        let ino = inodes.ino("a/b");
+        assert_eq!(inodes.ino("a/b"), ino);
+        assert_eq!(inodes.path(ino), Some("a/b"));
+
+        let other = inodes.ino("c");
+        inodes.rename("a/b", "c");
+        assert_eq!(inodes.path(ino), Some("c"));
+        assert_eq!(inodes.path(other), None);
+        assert_eq!(inodes.ino("c"), ino);
+
+        inodes.remove("c");
+        assert_eq!(inodes.path(ino), None);
+
+        let dir = inodes.ino("d");
+        let child = inodes.ino("d/e");
+        let other = inodes.ino("f/g");
+        inodes.rename("d", "f");
+        assert_eq!(inodes.path(dir), Some("f"));
+        assert_eq!(inodes.path(child), Some("f/e"));
+        assert_eq!(inodes.path(other), None);
+    }
+
+    #[test]
+    fn test_write_buffer() -> Result<()> {
+        let mut wb = WriteBuffer::new(1)?;
+        wb.truncate(0)?;
+        // Writes at large offsets are sparse in the temp file.
+        wb.write_at(1 << 40, b"hello")?;
+        assert_eq!(wb.size, (1 << 40) + 5);
+        assert_eq!(wb.read_at(1 << 40, 100)?, b"hello");
+        assert_eq!(wb.read_at((1 << 40) - 2, 3)?, b"\0\0h");
+
+        wb.truncate(3)?;
+        assert_eq!(wb.read_at(0, 100)?, b"\0\0\0");
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_write_buffer_load_and_flush() -> Result<()> {
+        let op = Operator::new(opendal::services::Memory::default())?.finish();
+        op.write("a", "Hello, World!").await?;
+
+        let rt = Handle::current();
+        let op2 = op.clone();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut wb = WriteBuffer::new(1)?;
+            wb.load(&op2, &rt, "a")?;
+            wb.write_at(7, b"OpenDAL!")?;
+            wb.flush(&op2, &rt, "a")?;
+            assert!(!wb.dirty);
+            Ok(())
+        })
+        .await??;
+
+        assert_eq!(op.read("a").await?, b"Hello, OpenDAL!");
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_path() {
+        assert_eq!(join_path("", "a"), "a");
+        assert_eq!(join_path("a", "b"), "a/b");
+    }
+}
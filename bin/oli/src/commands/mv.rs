@@ -0,0 +1,68 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+
+use super::cp::copy_file;
+use super::cp::is_same_backend;
+use super::cp::CopyOptions;
+use crate::config::Config;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let src = args
+        .get_one::<String>("source")
+        .ok_or_else(|| anyhow!("missing source"))?;
+    let (src_op, src_path) = cfg.parse_location(src)?;
+
+    let dst = args
+        .get_one::<String>("destination")
+        .ok_or_else(|| anyhow!("missing destination"))?;
+    let (dst_op, dst_path) = cfg.parse_location(dst)?;
+
+    let same_backend = is_same_backend(&cfg, src, dst)?;
+    if same_backend && src_op.info().can_rename() {
+        src_op.rename(&src_path, &dst_path).await?;
+        return Ok(());
+    }
+
+    // Fallback to copy and delete if rename is not supported.
+    let opts = CopyOptions {
+        native: same_backend && src_op.info().can_copy(),
+        jobs: 1,
+        chunk_size: u64::MAX,
+    };
+    copy_file(&src_op, &src_path, &dst_op, &dst_path, &opts).await?;
+    src_op.delete(&src_path).await?;
+    Ok(())
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("move object")
+        .arg(Arg::new("source").required(true))
+        .arg(Arg::new("destination").required(true))
+}
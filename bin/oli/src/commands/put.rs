@@ -0,0 +1,87 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use bytes::Bytes;
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+use opendal::raw::oio;
+use opendal::Operator;
+use tokio::io;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+
+use crate::config::Config;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let target = args
+        .get_one::<String>("target")
+        .ok_or_else(|| anyhow!("missing target"))?;
+    let (op, path) = cfg.parse_location(target)?;
+
+    write_stdin(&op, &path, false).await
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("write stdin into object")
+        .arg(Arg::new("target").required(true))
+}
+
+/// Stream stdin into the object at path, and copy it to stdout too if
+/// `echo` is set.
+pub(crate) async fn write_stdin(op: &Operator, path: &str, echo: bool) -> Result<()> {
+    let mut stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let mut w = op.writer(path).await?;
+    let res = async {
+        let mut buf = oio::AppendBuffer::new();
+        let mut chunk = vec![0; 64 * 1024];
+        loop {
+            let n = stdin.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            if echo {
+                stdout.write_all(&chunk[..n]).await?;
+            }
+            if let Some(bs) = buf.push(Bytes::copy_from_slice(&chunk[..n])) {
+                w.append(bs).await?;
+            }
+        }
+        if let Some(bs) = buf.finish() {
+            w.append(bs).await?;
+        }
+        stdout.flush().await?;
+        w.close().await?;
+        Ok(())
+    }
+    .await;
+
+    if res.is_err() {
+        let _ = w.abort().await;
+    }
+    res
+}
@@ -0,0 +1,109 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use hyper::header;
+use hyper::Body;
+use hyper::Method;
+use hyper::Request;
+use hyper::Response;
+use hyper::StatusCode;
+use opendal::Metakey;
+use opendal::Operator;
+
+use super::decode_path;
+use super::get_object;
+use super::put_object;
+use crate::format::Entry;
+
+pub async fn handle(op: &Operator, req: Request<Body>) -> Result<Response<Body>> {
+    let path = decode_path(req.uri().path())?;
+    let is_dir = path.is_empty() || path.ends_with('/');
+
+    match *req.method() {
+        Method::GET if is_dir => {
+            let mut entries = Vec::new();
+            let mut ds = op.list(&path).await?;
+            while let Some(de) = ds.try_next().await? {
+                let meta = op.metadata(&de, Metakey::Mode).await?;
+                entries.push(Entry::new(de.name(), &meta));
+            }
+            Ok(Response::builder()
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_vec(&entries)?))?)
+        }
+        Method::GET | Method::HEAD => {
+            let head_only = req.method() == Method::HEAD;
+            get_object(op, &path, req.headers(), head_only).await
+        }
+        Method::PUT if is_dir => {
+            op.create_dir(&path).await?;
+            Ok(status(StatusCode::CREATED))
+        }
+        Method::PUT => {
+            put_object(op, &path, req.into_body()).await?;
+            Ok(status(StatusCode::CREATED))
+        }
+        Method::DELETE => {
+            op.delete(&path).await?;
+            Ok(status(StatusCode::NO_CONTENT))
+        }
+        _ => Ok(status(StatusCode::METHOD_NOT_ALLOWED)),
+    }
+}
+
+fn status(code: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(code)
+        .body(Body::empty())
+        .expect("response must be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use opendal::services;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_http() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        let req = Request::put("/dir/a.txt").body(Body::from("Hello, World!"))?;
+        assert_eq!(handle(&op, req).await?.status(), StatusCode::CREATED);
+
+        let req = Request::get("/dir/a.txt")
+            .header(header::RANGE, "bytes=7-")
+            .body(Body::empty())?;
+        let resp = handle(&op, req).await?;
+        assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(hyper::body::to_bytes(resp.into_body()).await?, "World!");
+
+        let req = Request::get("/dir/").body(Body::empty())?;
+        let resp = handle(&op, req).await?;
+        assert_eq!(
+            hyper::body::to_bytes(resp.into_body()).await?,
+            r#"[{"path":"a.txt","mode":"file"}]"#
+        );
+
+        let req = Request::delete("/dir/a.txt").body(Body::empty())?;
+        assert_eq!(handle(&op, req).await?.status(), StatusCode::NO_CONTENT);
+        assert!(!op.is_exist("dir/a.txt").await?);
+        Ok(())
+    }
+}
@@ -0,0 +1,387 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Serve exposes an operator through a local HTTP server.
+//!
+//! - `http`: plain HTTP, `GET` on a dir returns its entries as JSON.
+//! - `webdav`: WebDAV class 1 without locking.
+//! - `s3`: S3 compatible API in path style, requests are not authenticated.
+
+mod http;
+mod s3;
+mod webdav;
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use bytes::Bytes;
+use clap::value_parser;
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+use futures::Stream;
+use futures::TryStreamExt;
+use hyper::header;
+use hyper::service::make_service_fn;
+use hyper::service::service_fn;
+use hyper::Body;
+use hyper::HeaderMap;
+use hyper::Request;
+use hyper::Response;
+use hyper::Server;
+use hyper::StatusCode;
+use opendal::raw::oio;
+use opendal::ErrorKind;
+use opendal::Metadata;
+use opendal::Operator;
+use opendal::Writer;
+use percent_encoding::percent_decode_str;
+
+use crate::config::Config;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let profile = args
+        .get_one::<String>("profile")
+        .ok_or_else(|| anyhow!("missing profile"))?;
+    let (op, _) = cfg.parse_location(&format!("{profile}://"))?;
+    let protocol = args
+        .get_one::<String>("protocol")
+        .ok_or_else(|| anyhow!("missing protocol"))?
+        .parse::<Protocol>()?;
+    let listen = args
+        .get_one::<SocketAddr>("listen")
+        .ok_or_else(|| anyhow!("missing listen address"))?;
+
+    let make_svc = make_service_fn(move |_| {
+        let op = op.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let op = op.clone();
+                async move { Ok::<_, Infallible>(protocol.handle(&op, req).await) }
+            }))
+        }
+    });
+
+    let server = Server::try_bind(listen)?.serve(make_svc);
+    println!("Serving {profile} over {protocol:?} on http://{listen}");
+    server.await?;
+    Ok(())
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("serve the profile over http, webdav or s3")
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .help("Name of the profile to serve")
+                .required(true),
+        )
+        .arg(
+            Arg::new("protocol")
+                .long("protocol")
+                .help("Protocol to serve")
+                .default_value("http")
+                .value_parser(["http", "webdav", "s3"]),
+        )
+        .arg(
+            Arg::new("listen")
+                .long("listen")
+                .help("Address to listen on")
+                .default_value("127.0.0.1:8080")
+                .value_parser(value_parser!(SocketAddr)),
+        )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Http,
+    Webdav,
+    S3,
+}
+
+impl FromStr for Protocol {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "http" => Ok(Protocol::Http),
+            "webdav" => Ok(Protocol::Webdav),
+            "s3" => Ok(Protocol::S3),
+            v => Err(anyhow!("unknown protocol: {v}")),
+        }
+    }
+}
+
+impl Protocol {
+    /// Handle the request, errors will be converted into responses of the
+    /// protocol.
+    pub async fn handle(&self, op: &Operator, req: Request<Body>) -> Response<Body> {
+        let res = match self {
+            Protocol::Http => http::handle(op, req).await,
+            Protocol::Webdav => webdav::handle(op, req).await,
+            Protocol::S3 => s3::handle(op, req).await,
+        };
+
+        res.unwrap_or_else(|err| {
+            let status = status_of(&err);
+            let body = match self {
+                Protocol::S3 => s3::error_body(status, &err),
+                _ => Body::from(err.to_string()),
+            };
+            Response::builder()
+                .status(status)
+                .body(body)
+                .expect("response must be valid")
+        })
+    }
+}
+
+/// Map the error into http status code.
+fn status_of(err: &anyhow::Error) -> StatusCode {
+    if let Some(err) = err.downcast_ref::<RequestError>() {
+        return err.0;
+    }
+    match err.downcast_ref::<opendal::Error>().map(|e| e.kind()) {
+        Some(ErrorKind::NotFound) => StatusCode::NOT_FOUND,
+        Some(ErrorKind::PermissionDenied) => StatusCode::FORBIDDEN,
+        Some(ErrorKind::Unsupported) => StatusCode::NOT_IMPLEMENTED,
+        Some(ErrorKind::IsADirectory) | Some(ErrorKind::NotADirectory) => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// RequestError means the request is invalid.
+#[derive(Debug)]
+struct RequestError(StatusCode, &'static str);
+
+impl Display for RequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.1)
+    }
+}
+
+impl Error for RequestError {}
+
+/// Decode the percent encoded path of request without the leading `/`.
+///
+/// Paths with `..` are rejected after decoding, so that requests can't
+/// escape from the root of operator.
+fn decode_path(path: &str) -> Result<String> {
+    let path = percent_decode_str(path)
+        .decode_utf8()
+        .map_err(|_| RequestError(StatusCode::BAD_REQUEST, "path is not valid utf-8"))?;
+    check_path(&path)?;
+    Ok(path.trim_start_matches('/').to_string())
+}
+
+/// Reject paths with `..`, all paths taken from requests like keys in
+/// body or prefixes in query must be checked before used.
+fn check_path(path: &str) -> Result<()> {
+    if path.split(['/', '\\']).any(|v| v == "..") {
+        return Err(RequestError(StatusCode::BAD_REQUEST, "path must not contain '..'").into());
+    }
+    Ok(())
+}
+
+/// Parse the range header into `[start, end)` against the object size.
+///
+/// Only single range is supported, `None` means the whole object.
+fn parse_range(headers: &HeaderMap, size: u64) -> Result<Option<(u64, u64)>> {
+    let range = match headers.get(header::RANGE) {
+        Some(v) => v.to_str()?,
+        None => return Ok(None),
+    };
+    let invalid = || {
+        RequestError(
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            "range is not satisfiable",
+        )
+    };
+
+    let (start, end) = range
+        .strip_prefix("bytes=")
+        .and_then(|v| v.split_once('-'))
+        .filter(|_| !range.contains(','))
+        .ok_or_else(invalid)?;
+    let (start, end) = match (start, end) {
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().map_err(|_| invalid())?;
+            (size.saturating_sub(suffix), size)
+        }
+        (start, "") => (start.parse().map_err(|_| invalid())?, size),
+        (start, end) => (
+            start.parse().map_err(|_| invalid())?,
+            end.parse::<u64>()
+                .map_err(|_| invalid())?
+                .saturating_add(1)
+                .min(size),
+        ),
+    };
+    if start >= end {
+        return Err(invalid().into());
+    }
+    Ok(Some((start, end)))
+}
+
+/// Format time into HTTP date.
+fn format_http_date(meta: &Metadata) -> Option<String> {
+    meta.last_modified()
+        .map(|v| v.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+}
+
+/// Serve object content with support of `Range`.
+async fn get_object(
+    op: &Operator,
+    path: &str,
+    headers: &HeaderMap,
+    head_only: bool,
+) -> Result<Response<Body>> {
+    let meta = op.stat(path).await?;
+    if meta.mode().is_dir() {
+        return Err(RequestError(StatusCode::BAD_REQUEST, "path is a dir").into());
+    }
+
+    let size = meta.content_length();
+    let mut builder = Response::builder().header(header::ACCEPT_RANGES, "bytes");
+    if let Some(etag) = meta.etag() {
+        builder = builder.header(header::ETAG, etag);
+    }
+    if let Some(v) = format_http_date(&meta) {
+        builder = builder.header(header::LAST_MODIFIED, v);
+    }
+    if let Some(v) = meta.content_type() {
+        builder = builder.header(header::CONTENT_TYPE, v);
+    }
+
+    let (status, start, end) = match parse_range(headers, size)? {
+        Some((start, end)) => {
+            builder = builder.header(
+                header::CONTENT_RANGE,
+                format!("bytes {start}-{}/{size}", end - 1),
+            );
+            (StatusCode::PARTIAL_CONTENT, start, end)
+        }
+        None => (StatusCode::OK, 0, size),
+    };
+    builder = builder
+        .status(status)
+        .header(header::CONTENT_LENGTH, end - start);
+
+    if head_only || start == end {
+        return Ok(builder.body(Body::empty())?);
+    }
+    let reader = op.range_reader(path, start..end).await?;
+    Ok(builder.body(Body::wrap_stream(reader))?)
+}
+
+/// Stream request body into the object.
+async fn put_object(op: &Operator, path: &str, body: Body) -> Result<()> {
+    let mut w = op.writer(path).await?;
+    let res = async {
+        let mut buf = oio::AppendBuffer::new();
+        append_stream(&mut w, &mut buf, body).await?;
+        if let Some(bs) = buf.finish() {
+            w.append(bs).await?;
+        }
+        w.close().await?;
+        Ok(())
+    }
+    .await;
+
+    if res.is_err() {
+        let _ = w.abort().await;
+    }
+    res
+}
+
+/// Append all data of the stream into writer in chunks, the remaining
+/// data will be kept in `buf`.
+async fn append_stream<S, E>(w: &mut Writer, buf: &mut oio::AppendBuffer, mut s: S) -> Result<()>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+    E: Error + Send + Sync + 'static,
+{
+    while let Some(bs) = s.try_next().await? {
+        if let Some(bs) = buf.push(bs) {
+            w.append(bs).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Escape text to be embedded in XML.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        let cases = vec![
+            ("bytes=0-9", Some((0, 10))),
+            ("bytes=5-", Some((5, 100))),
+            ("bytes=-10", Some((90, 100))),
+            ("bytes=90-200", Some((90, 100))),
+        ];
+        for (range, expected) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::RANGE, range.parse().unwrap());
+            assert_eq!(parse_range(&headers, 100).unwrap(), expected, "{range}");
+        }
+
+        for range in ["bytes=100-", "bytes=0-1,2-3", "items=0-1", "bytes=a-b"] {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::RANGE, range.parse().unwrap());
+            assert!(parse_range(&headers, 100).is_err(), "{range}");
+        }
+    }
+
+    #[test]
+    fn test_decode_path() {
+        assert_eq!(decode_path("/dir/a%20b.txt").unwrap(), "dir/a b.txt");
+        for path in [
+            "/../a",
+            "/dir/%2e%2e/a",
+            "/dir/..",
+            "/dir%2f..%2fa",
+            "/..%5ca",
+        ] {
+            let err = decode_path(path).unwrap_err();
+            assert_eq!(status_of(&err), StatusCode::BAD_REQUEST, "{path}");
+        }
+    }
+}
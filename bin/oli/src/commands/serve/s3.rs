@@ -0,0 +1,528 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use hyper::header;
+use hyper::Body;
+use hyper::Method;
+use hyper::Request;
+use hyper::Response;
+use hyper::StatusCode;
+use opendal::raw::oio;
+use opendal::ErrorKind;
+use opendal::Metadata;
+use opendal::Metakey;
+use opendal::Operator;
+use uuid::Uuid;
+
+use super::append_stream;
+use super::check_path;
+use super::decode_path;
+use super::escape_xml;
+use super::get_object;
+use super::put_object;
+use super::RequestError;
+use crate::commands::cp::copy_file;
+use crate::commands::cp::CopyOptions;
+
+/// Parts of multipart uploads will be stored under this prefix before
+/// completed, and they will be hidden from listing.
+const MULTIPART_PREFIX: &str = ".oli-multipart/";
+
+/// Max number of keys returned by a single list request, the same as S3.
+const MAX_KEYS: usize = 1000;
+
+pub async fn handle(op: &Operator, req: Request<Body>) -> Result<Response<Body>> {
+    let path = decode_path(req.uri().path())?;
+    let (bucket, key) = path.split_once('/').unwrap_or((&path, ""));
+    let (bucket, key) = (bucket.to_string(), key.to_string());
+    let query: HashMap<String, String> =
+        url::form_urlencoded::parse(req.uri().query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect();
+
+    match (req.method().clone(), bucket.is_empty(), key.is_empty()) {
+        (Method::GET, true, _) => xml(
+            StatusCode::OK,
+            "<ListAllMyBucketsResult><Buckets><Bucket><Name>oli</Name></Bucket></Buckets></ListAllMyBucketsResult>".to_string(),
+        ),
+        (Method::HEAD, false, true) => Ok(Response::new(Body::empty())),
+        (Method::GET, false, true) => list_objects(op, &bucket, &query).await,
+        (Method::POST, false, true) if query.contains_key("delete") => {
+            let body = hyper::body::to_bytes(req.into_body()).await?;
+            let body = String::from_utf8_lossy(&body);
+
+            // Keys are checked before deleting any of them.
+            let keys = xml_values(&body, "Key")
+                .into_iter()
+                .map(|key| {
+                    let path = unescape_xml(key);
+                    check_path(&path)?;
+                    Ok((key, path))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut deleted = String::new();
+            for (key, path) in keys {
+                op.delete(&path).await?;
+                deleted.push_str(&format!("<Deleted><Key>{key}</Key></Deleted>"));
+            }
+            xml(StatusCode::OK, format!("<DeleteResult>{deleted}</DeleteResult>"))
+        }
+        (Method::GET | Method::HEAD, false, false) => {
+            let head_only = req.method() == Method::HEAD;
+            get_object(op, &key, req.headers(), head_only).await
+        }
+        (Method::PUT, false, false) => {
+            if let (Some(part), Some(upload_id)) = (query.get("partNumber"), query.get("uploadId")) {
+                let part: u32 = part
+                    .parse()
+                    .map_err(|_| RequestError(StatusCode::BAD_REQUEST, "invalid part number"))?;
+                put_object(op, &part_path(upload_id, part)?, req.into_body()).await?;
+                return Ok(Response::builder()
+                    .header(header::ETAG, format!("\"{upload_id}-{part}\""))
+                    .body(Body::empty())?);
+            }
+
+            if let Some(src) = req.headers().get("x-amz-copy-source") {
+                let src = decode_path(src.to_str()?)?;
+                let (_, src) = src
+                    .split_once('/')
+                    .ok_or(RequestError(StatusCode::BAD_REQUEST, "invalid copy source"))?;
+                match op.copy(src, &key).await {
+                    Err(err) if err.kind() == ErrorKind::Unsupported => {
+                        let opts = CopyOptions {
+                            native: false,
+                            jobs: 1,
+                            chunk_size: u64::MAX,
+                        };
+                        copy_file(op, src, op, &key, &opts).await?;
+                    }
+                    res => res?,
+                }
+                let meta = op.stat(&key).await?;
+                return xml(
+                    StatusCode::OK,
+                    format!(
+                        "<CopyObjectResult>{}{}</CopyObjectResult>",
+                        last_modified(&meta),
+                        etag(&meta)
+                    ),
+                );
+            }
+
+            if key.ends_with('/') {
+                op.create_dir(&key).await?;
+            } else {
+                put_object(op, &key, req.into_body()).await?;
+            }
+            let mut builder = Response::builder();
+            if let Some(etag) = op.stat(&key).await?.etag() {
+                builder = builder.header(header::ETAG, etag);
+            }
+            Ok(builder.body(Body::empty())?)
+        }
+        (Method::DELETE, false, false) => {
+            match query.get("uploadId") {
+                Some(upload_id) => op.remove_all(&upload_path(upload_id)?).await?,
+                None => op.delete(&key).await?,
+            }
+            Ok(Response::builder()
+                .status(StatusCode::NO_CONTENT)
+                .body(Body::empty())?)
+        }
+        (Method::POST, false, false) if query.contains_key("uploads") => {
+            let upload_id = Uuid::new_v4().simple().to_string();
+            xml(
+                StatusCode::OK,
+                format!(
+                    "<InitiateMultipartUploadResult><Bucket>{}</Bucket><Key>{}</Key><UploadId>{upload_id}</UploadId></InitiateMultipartUploadResult>",
+                    escape_xml(&bucket),
+                    escape_xml(&key)
+                ),
+            )
+        }
+        (Method::POST, false, false) if query.contains_key("uploadId") => {
+            let upload_id = &query["uploadId"];
+            let body = hyper::body::to_bytes(req.into_body()).await?;
+            let body = String::from_utf8_lossy(&body);
+
+            let parts = xml_values(&body, "PartNumber")
+                .into_iter()
+                .map(|part| {
+                    let part: u32 = part.parse().map_err(|_| {
+                        RequestError(StatusCode::BAD_REQUEST, "invalid part number")
+                    })?;
+                    part_path(upload_id, part)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            complete_multipart(op, &key, &parts).await?;
+            op.remove_all(&upload_path(upload_id)?).await?;
+
+            let meta = op.stat(&key).await?;
+            xml(
+                StatusCode::OK,
+                format!(
+                    "<CompleteMultipartUploadResult><Bucket>{}</Bucket><Key>{}</Key>{}</CompleteMultipartUploadResult>",
+                    escape_xml(&bucket),
+                    escape_xml(&key),
+                    etag(&meta)
+                ),
+            )
+        }
+        _ => Ok(Response::builder()
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .body(Body::empty())?),
+    }
+}
+
+/// Build the error body in S3's format.
+pub fn error_body(status: StatusCode, err: &anyhow::Error) -> Body {
+    let code = match status {
+        StatusCode::NOT_FOUND => "NoSuchKey",
+        StatusCode::FORBIDDEN => "AccessDenied",
+        StatusCode::NOT_IMPLEMENTED => "NotImplemented",
+        StatusCode::RANGE_NOT_SATISFIABLE => "InvalidRange",
+        StatusCode::BAD_REQUEST => "InvalidRequest",
+        _ => "InternalError",
+    };
+    Body::from(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>{}</Message></Error>"#,
+        escape_xml(&err.to_string())
+    ))
+}
+
+/// Stream all parts into the object in order without loading them into
+/// memory.
+async fn complete_multipart(op: &Operator, path: &str, parts: &[String]) -> Result<()> {
+    let mut w = op.writer(path).await?;
+    let res = async {
+        let mut buf = oio::AppendBuffer::new();
+        for part in parts {
+            let r = op.reader(part).await?;
+            append_stream(&mut w, &mut buf, r).await?;
+        }
+        if let Some(bs) = buf.finish() {
+            w.append(bs).await?;
+        }
+        w.close().await?;
+        Ok(())
+    }
+    .await;
+
+    if res.is_err() {
+        let _ = w.abort().await;
+    }
+    res
+}
+
+/// List objects in the order of keys, both ListObjects and ListObjectsV2
+/// are supported.
+///
+/// Keys are used as continuation tokens directly.
+async fn list_objects(
+    op: &Operator,
+    bucket: &str,
+    query: &HashMap<String, String>,
+) -> Result<Response<Body>> {
+    let prefix = query.get("prefix").map(String::as_str).unwrap_or_default();
+    let delimiter = query.get("delimiter").map(String::as_str);
+    let dir = &prefix[..prefix.rfind('/').map(|v| v + 1).unwrap_or(0)];
+    let v2 = query.get("list-type").map(String::as_str) == Some("2");
+    let max_keys = match query.get("max-keys") {
+        Some(v) => v
+            .parse::<usize>()
+            .map_err(|_| RequestError(StatusCode::BAD_REQUEST, "invalid max-keys"))?
+            .min(MAX_KEYS),
+        None => MAX_KEYS,
+    };
+    let start_after = if v2 {
+        query
+            .get("continuation-token")
+            .or_else(|| query.get("start-after"))
+    } else {
+        query.get("marker")
+    }
+    .map(String::as_str)
+    .unwrap_or_default();
+    check_path(prefix)?;
+    check_path(start_after)?;
+
+    let ds = if delimiter == Some("/") {
+        op.list(dir).await
+    } else {
+        op.scan(dir).await
+    };
+    let mut ds = match ds {
+        Ok(ds) => Some(ds),
+        Err(err) if err.kind() == ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    // Services don't promise the order of entries, so all of them are
+    // collected and sorted to make pages stable.
+    let mut entries = Vec::new();
+    while let Some(de) = match ds.as_mut() {
+        Some(ds) => ds.try_next().await?,
+        None => None,
+    } {
+        if de.path().starts_with(prefix)
+            && !de.path().starts_with(MULTIPART_PREFIX)
+            && de.path() > start_after
+        {
+            entries.push(de);
+        }
+    }
+    entries.sort_by(|a, b| a.path().cmp(b.path()));
+    let truncated = entries.len() > max_keys;
+    entries.truncate(max_keys);
+
+    let (mut contents, mut prefixes, mut count) = (String::new(), String::new(), 0);
+    for de in &entries {
+        let meta = op
+            .metadata(
+                de,
+                Metakey::Mode | Metakey::ContentLength | Metakey::LastModified | Metakey::Etag,
+            )
+            .await?;
+
+        count += 1;
+        if meta.mode().is_dir() {
+            if delimiter.is_some() {
+                prefixes.push_str(&format!(
+                    "<CommonPrefixes><Prefix>{}</Prefix></CommonPrefixes>",
+                    escape_xml(de.path())
+                ));
+            }
+            continue;
+        }
+        contents.push_str(&format!(
+            "<Contents><Key>{}</Key>{}{}<Size>{}</Size><StorageClass>STANDARD</StorageClass></Contents>",
+            escape_xml(de.path()),
+            last_modified(&meta),
+            etag(&meta),
+            meta.content_length()
+        ));
+    }
+
+    let mut next = String::new();
+    if let (true, Some(de)) = (truncated, entries.last()) {
+        let tag = if v2 {
+            "NextContinuationToken"
+        } else {
+            "NextMarker"
+        };
+        next = format!("<{tag}>{}</{tag}>", escape_xml(de.path()));
+    }
+
+    xml(
+        StatusCode::OK,
+        format!(
+            "<ListBucketResult><Name>{}</Name><Prefix>{}</Prefix>{}<MaxKeys>{max_keys}</MaxKeys><KeyCount>{count}</KeyCount><IsTruncated>{truncated}</IsTruncated>{next}{contents}{prefixes}</ListBucketResult>",
+            escape_xml(bucket),
+            escape_xml(prefix),
+            delimiter
+                .map(|v| format!("<Delimiter>{}</Delimiter>", escape_xml(v)))
+                .unwrap_or_default()
+        ),
+    )
+}
+
+fn xml(status: StatusCode, body: String) -> Result<Response<Body>> {
+    Ok(Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/xml")
+        .body(Body::from(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>{body}"#
+        )))?)
+}
+
+fn last_modified(meta: &Metadata) -> String {
+    meta.last_modified()
+        .map(|v| {
+            format!(
+                "<LastModified>{}</LastModified>",
+                v.format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            )
+        })
+        .unwrap_or_default()
+}
+
+fn etag(meta: &Metadata) -> String {
+    meta.etag()
+        .map(|v| format!("<ETag>{}</ETag>", escape_xml(v)))
+        .unwrap_or_default()
+}
+
+/// Make sure the upload id can't escape from the multipart prefix.
+fn upload_path(upload_id: &str) -> Result<String> {
+    if upload_id.is_empty() || !upload_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(RequestError(StatusCode::NOT_FOUND, "no such upload").into());
+    }
+    Ok(format!("{MULTIPART_PREFIX}{upload_id}/"))
+}
+
+fn part_path(upload_id: &str, part: u32) -> Result<String> {
+    Ok(format!("{}{part:05}", upload_path(upload_id)?))
+}
+
+/// Extract the text of all elements with given tag.
+fn xml_values<'a>(body: &'a str, tag: &str) -> Vec<&'a str> {
+    let (start, end) = (format!("<{tag}>"), format!("</{tag}>"));
+    body.split(start.as_str())
+        .skip(1)
+        .filter_map(|v| v.split_once(end.as_str()).map(|(v, _)| v.trim()))
+        .collect()
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use opendal::services;
+
+    use super::*;
+    use crate::commands::serve::Protocol;
+
+    async fn call(op: &Operator, req: Request<Body>) -> Result<(StatusCode, String)> {
+        let resp = handle(op, req).await?;
+        let status = resp.status();
+        let body = hyper::body::to_bytes(resp.into_body()).await?;
+        Ok((status, String::from_utf8_lossy(&body).to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_s3_multipart_upload() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        let (_, body) = call(
+            &op,
+            Request::post("/bucket/dir/obj?uploads").body(Body::empty())?,
+        )
+        .await?;
+        let upload_id = xml_values(&body, "UploadId")[0].to_string();
+
+        for (part, content) in [(1, "Hello, "), (2, "World!")] {
+            let req = Request::put(format!(
+                "/bucket/dir/obj?partNumber={part}&uploadId={upload_id}"
+            ))
+            .body(Body::from(content))?;
+            assert_eq!(call(&op, req).await?.0, StatusCode::OK);
+        }
+
+        let req = Request::post(format!("/bucket/dir/obj?uploadId={upload_id}")).body(Body::from(
+            "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber></Part><Part><PartNumber>2</PartNumber></Part></CompleteMultipartUpload>",
+        ))?;
+        assert_eq!(call(&op, req).await?.0, StatusCode::OK);
+        assert_eq!(op.read("dir/obj").await?, b"Hello, World!");
+
+        let (status, body) = call(
+            &op,
+            Request::get("/bucket?list-type=2&prefix=dir/o&delimiter=/").body(Body::empty())?,
+        )
+        .await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("<Key>dir/obj</Key>"));
+        assert!(body.contains("<Size>13</Size>"));
+        assert!(!body.contains(MULTIPART_PREFIX));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_s3_list_objects_in_pages() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        for i in 0..5 {
+            op.write(&format!("dir/{i}"), "x").await?;
+        }
+
+        let mut keys = Vec::new();
+        let mut token = String::new();
+        loop {
+            let mut uri = "/bucket?list-type=2&prefix=dir/&max-keys=2".to_string();
+            if !token.is_empty() {
+                uri.push_str(&format!("&continuation-token={token}"));
+            }
+            let (status, body) = call(&op, Request::get(uri).body(Body::empty())?).await?;
+            assert_eq!(status, StatusCode::OK);
+            keys.extend(xml_values(&body, "Key").into_iter().map(String::from));
+
+            let truncated = xml_values(&body, "IsTruncated")[0] == "true";
+            assert_eq!(truncated, keys.len() < 5, "{body}");
+            if !truncated {
+                break;
+            }
+            token = xml_values(&body, "NextContinuationToken")[0].to_string();
+        }
+        assert_eq!(keys, ["dir/0", "dir/1", "dir/2", "dir/3", "dir/4"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_s3_reject_escaped_path() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("a", "Hello").await?;
+
+        let req = Request::put("/bucket/b")
+            .header("x-amz-copy-source", "/bucket/%2e%2e/a")
+            .body(Body::empty())?;
+        let resp = Protocol::S3.handle(&op, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(!op.is_exist("b").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_s3_reject_escaped_keys() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("a", "Hello").await?;
+
+        let req = Request::post("/bucket?delete").body(Body::from(
+            "<Delete><Object><Key>a</Key></Object><Object><Key>../a</Key></Object></Delete>",
+        ))?;
+        let resp = Protocol::S3.handle(&op, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(op.is_exist("a").await?, "no key will be deleted");
+
+        for uri in [
+            "/bucket?prefix=../",
+            "/bucket?prefix=%2e%2e/a",
+            "/bucket?marker=../a",
+            "/bucket?list-type=2&continuation-token=a/../../b",
+        ] {
+            let req = Request::get(uri).body(Body::empty())?;
+            let resp = Protocol::S3.handle(&op, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{uri}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_upload_path() {
+        assert!(upload_path("abc123").is_ok());
+        assert!(upload_path("../abc").is_err());
+        assert!(upload_path("").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use hyper::header;
+use hyper::http::uri::Uri;
+use hyper::Body;
+use hyper::Method;
+use hyper::Request;
+use hyper::Response;
+use hyper::StatusCode;
+use opendal::ErrorKind;
+use opendal::Metadata;
+use opendal::Metakey;
+use opendal::Operator;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::AsciiSet;
+use percent_encoding::NON_ALPHANUMERIC;
+
+use super::decode_path;
+use super::escape_xml;
+use super::format_http_date;
+use super::get_object;
+use super::put_object;
+use super::RequestError;
+use crate::commands::cp::copy_file;
+use crate::commands::cp::CopyOptions;
+
+/// Characters that need to be encoded in href.
+const HREF: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+pub async fn handle(op: &Operator, req: Request<Body>) -> Result<Response<Body>> {
+    let path = decode_path(req.uri().path())?;
+
+    match req.method().as_str() {
+        "OPTIONS" => Ok(Response::builder()
+            .header("DAV", "1")
+            .header(
+                header::ALLOW,
+                "OPTIONS, GET, HEAD, PUT, DELETE, MKCOL, COPY, MOVE, PROPFIND",
+            )
+            .body(Body::empty())?),
+        "GET" | "HEAD" => {
+            let head_only = req.method() == Method::HEAD;
+            get_object(op, &path, req.headers(), head_only).await
+        }
+        "PUT" => {
+            put_object(op, &path, req.into_body()).await?;
+            Ok(status(StatusCode::CREATED))
+        }
+        "DELETE" => {
+            let meta = stat(op, &path).await?;
+            if meta.mode().is_dir() {
+                op.remove_all(&dir_path(&path)).await?;
+            } else {
+                op.delete(&path).await?;
+            }
+            Ok(status(StatusCode::NO_CONTENT))
+        }
+        "MKCOL" => {
+            op.create_dir(&dir_path(&path)).await?;
+            Ok(status(StatusCode::CREATED))
+        }
+        "COPY" | "MOVE" => {
+            let dst = req
+                .headers()
+                .get("Destination")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<Uri>().ok())
+                .ok_or(RequestError(
+                    StatusCode::BAD_REQUEST,
+                    "missing or invalid destination",
+                ))?;
+            let dst = decode_path(dst.path())?;
+
+            let res = if req.method() == "COPY" {
+                op.copy(&path, &dst).await
+            } else {
+                op.rename(&path, &dst).await
+            };
+            match res {
+                Err(err) if err.kind() == ErrorKind::Unsupported => {
+                    let opts = CopyOptions {
+                        native: false,
+                        jobs: 1,
+                        chunk_size: u64::MAX,
+                    };
+                    copy_file(op, &path, op, &dst, &opts).await?;
+                    if req.method() == "MOVE" {
+                        op.delete(&path).await?;
+                    }
+                }
+                res => res?,
+            }
+            Ok(status(StatusCode::CREATED))
+        }
+        "PROPFIND" => {
+            let depth = req
+                .headers()
+                .get("Depth")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("1");
+
+            let meta = stat(op, &path).await?;
+            let mut responses = Vec::new();
+            if meta.mode().is_dir() {
+                let path = dir_path(&path);
+                responses.push(response(&path, &meta));
+
+                if depth != "0" {
+                    let mut ds = op.list(&path).await?;
+                    while let Some(de) = ds.try_next().await? {
+                        let meta = op
+                            .metadata(
+                                &de,
+                                Metakey::Mode
+                                    | Metakey::ContentLength
+                                    | Metakey::LastModified
+                                    | Metakey::Etag,
+                            )
+                            .await?;
+                        responses.push(response(de.path(), &meta));
+                    }
+                }
+            } else {
+                responses.push(response(&path, &meta));
+            }
+
+            let body = format!(
+                r#"<?xml version="1.0" encoding="utf-8"?><D:multistatus xmlns:D="DAV:">{}</D:multistatus>"#,
+                responses.join("")
+            );
+            Ok(Response::builder()
+                .status(StatusCode::MULTI_STATUS)
+                .header(header::CONTENT_TYPE, "application/xml; charset=utf-8")
+                .body(Body::from(body))?)
+        }
+        _ => Ok(status(StatusCode::METHOD_NOT_ALLOWED)),
+    }
+}
+
+fn status(code: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(code)
+        .body(Body::empty())
+        .expect("response must be valid")
+}
+
+fn dir_path(path: &str) -> String {
+    if path.is_empty() || path.ends_with('/') {
+        path.to_string()
+    } else {
+        format!("{path}/")
+    }
+}
+
+/// Stat the path, clients could request collections without the
+/// trailing `/`.
+async fn stat(op: &Operator, path: &str) -> Result<Metadata> {
+    match op.stat(path).await {
+        Err(err) if err.kind() == ErrorKind::NotFound && !path.ends_with('/') => {
+            Ok(op.stat(&dir_path(path)).await?)
+        }
+        res => Ok(res?),
+    }
+}
+
+/// Build the `response` element of multistatus.
+fn response(path: &str, meta: &Metadata) -> String {
+    let href = utf8_percent_encode(&format!("/{path}"), HREF).to_string();
+    let mut props = String::new();
+    if meta.mode().is_dir() {
+        props.push_str("<D:resourcetype><D:collection/></D:resourcetype>");
+    } else {
+        props.push_str("<D:resourcetype/>");
+        props.push_str(&format!(
+            "<D:getcontentlength>{}</D:getcontentlength>",
+            meta.content_length()
+        ));
+        if let Some(etag) = meta.etag() {
+            props.push_str(&format!("<D:getetag>{}</D:getetag>", escape_xml(etag)));
+        }
+    }
+    if let Some(v) = format_http_date(meta) {
+        props.push_str(&format!("<D:getlastmodified>{v}</D:getlastmodified>"));
+    }
+
+    format!(
+        "<D:response><D:href>{}</D:href><D:propstat><D:prop>{props}</D:prop><D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>",
+        escape_xml(&href)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use opendal::services;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_webdav() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        let req = Request::builder()
+            .method("MKCOL")
+            .uri("/dir")
+            .body(Body::empty())?;
+        assert_eq!(handle(&op, req).await?.status(), StatusCode::CREATED);
+
+        let req = Request::put("/dir/a%20b.txt").body(Body::from("Hello"))?;
+        assert_eq!(handle(&op, req).await?.status(), StatusCode::CREATED);
+
+        let req = Request::builder()
+            .method("MOVE")
+            .uri("/dir/a%20b.txt")
+            .header("Destination", "http://localhost/dir/c.txt")
+            .body(Body::empty())?;
+        assert_eq!(handle(&op, req).await?.status(), StatusCode::CREATED);
+        assert!(!op.is_exist("dir/a b.txt").await?);
+
+        let req = Request::builder()
+            .method("COPY")
+            .uri("/dir/c.txt")
+            .header("Destination", "http://localhost/dir/%2e%2e/../d.txt")
+            .body(Body::empty())?;
+        assert!(handle(&op, req).await.is_err());
+
+        let req = Request::builder()
+            .method("PROPFIND")
+            .uri("/dir/")
+            .header("Depth", "1")
+            .body(Body::empty())?;
+        let resp = handle(&op, req).await?;
+        assert_eq!(resp.status(), StatusCode::MULTI_STATUS);
+        let body = hyper::body::to_bytes(resp.into_body()).await?;
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("<D:href>/dir/</D:href>"));
+        assert!(body.contains(
+            "<D:href>/dir/c.txt</D:href><D:propstat><D:prop><D:resourcetype/><D:getcontentlength>5</D:getcontentlength>"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+use futures::TryStreamExt;
+use opendal::Metakey;
+use opendal::Operator;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::Context;
+use rustyline::Editor;
+use rustyline::Helper;
+use tokio::io;
+use tokio::runtime::Handle;
+use tokio::task::block_in_place;
+
+use super::cp::copy_file;
+use super::cp::CopyOptions;
+use crate::config::Config;
+use crate::format::Entry;
+use crate::format::Format;
+use crate::format::Output;
+
+const HELP: &str = "\
+cd <dir>             change current dir
+ls [-l] [-h] [dir]   list entries under dir
+cat <path>           display object content
+stat [-h] <path>     show object metadata
+rm [-r] <path>       remove object, or all objects under dir with -r
+cp <src> <dst>       copy object
+pwd                  print current dir
+help                 print this help
+exit                 exit the shell";
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let target = args
+        .get_one::<String>("target")
+        .ok_or_else(|| anyhow!("missing target"))?;
+    let (op, path) = cfg.parse_location(target)?;
+
+    let cwd = Arc::new(Mutex::new(resolve_path("", &format!("/{path}/"))));
+    let mut rl = Editor::<ShellHelper, DefaultHistory>::new()?;
+    rl.set_helper(Some(ShellHelper {
+        op: op.clone(),
+        cwd: cwd.clone(),
+    }));
+    let history = config_path.with_file_name("history");
+    // History file doesn't exist in the first run.
+    let _ = rl.load_history(&history);
+
+    let mut shell = Shell { op, cwd };
+    loop {
+        let prompt = format!("{}> ", shell.pwd());
+        let line = match block_in_place(|| rl.readline(&prompt)) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        };
+        let args = line.split_whitespace().collect::<Vec<_>>();
+        if args.is_empty() {
+            continue;
+        }
+        rl.add_history_entry(line.as_str())?;
+
+        match args[0] {
+            "exit" | "quit" => break,
+            cmd => {
+                if let Err(err) = shell.run(cmd, &args[1..]).await {
+                    eprintln!("{cmd}: {err}");
+                }
+            }
+        }
+    }
+
+    if let Some(parent) = history.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    rl.save_history(&history)?;
+    Ok(())
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("start an interactive shell on the location")
+        .arg(Arg::new("target").required(true))
+}
+
+struct Shell {
+    op: Operator,
+    /// Current dir, always starts and ends with `/`.
+    cwd: Arc<Mutex<String>>,
+}
+
+impl Shell {
+    fn pwd(&self) -> String {
+        self.cwd.lock().expect("lock must succeed").clone()
+    }
+
+    /// Resolve the path against current dir, the returned path is
+    /// relative to the operator's root.
+    fn path(&self, path: &str) -> String {
+        resolve_path(&self.pwd(), path)
+            .trim_start_matches('/')
+            .to_string()
+    }
+
+    async fn run(&mut self, cmd: &str, args: &[&str]) -> Result<()> {
+        let (flags, args): (Vec<&str>, Vec<&str>) = args.iter().partition(|v| v.starts_with('-'));
+        let arg = |idx: usize| {
+            args.get(idx)
+                .copied()
+                .ok_or_else(|| anyhow!("missing argument, see `help`"))
+        };
+
+        match cmd {
+            "help" => println!("{HELP}"),
+            "pwd" => println!("{}", self.pwd()),
+            "cd" => {
+                let path = resolve_path(&self.pwd(), &format!("{}/", arg(0).unwrap_or("/")));
+                let meta = self.op.stat(path.trim_start_matches('/')).await?;
+                if !meta.mode().is_dir() {
+                    return Err(anyhow!("{path} is not a dir"));
+                }
+                *self.cwd.lock().expect("lock must succeed") = path;
+            }
+            "ls" => {
+                let long = flags.contains(&"-l");
+                let output = Output {
+                    format: Format::Text,
+                    human: flags.contains(&"-h"),
+                };
+                let dir = match arg(0) {
+                    Ok(dir) => self.path(&format!("{dir}/")),
+                    Err(_) => self.path(""),
+                };
+                let mut ds = self.op.list(&dir).await?;
+                while let Some(de) = ds.try_next().await? {
+                    if !long {
+                        println!("{}", de.name());
+                        continue;
+                    }
+                    let meta = self
+                        .op
+                        .metadata(
+                            &de,
+                            Metakey::Mode
+                                | Metakey::ContentLength
+                                | Metakey::LastModified
+                                | Metakey::Etag,
+                        )
+                        .await?;
+                    println!("{}", Entry::long(de.name(), &meta).to_long_text(&output));
+                }
+            }
+            "cat" => {
+                let mut reader = self.op.reader(&self.path(arg(0)?)).await?;
+                io::copy(&mut reader, &mut io::stdout()).await?;
+            }
+            "stat" => {
+                let path = self.path(arg(0)?);
+                let meta = self.op.stat(&path).await?;
+                let output = Output {
+                    format: Format::Text,
+                    human: flags.contains(&"-h"),
+                };
+                output.print(&Entry::stat(&path, &meta), |v| v.to_stat_text(&output))?;
+            }
+            "rm" => {
+                let path = self.path(arg(0)?);
+                if flags.contains(&"-r") {
+                    self.op.remove_all(&path).await?;
+                } else {
+                    self.op.delete(&path).await?;
+                }
+            }
+            "cp" => {
+                let (src, dst) = (self.path(arg(0)?), self.path(arg(1)?));
+                let opts = CopyOptions {
+                    native: self.op.info().can_copy(),
+                    jobs: 1,
+                    chunk_size: u64::MAX,
+                };
+                copy_file(&self.op, &src, &self.op, &dst, &opts).await?;
+            }
+            _ => return Err(anyhow!("unknown command, see `help`")),
+        }
+        Ok(())
+    }
+}
+
+/// Resolve path against the dir, `.` and `..` will be normalized.
+///
+/// The returned path always starts with `/`, and ends with `/` if the
+/// input is a dir.
+fn resolve_path(dir: &str, path: &str) -> String {
+    let full = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("{dir}{path}")
+    };
+
+    let mut parts = Vec::new();
+    for part in full.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            v => parts.push(v),
+        }
+    }
+
+    let mut resolved = format!("/{}", parts.join("/"));
+    if !parts.is_empty() && (full.ends_with('/') || full.ends_with("/.") || full.ends_with("/..")) {
+        resolved.push('/');
+    }
+    resolved
+}
+
+/// ShellHelper completes remote paths with entries returned by `list`.
+struct ShellHelper {
+    op: Operator,
+    cwd: Arc<Mutex<String>>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line.rfind(' ').map(|v| v + 1).unwrap_or(0);
+        // Only paths will be completed.
+        if start == 0 {
+            return Ok((pos, vec![]));
+        }
+
+        let word = &line[start..];
+        let (dir, prefix) = match word.rfind('/') {
+            Some(idx) => word.split_at(idx + 1),
+            None => ("", word),
+        };
+        let cwd = self.cwd.lock().expect("lock must succeed").clone();
+        let path = resolve_path(&cwd, &format!("{dir}/"));
+
+        let names = block_in_place(|| {
+            Handle::current().block_on(async {
+                let mut names = Vec::new();
+                let mut ds = self.op.list(path.trim_start_matches('/')).await?;
+                while let Some(de) = ds.try_next().await? {
+                    if de.name().starts_with(prefix) {
+                        names.push(format!("{dir}{}", de.name()));
+                    }
+                }
+                Ok::<_, opendal::Error>(names)
+            })
+        });
+
+        // Errors are ignored so that users can keep typing.
+        Ok((start, names.unwrap_or_default()))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_path() {
+        let cases = vec![
+            ("/", "", "/"),
+            ("/", "a", "/a"),
+            ("/", "a/", "/a/"),
+            ("/a/", "b/c", "/a/b/c"),
+            ("/a/", "../b", "/b"),
+            ("/a/b/", "..", "/a/"),
+            ("/a/", "../..", "/"),
+            ("/a/", "/c/./d/", "/c/d/"),
+        ];
+
+        for (dir, path, expected) in cases {
+            assert_eq!(resolve_path(dir, path), expected, "{dir} {path}");
+        }
+    }
+}
@@ -24,6 +24,9 @@ use clap::ArgMatches;
 use clap::Command;
 
 use crate::config::Config;
+use crate::format::output_args;
+use crate::format::Entry;
+use crate::format::Output;
 
 pub async fn main(args: &ArgMatches) -> Result<()> {
     let config_path = args
@@ -36,26 +39,12 @@ pub async fn main(args: &ArgMatches) -> Result<()> {
         .ok_or_else(|| anyhow!("missing target"))?;
     let (op, path) = cfg.parse_location(target)?;
 
+    let output = Output::from_args(args);
+
     let meta = op.stat(&path).await?;
-    println!("path: {path}");
-    let size = meta.content_length();
-    println!("size: {size}");
-    if let Some(etag) = meta.etag() {
-        println!("etag: {etag}");
-    }
-    let file_type = meta.mode();
-    println!("type: {file_type}");
-    if let Some(content_type) = meta.content_type() {
-        println!("content-type: {content_type}");
-    }
-    if let Some(last_modified) = meta.last_modified() {
-        println!("last-modified: {last_modified}");
-    }
-
-    Ok(())
+    output.print(&Entry::stat(&path, &meta), |v| v.to_stat_text(&output))
 }
 
 pub fn cli(cmd: Command) -> Command {
-    cmd.about("show object metadata")
-        .arg(Arg::new("target").required(true))
+    output_args(cmd.about("show object metadata")).arg(Arg::new("target").required(true))
 }
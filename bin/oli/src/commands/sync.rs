@@ -0,0 +1,381 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use clap::value_parser;
+use clap::Arg;
+use clap::ArgAction;
+use clap::ArgMatches;
+use clap::Command;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use globset::Glob;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
+use opendal::ErrorKind;
+use opendal::Metadata;
+use opendal::Metakey;
+use opendal::Operator;
+
+use super::cp::copy_file;
+use super::cp::is_same_backend;
+use super::cp::CopyOptions;
+use super::hash::verify_file;
+use crate::config::Config;
+use crate::utils::normalize_dir;
+
+pub async fn main(args: &ArgMatches) -> Result<()> {
+    let config_path = args
+        .get_one::<PathBuf>("config")
+        .ok_or_else(|| anyhow!("missing config path"))?;
+    let cfg = Config::load(config_path)?;
+
+    let delete = args.get_flag("delete");
+    let dry_run = args.get_flag("dry-run");
+    let verify = args.get_flag("verify");
+    let jobs = *args
+        .get_one::<u64>("jobs")
+        .ok_or_else(|| anyhow!("missing jobs"))? as usize;
+    let filter = Filter::new(
+        args.get_many::<String>("include").unwrap_or_default(),
+        args.get_many::<String>("exclude").unwrap_or_default(),
+    )?;
+
+    let src = args
+        .get_one::<String>("source")
+        .ok_or_else(|| anyhow!("missing source"))?;
+    let (src_op, src_path) = cfg.parse_location(src)?;
+    let src_path = normalize_dir(src_path);
+
+    let dst = args
+        .get_one::<String>("destination")
+        .ok_or_else(|| anyhow!("missing destination"))?;
+    let (dst_op, dst_path) = cfg.parse_location(dst)?;
+    let dst_path = normalize_dir(dst_path);
+
+    // Files are copied concurrently, so chunked copy is not needed here.
+    let opts = CopyOptions {
+        native: is_same_backend(&cfg, src, dst)? && src_op.info().can_copy(),
+        jobs: 1,
+        chunk_size: u64::MAX,
+    };
+
+    // A missing source must not be treated as empty, otherwise `--delete`
+    // will wipe the whole destination.
+    let src_entries = match scan_files(&src_op, &src_path, &filter).await {
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Err(anyhow!("source {src} doesn't exist"))
+        }
+        res => res?,
+    };
+    let mut dst_entries = match scan_files(&dst_op, &dst_path, &filter).await {
+        Err(err) if err.kind() == ErrorKind::NotFound => HashMap::new(),
+        res => res?,
+    };
+    // Object storages can't tell a missing prefix from an empty one.
+    if delete && src_entries.is_empty() && !dst_entries.is_empty() {
+        return Err(anyhow!(
+            "source {src} is empty, refuse to delete all files under destination"
+        ));
+    }
+
+    // Etags are only comparable while the destination is copied natively
+    // by the same backend, objects uploaded via multipart by us will never
+    // have the same etag as the source.
+    let compare_etag = opts.native;
+
+    let mut summary = Summary::default();
+    let mut tasks = Vec::new();
+    for (name, src_meta) in src_entries {
+        match dst_entries.remove(&name) {
+            Some(dst_meta) if !is_changed(&src_meta, &dst_meta, compare_etag) => {
+                summary.skipped += 1
+            }
+            _ => tasks.push(Task::Copy(name, src_meta.content_length())),
+        }
+    }
+    if delete {
+        tasks.extend(dst_entries.into_keys().map(Task::Delete));
+    }
+
+    let results = futures::stream::iter(tasks)
+        .map(|task| {
+            let (src_op, dst_op, opts) = (&src_op, &dst_op, &opts);
+            let (src_path, dst_path) = (&src_path, &dst_path);
+            async move {
+                let res = match &task {
+                    Task::Copy(name, _) => {
+                        println!("Copying {src_path}{name} -> {dst_path}{name}");
+                        if dry_run {
+                            Ok(())
+                        } else {
+                            let (src, dst) =
+                                (format!("{src_path}{name}"), format!("{dst_path}{name}"));
+                            match copy_file(src_op, &src, dst_op, &dst, opts).await {
+                                Ok(()) if verify => verify_file(src_op, &src, dst_op, &dst).await,
+                                res => res,
+                            }
+                        }
+                    }
+                    Task::Delete(name) => {
+                        println!("Deleting {dst_path}{name}");
+                        if dry_run {
+                            Ok(())
+                        } else {
+                            dst_op
+                                .delete(&format!("{dst_path}{name}"))
+                                .await
+                                .map_err(Into::into)
+                        }
+                    }
+                };
+                (task, res)
+            }
+        })
+        .buffer_unordered(jobs)
+        .collect::<Vec<_>>()
+        .await;
+
+    for (task, res) in results {
+        match (task, res) {
+            (Task::Copy(_, size), Ok(())) => {
+                summary.copied += 1;
+                summary.copied_bytes += size;
+            }
+            (Task::Delete(_), Ok(())) => summary.deleted += 1,
+            (Task::Copy(name, _), Err(err)) | (Task::Delete(name), Err(err)) => {
+                eprintln!("Failed {name}: {err}");
+                summary.failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "{}{} copied ({} bytes), {} deleted, {} skipped, {} failed",
+        if dry_run { "(dry run) " } else { "" },
+        summary.copied,
+        summary.copied_bytes,
+        summary.deleted,
+        summary.skipped,
+        summary.failed
+    );
+    if summary.failed > 0 {
+        return Err(anyhow!("{} entries failed to sync", summary.failed));
+    }
+    Ok(())
+}
+
+pub fn cli(cmd: Command) -> Command {
+    cmd.about("sync objects from source to destination incrementally")
+        .arg(Arg::new("source").required(true))
+        .arg(Arg::new("destination").required(true))
+        .arg(
+            Arg::new("delete")
+                .long("delete")
+                .help("Delete files under destination that don't exist in source")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .help("Show what would be done without changing anything")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("include")
+                .long("include")
+                .help("Only sync files whose relative path matches the glob")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .help("Skip files whose relative path matches the glob")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("jobs")
+                .long("jobs")
+                .short('j')
+                .help("Number of concurrent transfers")
+                .default_value("4")
+                .value_parser(value_parser!(u64).range(1..)),
+        )
+        .arg(
+            Arg::new("verify")
+                .long("verify")
+                .help("Compare checksums of source and destination after copying")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+enum Task {
+    /// Copy the file with given relative path and size.
+    Copy(String, u64),
+    /// Delete the file with given relative path from destination.
+    Delete(String),
+}
+
+#[derive(Default)]
+struct Summary {
+    copied: usize,
+    copied_bytes: u64,
+    deleted: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+struct Filter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl Filter {
+    fn new<'a>(
+        include: impl IntoIterator<Item = &'a String>,
+        exclude: impl IntoIterator<Item = &'a String>,
+    ) -> Result<Filter> {
+        let build = |patterns: Vec<&String>| -> Result<Option<GlobSet>> {
+            if patterns.is_empty() {
+                return Ok(None);
+            }
+            let mut builder = GlobSetBuilder::new();
+            for p in patterns {
+                builder.add(Glob::new(p)?);
+            }
+            Ok(Some(builder.build()?))
+        };
+
+        Ok(Filter {
+            include: build(include.into_iter().collect())?,
+            exclude: build(exclude.into_iter().collect())?.unwrap_or_else(GlobSet::empty),
+        })
+    }
+
+    fn is_match(&self, name: &str) -> bool {
+        self.include.as_ref().map_or(true, |g| g.is_match(name)) && !self.exclude.is_match(name)
+    }
+}
+
+/// Scan all files under path, returns a map from relative path to metadata.
+async fn scan_files(
+    op: &Operator,
+    path: &str,
+    filter: &Filter,
+) -> opendal::Result<HashMap<String, Metadata>> {
+    let mut files = HashMap::new();
+    let mut ds = op.scan(if path.is_empty() { "/" } else { path }).await?;
+    while let Some(de) = ds.try_next().await? {
+        let meta = op
+            .metadata(
+                &de,
+                Metakey::Mode | Metakey::ContentLength | Metakey::Etag | Metakey::LastModified,
+            )
+            .await?;
+        if meta.mode().is_dir() {
+            continue;
+        }
+
+        let name = de.path().strip_prefix(path).expect("invalid path");
+        if filter.is_match(name) {
+            files.insert(name.to_string(), meta);
+        }
+    }
+    Ok(files)
+}
+
+/// Check if the destination file is outdated.
+///
+/// - Files with different sizes are always changed.
+/// - Etags are compared if `compare_etag` and both sides provide them,
+///   except multipart etags which are not the digest of content.
+/// - Otherwise, source that is newer than destination is changed.
+fn is_changed(src: &Metadata, dst: &Metadata, compare_etag: bool) -> bool {
+    if src.content_length() != dst.content_length() {
+        return true;
+    }
+    if let (true, Some(src_etag), Some(dst_etag)) = (compare_etag, src.etag(), dst.etag()) {
+        if !is_multipart_etag(src_etag) && !is_multipart_etag(dst_etag) {
+            return src_etag != dst_etag;
+        }
+    }
+    match (src.last_modified(), dst.last_modified()) {
+        (Some(src_lm), Some(dst_lm)) => src_lm > dst_lm,
+        _ => false,
+    }
+}
+
+/// Check if the etag looks like a multipart one like `"<md5>-<parts>"`.
+fn is_multipart_etag(etag: &str) -> bool {
+    match etag.trim_matches('"').rsplit_once('-') {
+        Some((_, parts)) => !parts.is_empty() && parts.bytes().all(|b| b.is_ascii_digit()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opendal::EntryMode;
+
+    use super::*;
+
+    #[test]
+    fn test_is_changed() {
+        let meta = |size: u64, etag: &str| {
+            Metadata::new(EntryMode::FILE)
+                .with_content_length(size)
+                .with_etag(etag.to_string())
+                .with_last_modified("2023-01-01T00:00:00Z".parse().unwrap())
+        };
+
+        assert!(is_changed(&meta(1, "a"), &meta(2, "a"), true));
+        assert!(is_changed(&meta(1, "a"), &meta(1, "b"), true));
+        assert!(!is_changed(&meta(1, "a"), &meta(1, "a"), true));
+
+        // Etags from different backends are ignored.
+        assert!(!is_changed(&meta(1, "a"), &meta(1, "b"), false));
+        assert!(is_changed(&meta(1, "a"), &meta(2, "a"), false));
+
+        // Multipart etags are ignored.
+        assert!(!is_changed(&meta(1, "\"a\""), &meta(1, "\"b-2\""), true));
+        assert!(is_multipart_etag("\"9b2cf535f27731c974343645a3985328-12\""));
+        assert!(!is_multipart_etag("\"9b2cf535f27731c974343645a3985328\""));
+
+        let (old, new) = (
+            meta(1, "a").with_last_modified("2023-01-01T00:00:00Z".parse().unwrap()),
+            meta(1, "b").with_last_modified("2023-01-02T00:00:00Z".parse().unwrap()),
+        );
+        assert!(is_changed(&new, &old, false));
+        assert!(!is_changed(&old, &new, false));
+    }
+
+    #[test]
+    fn test_filter() -> Result<()> {
+        let include = vec!["**/*.parquet".to_string()];
+        let exclude = vec!["tmp/**".to_string()];
+        let filter = Filter::new(&include, &exclude)?;
+
+        assert!(filter.is_match("data/a.parquet"));
+        assert!(!filter.is_match("data/a.json"));
+        assert!(!filter.is_match("tmp/a.parquet"));
+        Ok(())
+    }
+}
@@ -16,6 +16,7 @@
 // under the License.
 
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::env;
 use std::fs;
@@ -26,17 +27,27 @@ use std::str::FromStr;
 
 use anyhow::anyhow;
 use anyhow::Result;
-use opendal::services;
+use opendal::layers::ConcurrentLimitLayer;
+use opendal::layers::LoggingLayer;
+use opendal::layers::RetryLayer;
 use opendal::Operator;
 use opendal::Scheme;
 use serde::Deserialize;
+use serde::Serialize;
 use toml;
 
-#[derive(Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct Config {
     profiles: HashMap<String, HashMap<String, String>>,
 }
 
+/// Options in profile that are used to build layers instead of services.
+///
+/// - `retry`: max retry times of temporary errors.
+/// - `logging`: enable logging if set to `on` or `true`.
+/// - `concurrent_limit`: max concurrent requests to the service.
+pub const LAYER_OPTIONS: [&str; 3] = ["retry", "logging", "concurrent_limit"];
+
 /// resolve_relative_path turns a relative path to a absolute path.
 ///
 /// The reason why we don't use `fs::canonicalize` here is `fs::canonicalize`
@@ -95,6 +106,49 @@ impl Config {
         Ok(toml::from_str(&data)?)
     }
 
+    /// Save profiles into the local config file.
+    ///
+    /// Profiles loaded from environment variables will be saved too, so
+    /// callers should use [`Config::load_from_file`] before modifying.
+    pub fn save_to_file(&self, config_path: &Path) -> Result<()> {
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Sort profiles and options to keep the file stable.
+        let profiles: BTreeMap<_, BTreeMap<_, _>> = self
+            .profiles
+            .iter()
+            .map(|(name, opts)| (name, opts.iter().collect()))
+            .collect();
+        let mut data = toml::map::Map::new();
+        data.insert("profiles".to_string(), toml::Value::try_from(profiles)?);
+        fs::write(config_path, toml::to_string(&data)?)?;
+        Ok(())
+    }
+
+    /// Get the options of the profile.
+    pub fn profile(&self, name: &str) -> Option<&HashMap<String, String>> {
+        self.profiles.get(name)
+    }
+
+    /// Get the names of all profiles in order.
+    pub fn profile_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.profiles.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Add or replace the profile.
+    pub fn set_profile(&mut self, name: &str, opts: HashMap<String, String>) {
+        self.profiles.insert(name.to_string(), opts);
+    }
+
+    /// Remove the profile, returns `false` if it doesn't exist.
+    pub fn remove_profile(&mut self, name: &str) -> bool {
+        self.profiles.remove(name).is_some()
+    }
+
     /// Load config from environment variables.
     ///
     /// The format of each environment variable should be `OLI_PROFILE_{PROFILE NAME}_{OPTION}`,
@@ -123,139 +177,103 @@ impl Config {
         Config { profiles }
     }
 
-    /// Parse `<profile>://abc/def` into `op` and `location`.
-    pub fn parse_location(&self, s: &str) -> Result<(Operator, String)> {
+    /// Parse `<profile>://abc/def` into options of the profile and
+    /// `location`.
+    ///
+    /// Local paths will be parsed into options of `fs` service.
+    pub fn parse_profile(&self, s: &str) -> Result<(HashMap<String, String>, String)> {
         if !s.contains("://") {
-            let mut fs_builder = services::Fs::default();
+            let mut profile = HashMap::from([("type".to_string(), Scheme::Fs.to_string())]);
             let fp = resolve_relative_path(Path::new(s));
             let fp_str = fp.as_os_str().to_string_lossy();
 
             let filename = match fp_str.split_once(['/', '\\']) {
                 Some((base, filename)) => {
-                    fs_builder.root(if base.is_empty() { "/" } else { base });
+                    let root = if base.is_empty() { "/" } else { base };
+                    profile.insert("root".to_string(), root.to_string());
                     filename
                 }
                 _ => s,
             };
 
-            return Ok((Operator::new(fs_builder)?.finish(), filename.into()));
+            return Ok((profile, filename.into()));
         }
 
         let parts = s.splitn(2, "://").collect::<Vec<_>>();
         debug_assert!(parts.len() == 2);
 
-        let profile_name = parts[0];
-        let path = parts[1].into();
+        match self.profiles.get(parts[0]) {
+            Some(profile) => Ok((profile.clone(), parts[1].to_string())),
+            None => parse_native_url(parts[0], parts[1]),
+        }
+    }
 
-        let profile = self
-            .profiles
-            .get(profile_name)
-            .ok_or_else(|| anyhow!("unknown profile: {}", profile_name))?;
+    /// Parse `<profile>://abc/def` into `op` and `location`.
+    pub fn parse_location(&self, s: &str) -> Result<(Operator, String)> {
+        let (profile, path) = self.parse_profile(s)?;
+        let profile = &profile;
+        let profile_name = s.split_once("://").map_or("", |(name, _)| name);
 
         let svc = profile
             .get("type")
             .ok_or_else(|| anyhow!("missing 'type' in profile"))?;
         let scheme = Scheme::from_str(svc)?;
-        match scheme {
-            Scheme::Azblob => Ok((
-                Operator::from_map::<services::Azblob>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Azdfs => Ok((
-                Operator::from_map::<services::Azdfs>(profile.clone())?.finish(),
-                path,
-            )),
-            #[cfg(feature = "services-dashmap")]
-            Scheme::Dashmap => Ok((
-                Operator::from_map::<services::Dashmap>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Gcs => Ok((
-                Operator::from_map::<services::Gcs>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Ghac => Ok((
-                Operator::from_map::<services::Ghac>(profile.clone())?.finish(),
-                path,
-            )),
-            #[cfg(feature = "services-hdfs")]
-            Scheme::Hdfs => Ok((
-                Operator::from_map::<services::Hdfs>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Http => Ok((
-                Operator::from_map::<services::Http>(profile.clone())?.finish(),
-                path,
-            )),
-            #[cfg(feature = "services-ftp")]
-            Scheme::Ftp => Ok((
-                Operator::from_map::<services::Ftp>(profile.clone())?.finish(),
-                path,
-            )),
-            #[cfg(feature = "services-ipfs")]
-            Scheme::Ipfs => Ok((
-                Operator::from_map::<services::Ipfs>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Ipmfs => Ok((
-                Operator::from_map::<services::Ipmfs>(profile.clone())?.finish(),
-                path,
-            )),
-            #[cfg(feature = "services-memcached")]
-            Scheme::Memcached => Ok((
-                Operator::from_map::<services::Memcached>(profile.clone())?.finish(),
-                path,
-            )),
-            // ignore the memory backend
-            #[cfg(feature = "services-moka")]
-            Scheme::Moka => Ok((
-                Operator::from_map::<services::Moka>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Obs => Ok((
-                Operator::from_map::<services::Obs>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Oss => Ok((
-                Operator::from_map::<services::Oss>(profile.clone())?.finish(),
-                path,
-            )),
-            #[cfg(feature = "services-redis")]
-            Scheme::Redis => Ok((
-                Operator::from_map::<services::Redis>(profile.clone())?.finish(),
-                path,
-            )),
-            #[cfg(feature = "services-rocksdb")]
-            Scheme::Rocksdb => Ok((
-                Operator::from_map::<services::Rocksdb>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::S3 => Ok((
-                Operator::from_map::<services::S3>(profile.clone())?.finish(),
-                path,
-            )),
-            #[cfg(feature = "services-sled")]
-            Scheme::Sled => Ok((
-                Operator::from_map::<services::Sled>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Webdav => Ok((
-                Operator::from_map::<services::Webdav>(profile.clone())?.finish(),
-                path,
-            )),
-            Scheme::Webhdfs => Ok((
-                Operator::from_map::<services::Webhdfs>(profile.clone())?.finish(),
-                path,
-            )),
-            _ => Err(anyhow!(
+        if !Operator::is_registered(scheme) {
+            return Err(anyhow!(
                 "unknown type '{}' in profile '{}'",
                 scheme,
                 profile_name
-            )),
+            ));
         }
+        let op = Operator::via_map(scheme, profile.clone())?;
+
+        Ok((apply_layers(op, profile)?, path))
     }
 }
 
+/// Parse native URL like `s3://bucket/key` into profile and path.
+///
+/// Credentials will be loaded from the environment by services.
+fn parse_native_url(scheme: &str, s: &str) -> Result<(HashMap<String, String>, String)> {
+    let bucket_key = match Scheme::from_str(scheme) {
+        Ok(Scheme::S3) | Ok(Scheme::Gcs) | Ok(Scheme::Oss) | Ok(Scheme::Obs) => "bucket",
+        Ok(Scheme::Azblob) => "container",
+        Ok(Scheme::Azdfs) => "filesystem",
+        _ => return Err(anyhow!("unknown profile: {}", scheme)),
+    };
+
+    let (bucket, path) = s.split_once('/').unwrap_or((s, ""));
+    if bucket.is_empty() {
+        return Err(anyhow!("missing {} in url: {}://{}", bucket_key, scheme, s));
+    }
+    let profile = HashMap::from([
+        ("type".to_string(), scheme.to_string()),
+        (bucket_key.to_string(), bucket.to_string()),
+    ]);
+    Ok((profile, path.to_string()))
+}
+
+/// Apply layers configured by [`LAYER_OPTIONS`] in profile.
+fn apply_layers(mut op: Operator, profile: &HashMap<String, String>) -> Result<Operator> {
+    if let Some(v) = profile.get("concurrent_limit") {
+        let permits = v
+            .parse()
+            .map_err(|_| anyhow!("invalid concurrent_limit: {}", v))?;
+        op = op.layer(ConcurrentLimitLayer::new(permits));
+    }
+    if let Some(v) = profile.get("logging") {
+        if matches!(v.to_lowercase().as_str(), "on" | "true") {
+            op = op.layer(LoggingLayer::default());
+        }
+    }
+    // Retry should be the outermost layer to retry all errors.
+    if let Some(v) = profile.get("retry") {
+        let max_times = v.parse().map_err(|_| anyhow!("invalid retry: {}", v))?;
+        op = op.layer(RetryLayer::new().with_max_times(max_times));
+    }
+    Ok(op)
+}
+
 #[cfg(test)]
 mod tests {
     use opendal::Scheme;
@@ -392,4 +410,97 @@ enable_virtual_host_style = "on"
         assert_eq!(Scheme::S3, info.scheme());
         assert_eq!("mybucket", info.name());
     }
+
+    #[test]
+    fn test_parse_location_errors() {
+        let cfg = Config {
+            profiles: HashMap::from([
+                (
+                    "unknown".into(),
+                    HashMap::from([("type".into(), "not-a-service".into())]),
+                ),
+                (
+                    "nobucket".into(),
+                    HashMap::from([("type".into(), "s3".into())]),
+                ),
+            ]),
+        };
+        let err = cfg.parse_location("unknown://foo").unwrap_err();
+        assert!(err.to_string().contains("unknown type"));
+
+        // Builder errors of a known service must be kept as is.
+        let err = cfg.parse_location("nobucket://foo").unwrap_err();
+        assert!(!err.to_string().contains("unknown type"));
+        assert!(err.to_string().contains("bucket"));
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        let cfg = Config {
+            profiles: HashMap::from([
+                (
+                    "a".into(),
+                    HashMap::from([
+                        ("type".into(), "s3".into()),
+                        ("bucket".into(), "mybucket".into()),
+                        ("endpoint".into(), "http://127.0.0.1:9000".into()),
+                    ]),
+                ),
+                (
+                    "b".into(),
+                    HashMap::from([
+                        ("type".into(), "s3".into()),
+                        ("bucket".into(), "mybucket".into()),
+                        ("endpoint".into(), "http://127.0.0.1:9001".into()),
+                    ]),
+                ),
+            ]),
+        };
+
+        let (a, path) = cfg.parse_profile("a://foo/1.txt").unwrap();
+        assert_eq!("foo/1.txt", path);
+        assert_eq!(a, cfg.parse_profile("a://bar").unwrap().0);
+        assert_ne!(a, cfg.parse_profile("b://foo/1.txt").unwrap().0);
+
+        let (fs, _) = cfg.parse_profile("/tmp/1.txt").unwrap();
+        assert_eq!(Some(&"fs".to_string()), fs.get("type"));
+        assert_eq!(Some(&"/".to_string()), fs.get("root"));
+    }
+
+    #[test]
+    fn test_parse_native_url() {
+        let cfg = Config::default();
+        let (op, path) = cfg.parse_location("s3://mybucket/foo/1.txt").unwrap();
+        assert_eq!("foo/1.txt", path);
+        let info = op.info();
+        assert_eq!(Scheme::S3, info.scheme());
+        assert_eq!("mybucket", info.name());
+
+        assert!(cfg.parse_location("s3:///foo").is_err());
+        assert!(cfg.parse_location("unknown://foo").is_err());
+    }
+
+    #[test]
+    fn test_save_to_file() -> Result<()> {
+        let tmpfile = env::temp_dir().join("oli3/config.toml");
+        let mut cfg = Config::default();
+        cfg.set_profile(
+            "mys3",
+            HashMap::from([
+                ("type".into(), "s3".into()),
+                ("bucket".into(), "mybucket".into()),
+                ("retry".into(), "3".into()),
+            ]),
+        );
+        cfg.save_to_file(&tmpfile)?;
+
+        let mut cfg = Config::load_from_file(&tmpfile)?;
+        assert_eq!(cfg.profile_names(), ["mys3"]);
+        assert_eq!(cfg.profile("mys3").unwrap()["retry"], "3");
+        cfg.parse_location("mys3://foo")?;
+
+        assert!(cfg.remove_profile("mys3"));
+        assert!(!cfg.remove_profile("mys3"));
+        Ok(())
+    }
 }
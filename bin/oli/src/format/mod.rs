@@ -0,0 +1,295 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Format provides the shared output formatting of all commands.
+//!
+//! Commands that print metadata should call [`output_args`] to accept
+//! `--json`, `--jsonl` and `-h`, so that every command behaves the same.
+
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use clap::Arg;
+use clap::ArgAction;
+use clap::ArgMatches;
+use clap::Command;
+use opendal::EntryMode;
+use opendal::Metadata;
+use serde::Serialize;
+use serde::Serializer;
+
+/// Format of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Human friendly text.
+    Text,
+    /// A JSON document, lists will be printed as an array.
+    Json,
+    /// JSON lines, one JSON object per line.
+    Jsonl,
+}
+
+/// Output options parsed from args.
+#[derive(Debug, Clone, Copy)]
+pub struct Output {
+    pub format: Format,
+    pub human: bool,
+}
+
+impl Output {
+    pub fn from_args(args: &ArgMatches) -> Output {
+        let format = if args.get_flag("json") {
+            Format::Json
+        } else if args.get_flag("jsonl") {
+            Format::Jsonl
+        } else {
+            Format::Text
+        };
+
+        Output {
+            format,
+            human: args.get_flag("human-readable"),
+        }
+    }
+
+    /// Format size in text output.
+    pub fn size(&self, size: u64) -> String {
+        if self.human {
+            human_size(size)
+        } else {
+            size.to_string()
+        }
+    }
+
+    /// Print a single object.
+    pub fn print<T: Serialize>(&self, v: &T, text: impl FnOnce(&T) -> String) -> Result<()> {
+        match self.format {
+            Format::Text => println!("{}", text(v)),
+            Format::Json => println!("{}", serde_json::to_string_pretty(v)?),
+            Format::Jsonl => println!("{}", serde_json::to_string(v)?),
+        }
+        Ok(())
+    }
+
+    /// Create a printer to print a list of objects.
+    pub fn printer<T: Serialize>(&self) -> Printer<T> {
+        Printer {
+            output: *self,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Printer prints a list of objects, text and JSON lines will be
+/// printed as soon as possible while JSON will be printed after finished.
+pub struct Printer<T> {
+    output: Output,
+    items: Vec<T>,
+}
+
+impl<T: Serialize> Printer<T> {
+    pub fn print(&mut self, v: T, text: impl FnOnce(&T) -> String) -> Result<()> {
+        match self.output.format {
+            Format::Text => println!("{}", text(&v)),
+            Format::Json => self.items.push(v),
+            Format::Jsonl => println!("{}", serde_json::to_string(&v)?),
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<()> {
+        if self.output.format == Format::Json {
+            println!("{}", serde_json::to_string_pretty(&self.items)?);
+        }
+        Ok(())
+    }
+}
+
+/// Add the output args to command.
+///
+/// `-h` is used by `--human-readable` like coreutils does, so the help
+/// flag will only be available as `--help`.
+pub fn output_args(cmd: Command) -> Command {
+    cmd.disable_help_flag(true)
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .help("Print help")
+                .action(ArgAction::Help),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print output as JSON")
+                .conflicts_with("jsonl")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("jsonl")
+                .long("jsonl")
+                .help("Print output as JSON lines")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("human-readable")
+                .long("human-readable")
+                .short('h')
+                .help("Print sizes in human readable format, like 1.5K and 234M")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+/// Entry is the output of an entry's metadata.
+#[derive(Debug, Serialize)]
+pub struct Entry {
+    pub path: String,
+    #[serde(serialize_with = "serialize_mode")]
+    pub mode: EntryMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+impl Entry {
+    /// Build entry with mode only.
+    pub fn new(path: &str, meta: &Metadata) -> Entry {
+        Entry {
+            path: path.to_string(),
+            mode: meta.mode(),
+            size: None,
+            last_modified: None,
+            etag: None,
+            content_type: None,
+        }
+    }
+
+    /// Build entry with mode, size, last_modified and etag.
+    ///
+    /// The metadata must contain all of them, either queried by
+    /// `Metakey` or returned by `stat`.
+    pub fn long(path: &str, meta: &Metadata) -> Entry {
+        Entry {
+            size: Some(meta.content_length()),
+            last_modified: meta.last_modified().map(format_time),
+            etag: meta.etag().map(|v| v.to_string()),
+            ..Entry::new(path, meta)
+        }
+    }
+
+    /// Build entry with all metadata that returned by `stat`.
+    pub fn stat(path: &str, meta: &Metadata) -> Entry {
+        Entry {
+            content_type: meta.content_type().map(|v| v.to_string()),
+            ..Entry::long(path, meta)
+        }
+    }
+
+    /// Format entry in the way like `stat`, one field per line.
+    pub fn to_stat_text(&self, output: &Output) -> String {
+        let mut lines = vec![format!("path: {}", self.path)];
+        if let Some(size) = self.size {
+            lines.push(format!("size: {}", output.size(size)));
+        }
+        if let Some(etag) = &self.etag {
+            lines.push(format!("etag: {etag}"));
+        }
+        lines.push(format!("type: {}", self.mode));
+        if let Some(content_type) = &self.content_type {
+            lines.push(format!("content-type: {content_type}"));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            lines.push(format!("last-modified: {last_modified}"));
+        }
+        lines.join("\n")
+    }
+
+    /// Format entry in the way like `ls -l`.
+    pub fn to_long_text(&self, output: &Output) -> String {
+        let mode = match self.mode {
+            EntryMode::DIR => 'd',
+            EntryMode::FILE => '-',
+            EntryMode::Unknown => '?',
+        };
+        format!(
+            "{mode} {:>10} {:<20} {} {}",
+            self.size.map(|v| output.size(v)).unwrap_or_default(),
+            self.last_modified.as_deref().unwrap_or("-"),
+            self.etag.as_deref().unwrap_or("-"),
+            self.path
+        )
+    }
+}
+
+fn serialize_mode<S: Serializer>(mode: &EntryMode, s: S) -> Result<S::Ok, S::Error> {
+    s.collect_str(mode)
+}
+
+/// Format time in RFC 3339.
+pub fn format_time(t: DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Format size with binary unit like `1.5K`, `234M` and `2.0G`.
+pub fn human_size(size: u64) -> String {
+    const UNITS: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+
+    if size < 1024 {
+        return size.to_string();
+    }
+    let mut v = size as f64;
+    let mut unit = "";
+    for u in UNITS {
+        v /= 1024.0;
+        unit = u;
+        if v < 1024.0 {
+            break;
+        }
+    }
+    if v < 10.0 {
+        format!("{v:.1}{unit}")
+    } else {
+        format!("{v:.0}{unit}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_size() {
+        let cases = vec![
+            (0, "0"),
+            (1023, "1023"),
+            (1024, "1.0K"),
+            (1536, "1.5K"),
+            (10 * 1024, "10K"),
+            (234 * 1024 * 1024, "234M"),
+            (2 * 1024 * 1024 * 1024, "2.0G"),
+        ];
+
+        for (size, expected) in cases {
+            assert_eq!(human_size(size), expected, "{size}");
+        }
+    }
+}
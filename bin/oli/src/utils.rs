@@ -0,0 +1,43 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Utils shared by commands.
+
+/// Make sure the path points to a dir so that it can be joined with
+/// relative paths.
+pub fn normalize_dir(path: String) -> String {
+    if path.is_empty() || path == "/" {
+        String::new()
+    } else if path.ends_with('/') {
+        path
+    } else {
+        format!("{path}/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_dir() {
+        assert_eq!(normalize_dir(String::new()), "");
+        assert_eq!(normalize_dir("/".to_string()), "");
+        assert_eq!(normalize_dir("a/b".to_string()), "a/b/");
+        assert_eq!(normalize_dir("a/b/".to_string()), "a/b/");
+    }
+}
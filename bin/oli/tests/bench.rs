@@ -0,0 +1,55 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[tokio::test]
+async fn test_bench() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-bench");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("bench")
+        .arg(format!("{}/", dir.display()))
+        .arg("--file-size")
+        .arg("1K")
+        .arg("--range-size")
+        .arg("100")
+        .arg("--object-size")
+        .arg("10")
+        .arg("--ops")
+        .arg("5");
+    // Anchor names so that `read` will not be matched by `range-read`.
+    let report = |name: &str| predicate::str::is_match(format!(r"(?m)^{name}\s+ops:")).unwrap();
+    cmd.assert().success().stdout(
+        report("write")
+            .and(report("list"))
+            .and(report("read"))
+            .and(report("range-read")),
+    );
+
+    // Test data must be cleaned up.
+    assert_eq!(fs::read_dir(&dir)?.count(), 0);
+    Ok(())
+}
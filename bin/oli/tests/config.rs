@@ -0,0 +1,88 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[tokio::test]
+async fn test_config_profiles() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-config");
+    let _ = fs::remove_dir_all(&dir);
+    let config_path = dir.join("config.toml");
+    let root = dir.join("data");
+    fs::create_dir_all(&root)?;
+    fs::write(root.join("1.txt"), "hello")?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("config")
+        .arg("--config")
+        .arg(config_path.as_os_str())
+        .arg("add")
+        .arg("local")
+        .arg("type=fs")
+        .arg(format!("root={}", root.display()))
+        .arg("retry=3")
+        .arg("token=abc");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("config")
+        .arg("--config")
+        .arg(config_path.as_os_str())
+        .arg("list");
+    cmd.assert().success().stdout("local\n");
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("config")
+        .arg("--config")
+        .arg(config_path.as_os_str())
+        .arg("show")
+        .arg("local");
+    cmd.assert().success().stdout(
+        predicate::str::contains("retry = 3")
+            .and(predicate::str::contains("token = ******"))
+            .and(predicate::str::contains("abc").not()),
+    );
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("cat")
+        .arg("--config")
+        .arg(config_path.as_os_str())
+        .arg("local://1.txt");
+    cmd.assert().success().stdout("hello");
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("config")
+        .arg("--config")
+        .arg(config_path.as_os_str())
+        .arg("remove")
+        .arg("local");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("config")
+        .arg("--config")
+        .arg(config_path.as_os_str())
+        .arg("list");
+    cmd.assert().success().stdout("");
+    Ok(())
+}
@@ -43,3 +43,52 @@ async fn test_basic_cp() -> Result<()> {
     assert_eq!(expect, actual);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_recursive_cp_with_checkpoint() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-cp-recursive");
+    let _ = fs::remove_dir_all(&dir);
+    let src_dir = dir.join("src");
+    let dst_dir = dir.join("dst");
+    let checkpoint = dir.join("checkpoint");
+    fs::create_dir_all(src_dir.join("sub"))?;
+    fs::write(src_dir.join("a.txt"), "hello")?;
+    fs::write(src_dir.join("sub/b.txt"), "world")?;
+
+    // Pretend that `a.txt` has been copied by an interrupted run, while
+    // `sub/b.txt` has only been copied to another destination.
+    let src_path = src_dir.to_string_lossy();
+    fs::write(
+        &checkpoint,
+        format!(
+            "{src}/a.txt\tfs:///{dst}/a.txt\n{src}/sub/b.txt\tfs:///other/sub/b.txt\n",
+            src = src_path.trim_start_matches('/'),
+            dst = dst_dir.to_string_lossy().trim_start_matches('/'),
+        ),
+    )?;
+
+    // Checkpoint is only supported by recursive copy.
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("cp")
+        .arg("--checkpoint")
+        .arg(checkpoint.as_os_str())
+        .arg(format!("{src_path}/a.txt"))
+        .arg(format!("{}/a.txt", dst_dir.display()));
+    cmd.assert().failure();
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("cp")
+        .arg("-r")
+        .arg("--jobs")
+        .arg("4")
+        .arg("--checkpoint")
+        .arg(checkpoint.as_os_str())
+        .arg(format!("{src_path}/"))
+        .arg(format!("{}/", dst_dir.display()));
+    cmd.assert().success();
+
+    assert!(!dst_dir.join("a.txt").exists());
+    assert_eq!(fs::read_to_string(dst_dir.join("sub/b.txt"))?, "world");
+    assert!(!checkpoint.exists());
+    Ok(())
+}
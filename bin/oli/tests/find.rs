@@ -0,0 +1,54 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[tokio::test]
+async fn test_basic_find_and_du() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-find");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("sub"))?;
+    fs::write(dir.join("a.txt"), "hello")?;
+    fs::write(dir.join("sub/b.txt"), "hello, world")?;
+    fs::write(dir.join("sub/c.log"), "log")?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("find")
+        .arg("--name")
+        .arg("*.txt")
+        .arg("--min-size")
+        .arg("10")
+        .arg(format!("{}/", dir.display()));
+    cmd.assert().success().stdout(
+        predicate::str::contains("sub/b.txt")
+            .and(predicate::str::contains("a.txt").not())
+            .and(predicate::str::contains("c.log").not()),
+    );
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("du").arg(format!("{}/", dir.display()));
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("20\t3 files"));
+    Ok(())
+}
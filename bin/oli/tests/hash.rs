@@ -0,0 +1,67 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+
+#[tokio::test]
+async fn test_hash() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-hash");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("hello.txt");
+    fs::write(&path, "hello")?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("hash").arg(path.as_os_str());
+    cmd.assert().success().stdout(format!(
+        "5d41402abc4b2a76b9719d911017c592  {}\n",
+        path.display()
+    ));
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("hash")
+        .arg("--algorithm")
+        .arg("crc32c")
+        .arg(path.as_os_str());
+    cmd.assert()
+        .success()
+        .stdout(format!("9a71bb4c  {}\n", path.display()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cp_with_verify() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-cp-verify");
+    fs::create_dir_all(&dir)?;
+    let src_path = dir.join("src.txt");
+    let dst_path = dir.join("dst.txt");
+    fs::write(&src_path, "hello")?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("cp")
+        .arg("--verify")
+        .arg(src_path.as_os_str())
+        .arg(dst_path.as_os_str());
+    cmd.assert().success();
+
+    assert_eq!("hello", fs::read_to_string(&dst_path)?);
+    Ok(())
+}
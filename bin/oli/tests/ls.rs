@@ -0,0 +1,59 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[tokio::test]
+async fn test_ls_output() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-ls");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("a.txt"), "x".repeat(2048))?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("ls")
+        .arg("-l")
+        .arg("-h")
+        .arg(format!("{}/", dir.display()));
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_match(r"^- +2\.0K \S+ - a\.txt\n$")?);
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("ls")
+        .arg("-l")
+        .arg("--jsonl")
+        .arg(format!("{}/", dir.display()));
+    cmd.assert().success().stdout(predicate::str::starts_with(
+        r#"{"path":"a.txt","mode":"file","size":2048,"last_modified":"#,
+    ));
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("stat")
+        .arg("--json")
+        .arg(dir.join("a.txt").as_os_str());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#""size": 2048"#));
+    Ok(())
+}
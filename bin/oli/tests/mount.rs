@@ -0,0 +1,99 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#![cfg(target_os = "linux")]
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+
+#[test]
+fn test_mount() -> Result<()> {
+    if !Path::new("/dev/fuse").exists() {
+        eprintln!("/dev/fuse not found, skip mount test");
+        return Ok(());
+    }
+
+    let dir = env::temp_dir().join("oli-test-mount");
+    let _ = fs::remove_dir_all(&dir);
+    let data = dir.join("data");
+    let mnt = dir.join("mnt");
+    fs::create_dir_all(data.join("sub"))?;
+    fs::create_dir_all(&mnt)?;
+    fs::write(data.join("hello.txt"), "hello")?;
+
+    let mut child = Command::cargo_bin("oli")?
+        .arg("mount")
+        .arg(format!("{}/", data.display()))
+        .arg(mnt.as_os_str())
+        .arg("--cache-ttl")
+        .arg("0")
+        .spawn()?;
+
+    let res = (|| -> Result<()> {
+        for _ in 0..100 {
+            if mnt.join("hello.txt").exists() {
+                break;
+            }
+            sleep(Duration::from_millis(100));
+        }
+        assert_eq!(fs::read_to_string(mnt.join("hello.txt"))?, "hello");
+
+        let mut names: Vec<_> = fs::read_dir(&mnt)?
+            .map(|e| e.map(|e| e.file_name().to_string_lossy().to_string()))
+            .collect::<Result<_, _>>()?;
+        names.sort();
+        assert_eq!(names, ["hello.txt", "sub"]);
+
+        fs::write(mnt.join("sub/new.txt"), "world")?;
+        assert_eq!(fs::read_to_string(data.join("sub/new.txt"))?, "world");
+
+        fs::rename(mnt.join("sub/new.txt"), mnt.join("renamed.txt"))?;
+        assert!(!data.join("sub/new.txt").exists());
+        assert_eq!(fs::read_to_string(data.join("renamed.txt"))?, "world");
+
+        fs::remove_file(mnt.join("renamed.txt"))?;
+        assert!(!data.join("renamed.txt").exists());
+
+        // Open without truncate keeps the content.
+        let mut f = fs::OpenOptions::new()
+            .append(true)
+            .open(mnt.join("hello.txt"))?;
+        f.write_all(b", world")?;
+        drop(f);
+        assert_eq!(fs::read_to_string(data.join("hello.txt"))?, "hello, world");
+
+        fs::write(mnt.join("sub/a.txt"), "a")?;
+        fs::rename(mnt.join("sub"), mnt.join("moved"))?;
+        assert!(!data.join("sub").exists());
+        assert_eq!(fs::read_to_string(data.join("moved/a.txt"))?, "a");
+        assert_eq!(fs::read_to_string(mnt.join("moved/a.txt"))?, "a");
+        Ok(())
+    })();
+
+    // SAFETY: the pid is a valid child process.
+    unsafe { libc::kill(child.id() as i32, libc::SIGINT) };
+    child.wait()?;
+    res
+}
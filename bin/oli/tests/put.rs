@@ -0,0 +1,52 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::env;
+use std::fs;
+
+#[tokio::test]
+async fn test_basic_put() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-put");
+    fs::create_dir_all(&dir)?;
+    let dst_path = dir.join("dst.txt");
+    let expect = "hello";
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("put").arg(dst_path.as_os_str()).write_stdin(expect);
+    cmd.assert().success().stdout(predicate::str::is_empty());
+
+    assert_eq!(expect, fs::read_to_string(&dst_path)?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_basic_tee() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-tee");
+    fs::create_dir_all(&dir)?;
+    let dst_path = dir.join("dst.txt");
+    let expect = "hello";
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("tee").arg(dst_path.as_os_str()).write_stdin(expect);
+    cmd.assert().success().stdout(expect);
+
+    assert_eq!(expect, fs::read_to_string(&dst_path)?);
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fs;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[tokio::test]
+async fn test_basic_shell() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-shell");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("data/sub"))?;
+    fs::write(dir.join("data/sub/a.txt"), "hello")?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("shell")
+        .arg("--config")
+        .arg(dir.join("config.toml").as_os_str())
+        .arg(format!("{}/", dir.join("data").display()))
+        .write_stdin("cd sub\ncp a.txt b.txt\nls\ncd ..\nrm sub/a.txt\nls sub\nexit\n");
+    // Entries could be listed in any order.
+    cmd.assert()
+        .success()
+        .stdout(predicate::function(|s: &str| {
+            let mut lines = s.lines().collect::<Vec<_>>();
+            lines.sort();
+            lines == ["a.txt", "b.txt", "b.txt"]
+        }));
+
+    assert!(dir.join("history").exists());
+    assert_eq!(fs::read_to_string(dir.join("data/sub/b.txt"))?, "hello");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shell_stat() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-shell-stat");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("data"))?;
+    fs::write(dir.join("data/a.txt"), "hello")?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("shell")
+        .arg("--config")
+        .arg(dir.join("config.toml").as_os_str())
+        .arg(format!("{}/", dir.join("data").display()))
+        .write_stdin("stat a.txt\nexit\n");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("size: 5\n"))
+        .stdout(predicate::str::contains("type: file\n"));
+    Ok(())
+}
@@ -0,0 +1,81 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+
+#[tokio::test]
+async fn test_basic_sync() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-sync");
+    let _ = fs::remove_dir_all(&dir);
+    let src_dir = dir.join("src");
+    let dst_dir = dir.join("dst");
+    fs::create_dir_all(src_dir.join("sub"))?;
+    fs::create_dir_all(&dst_dir)?;
+    fs::write(src_dir.join("a.txt"), "hello")?;
+    fs::write(src_dir.join("sub/b.txt"), "world")?;
+    fs::write(src_dir.join("c.log"), "ignored")?;
+    fs::write(dst_dir.join("stale.txt"), "stale")?;
+
+    // Dry run should not touch destination.
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("sync")
+        .arg("--delete")
+        .arg("--dry-run")
+        .arg(format!("{}/", src_dir.display()))
+        .arg(format!("{}/", dst_dir.display()));
+    cmd.assert().success();
+    assert!(!dst_dir.join("a.txt").exists());
+    assert!(dst_dir.join("stale.txt").exists());
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("sync")
+        .arg("--delete")
+        .arg("--exclude")
+        .arg("*.log")
+        .arg(format!("{}/", src_dir.display()))
+        .arg(format!("{}/", dst_dir.display()));
+    cmd.assert().success();
+
+    assert_eq!(fs::read_to_string(dst_dir.join("a.txt"))?, "hello");
+    assert_eq!(fs::read_to_string(dst_dir.join("sub/b.txt"))?, "world");
+    assert!(!dst_dir.join("c.log").exists());
+    assert!(!dst_dir.join("stale.txt").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_missing_source() -> Result<()> {
+    let dir = env::temp_dir().join("oli-test-sync-missing");
+    let _ = fs::remove_dir_all(&dir);
+    let dst_dir = dir.join("dst");
+    fs::create_dir_all(&dst_dir)?;
+    fs::write(dst_dir.join("a.txt"), "hello")?;
+
+    let mut cmd = Command::cargo_bin("oli")?;
+    cmd.arg("sync")
+        .arg("--delete")
+        .arg(format!("{}/", dir.join("not-exist").display()))
+        .arg(format!("{}/", dst_dir.display()));
+    cmd.assert().failure();
+    assert_eq!(fs::read_to_string(dst_dir.join("a.txt"))?, "hello");
+    Ok(())
+}
@@ -37,6 +37,7 @@ typedef enum opendal_code {
      */
     OPENDAL_ERROR,
     /*
+     OpenDAL don't know what happened here, and no actions other than just
      returning it back. For example, s3 returns an internal service error.
      */
     OPENDAL_UNEXPECTED,
@@ -76,8 +77,31 @@ typedef enum opendal_code {
      The given file paths are same.
      */
     OPENDAL_IS_SAME_FILE,
+    /*
+     The condition of this operation is not match.
+     */
+    OPENDAL_PRECONDITION_FAILED,
 } opendal_code;
 
+/*
+ The whence of [`opendal_reader_seek`], which has the same meaning
+ as `SEEK_SET`, `SEEK_CUR` and `SEEK_END` of `fseek`.
+ */
+typedef enum opendal_seek_whence {
+    /*
+     Seek from the start of the content.
+     */
+    OPENDAL_SEEK_SET,
+    /*
+     Seek from the current position.
+     */
+    OPENDAL_SEEK_CUR,
+    /*
+     Seek from the end of the content.
+     */
+    OPENDAL_SEEK_END,
+} opendal_seek_whence;
+
 /*
  BlockingOperator is the entry for all public blocking APIs.
 
@@ -110,6 +134,53 @@ typedef enum opendal_code {
  */
 typedef struct BlockingOperator BlockingOperator;
 
+/*
+ The [`opendal_blocking_lister`] iterates over the entries under a
+ directory, it is returned by [`opendal_operator_blocking_list`] and
+ [`opendal_operator_blocking_scan`]. Free it by [`opendal_lister_free`]
+ after use.
+ */
+typedef struct opendal_blocking_lister opendal_blocking_lister;
+
+/*
+ The [`opendal_list_entry`] is an entry returned by [`opendal_lister_next`].
+ Free it by [`opendal_list_entry_free`] after use.
+ */
+typedef struct opendal_list_entry opendal_list_entry;
+
+/*
+ The [`opendal_metadata`] carries the metadata of a path, which is returned
+ by [`opendal_operator_stat`]. Use the `opendal_metadata_*` functions to
+ access its fields, and free it by [`opendal_metadata_free`] after use.
+ */
+typedef struct opendal_metadata opendal_metadata;
+
+/*
+ The [`opendal_operator_options`] holds the options that are used to
+ construct an operator in [`opendal_operator_new`]. It is created by
+ [`opendal_operator_options_new`], filled by [`opendal_operator_options_set`]
+ and must be freed by [`opendal_operator_options_free`] after use.
+ */
+typedef struct opendal_operator_options opendal_operator_options;
+
+/*
+ The [`opendal_reader`] reads the content of a path incrementally, it is
+ returned by [`opendal_operator_blocking_reader`]. Free it by
+ [`opendal_reader_free`] after use.
+ */
+typedef struct opendal_reader opendal_reader;
+
+/*
+ The [`opendal_writer`] writes content into a path incrementally, it is
+ returned by [`opendal_operator_blocking_writer`]. The content is not
+ guaranteed to be stored until [`opendal_writer_close`] succeeds. Free it
+ by [`opendal_writer_free`] after use.
+
+ Written bytes are buffered and appended in chunks of 8 MiB, so that
+ services with a minimum part size like s3 can accept small writes.
+ */
+typedef struct opendal_writer opendal_writer;
+
 /*
  The [`opendal_operator_ptr`] owns a pointer to a [`od::BlockingOperator`].
  It is also the key struct that OpenDAL's APIs access the real
@@ -127,6 +198,8 @@ typedef const struct BlockingOperator* opendal_operator_ptr;
  in Rust, it will not be deallocated automatically like what
  has been done in Rust. Instead, you have to call [`opendal_free_bytes`]
  to free the heap memory to avoid memory leak.
+
+ When passed into OpenDAL, the `data` could be NULL if the `len` is 0.
  */
 typedef struct opendal_bytes {
     const uint8_t* data;
@@ -154,13 +227,82 @@ typedef struct opendal_result_is_exist {
     enum opendal_code code;
 } opendal_result_is_exist;
 
+/*
+ The result type for [`opendal_operator_stat()`], the field `meta` contains
+ the metadata of the path, and the field `code` contains the corresponding
+ error code. If the error code is not OPENDAL_OK, the `meta` field points
+ to NULL.
+ */
+typedef struct opendal_result_stat {
+    struct opendal_metadata* meta;
+    enum opendal_code code;
+} opendal_result_stat;
+
+/*
+ The result type for [`opendal_operator_blocking_list()`] and
+ [`opendal_operator_blocking_scan()`]. If the error code is not
+ OPENDAL_OK, the `lister` field points to NULL.
+ */
+typedef struct opendal_result_list {
+    struct opendal_blocking_lister* lister;
+    enum opendal_code code;
+} opendal_result_list;
+
+/*
+ The result type for [`opendal_operator_blocking_reader()`]. If the error
+ code is not OPENDAL_OK, the `reader` field points to NULL.
+ */
+typedef struct opendal_result_reader {
+    struct opendal_reader* reader;
+    enum opendal_code code;
+} opendal_result_reader;
+
+/*
+ The result type for [`opendal_operator_blocking_writer()`]. If the error
+ code is not OPENDAL_OK, the `writer` field points to NULL.
+ */
+typedef struct opendal_result_writer {
+    struct opendal_writer* writer;
+    enum opendal_code code;
+} opendal_result_writer;
+
+/*
+ The result type for [`opendal_lister_next()`]. If the lister is exhausted
+ or the error code is not OPENDAL_OK, the `entry` field points to NULL.
+ */
+typedef struct opendal_result_lister_next {
+    struct opendal_list_entry* entry;
+    enum opendal_code code;
+} opendal_result_lister_next;
+
+/*
+ The result type for [`opendal_reader_read()`], the field `size` contains
+ the number of bytes read into the buffer.
+ */
+typedef struct opendal_result_reader_read {
+    uintptr_t size;
+    enum opendal_code code;
+} opendal_result_reader_read;
+
+/*
+ The result type for [`opendal_reader_seek()`], the field `pos` contains
+ the new position from the start of the content.
+ */
+typedef struct opendal_result_reader_seek {
+    uint64_t pos;
+    enum opendal_code code;
+} opendal_result_reader_seek;
+
 #ifdef __cplusplus
 extern "C" {
 #endif // __cplusplus
 
 /*
- Returns a result type [`opendal_result_op`], with operator_ptr. If the construction succeeds
- the error is nullptr, otherwise it contains the error information.
+ Returns an [`opendal_operator_ptr`] built with the given `scheme` and `options`. If the
+ construction fails, the returned pointer is NULL.
+
+ The `options` could be NULL, which means no option is set. The `options` is not
+ consumed, so you still have to free it by [`opendal_operator_options_free`].
 
  # Safety
 
@@ -169,7 +311,8 @@ extern "C" {
    the string.
  * The `scheme` points to NULL, this function simply returns you a null opendal_operator_ptr
  */
-opendal_operator_ptr opendal_operator_new(const char* scheme);
+opendal_operator_ptr opendal_operator_new(const char* scheme,
+    const struct opendal_operator_options* options);
 
 /*
  Free the allocated operator pointed by [`opendal_operator_ptr`]
@@ -233,11 +376,314 @@ struct opendal_result_read opendal_operator_blocking_read(opendal_operator_ptr o
 struct opendal_result_is_exist opendal_operator_is_exist(opendal_operator_ptr op_ptr,
     const char* path);
 
+/*
+ Stat the path, returns a result with the [`opendal_metadata`] of the path. If the
+ error code is not OPENDAL_OK, the `meta` field of the result points to NULL.
+
+ The returned metadata must be freed by [`opendal_metadata_free`].
+
+ # Safety
+
+ It is [safe] under two cases below
+ * The memory pointed to by `path` must contain a valid nul terminator at the end of
+   the string.
+
+ # Panic
+
+ * If the `path` points to NULL, this function panics
+ */
+struct opendal_result_stat opendal_operator_stat(opendal_operator_ptr op_ptr, const char* path);
+
+/*
+ Delete the path blockingly by operator, returns the error code OPENDAL_OK
+ if succeeds, others otherwise. Deleting a path that doesn't exist is
+ also regarded as success.
+
+ # Safety
+
+ It is [safe] under two cases below
+ * The memory pointed to by `path` must contain a valid nul terminator at the end of
+   the string.
+
+ # Panic
+
+ * If the `path` points to NULL, this function panics
+ */
+enum opendal_code opendal_operator_blocking_delete(opendal_operator_ptr op_ptr, const char* path);
+
+/*
+ Create the directory blockingly by operator, returns the error code OPENDAL_OK
+ if succeeds, others otherwise. The `path` must end with "/".
+
+ # Safety
+
+ It is [safe] under two cases below
+ * The memory pointed to by `path` must contain a valid nul terminator at the end of
+   the string.
+
+ # Panic
+
+ * If the `path` points to NULL, this function panics
+ */
+enum opendal_code opendal_operator_blocking_create_dir(opendal_operator_ptr op_ptr,
+    const char* path);
+
+/*
+ List the entries directly under the directory `path`, returns a result with
+ an [`opendal_blocking_lister`]. If the error code is not OPENDAL_OK, the `lister`
+ field of the result points to NULL.
+
+ The returned lister must be freed by [`opendal_lister_free`].
+
+ # Safety
+
+ It is [safe] under two cases below
+ * The memory pointed to by `path` must contain a valid nul terminator at the end of
+   the string.
+
+ # Panic
+
+ * If the `path` points to NULL, this function panics
+ */
+struct opendal_result_list opendal_operator_blocking_list(opendal_operator_ptr op_ptr,
+    const char* path);
+
+/*
+ Scan the entries under the directory `path` recursively, returns a result with
+ an [`opendal_blocking_lister`]. If the error code is not OPENDAL_OK, the `lister`
+ field of the result points to NULL.
+
+ The returned lister must be freed by [`opendal_lister_free`].
+
+ # Safety
+
+ It is [safe] under two cases below
+ * The memory pointed to by `path` must contain a valid nul terminator at the end of
+   the string.
+
+ # Panic
+
+ * If the `path` points to NULL, this function panics
+ */
+struct opendal_result_list opendal_operator_blocking_scan(opendal_operator_ptr op_ptr,
+    const char* path);
+
+/*
+ Open an [`opendal_reader`] on the path to read its content incrementally. If the
+ error code is not OPENDAL_OK, the `reader` field of the result points to NULL.
+
+ The returned reader must be freed by [`opendal_reader_free`].
+
+ # Safety
+
+ It is [safe] under two cases below
+ * The memory pointed to by `path` must contain a valid nul terminator at the end of
+   the string.
+
+ # Panic
+
+ * If the `path` points to NULL, this function panics
+ */
+struct opendal_result_reader opendal_operator_blocking_reader(opendal_operator_ptr op_ptr,
+    const char* path);
+
+/*
+ Open an [`opendal_writer`] on the path to write its content incrementally. If the
+ error code is not OPENDAL_OK, the `writer` field of the result points to NULL.
+
+ The returned writer must be closed by [`opendal_writer_close`] to store the content,
+ and then freed by [`opendal_writer_free`].
+
+ # Safety
+
+ It is [safe] under two cases below
+ * The memory pointed to by `path` must contain a valid nul terminator at the end of
+   the string.
+
+ # Panic
+
+ * If the `path` points to NULL, this function panics
+ */
+struct opendal_result_writer opendal_operator_blocking_writer(opendal_operator_ptr op_ptr,
+    const char* path);
+
 /*
  Frees the heap memory used by the [`opendal_bytes`]
  */
 void opendal_bytes_free(const struct opendal_bytes* self);
 
+/*
+ Construct a heap-allocated [`opendal_operator_options`] without
+ any option set.
+ */
+struct opendal_operator_options* opendal_operator_options_new(void);
+
+/*
+ Set a key-value pair into the options, the value of an existing key
+ will be overwritten. For example, you could set the root of a fs
+ operator by key "root".
+
+ # Safety
+
+ It is [safe] under two cases below
+ * The memory pointed to by `key` and `value` must contain a valid nul
+   terminator at the end of the string.
+
+ # Panic
+
+ * If the `key` or `value` points to NULL, this function panics
+ */
+void opendal_operator_options_set(struct opendal_operator_options* self,
+    const char* key,
+    const char* value);
+
+/*
+ Frees the heap memory used by the [`opendal_operator_options`]
+
+ # Safety
+
+ The `options` must be either NULL or returned by
+ [`opendal_operator_options_new`], and must not be used after freed.
+ */
+void opendal_operator_options_free(struct opendal_operator_options* options);
+
+/*
+ Returns the content length of the path, in bytes.
+ */
+uint64_t opendal_metadata_content_length(const struct opendal_metadata* self);
+
+/*
+ Returns whether the path is a file.
+ */
+bool opendal_metadata_is_file(const struct opendal_metadata* self);
+
+/*
+ Returns whether the path is a directory.
+ */
+bool opendal_metadata_is_dir(const struct opendal_metadata* self);
+
+/*
+ Returns the last modified time of the path in milliseconds since
+ the unix epoch, or -1 if the underlying service doesn't provide it.
+ */
+int64_t opendal_metadata_last_modified_ms(const struct opendal_metadata* self);
+
+/*
+ Frees the heap memory used by the [`opendal_metadata`]
+
+ # Safety
+
+ The `meta` must be either NULL or returned by [`opendal_operator_stat`],
+ and must not be used after freed.
+ */
+void opendal_metadata_free(struct opendal_metadata* meta);
+
+/*
+ Returns the next entry of the lister. If the lister has been
+ exhausted, the `entry` field of the result points to NULL and the
+ error code is OPENDAL_OK.
+ */
+struct opendal_result_lister_next opendal_lister_next(struct opendal_blocking_lister* self);
+
+/*
+ Frees the heap memory used by the [`opendal_blocking_lister`]
+
+ # Safety
+
+ The `lister` must be either NULL or returned by the list functions,
+ and must not be used after freed.
+ */
+void opendal_lister_free(struct opendal_blocking_lister* lister);
+
+/*
+ Returns the full path of the entry, directories end with "/". The
+ returned string is owned by the entry and is valid until it's freed.
+ */
+const char* opendal_list_entry_path(const struct opendal_list_entry* self);
+
+/*
+ Returns the last component of the entry's path, directories end with
+ "/". The returned string is owned by the entry and is valid until
+ it's freed.
+ */
+const char* opendal_list_entry_name(const struct opendal_list_entry* self);
+
+/*
+ Frees the heap memory used by the [`opendal_list_entry`]
+
+ # Safety
+
+ The `entry` must be either NULL or returned by [`opendal_lister_next`],
+ and must not be used after freed.
+ */
+void opendal_list_entry_free(struct opendal_list_entry* entry);
+
+/*
+ Read at most `len` bytes into `buf`, returns the number of bytes read.
+ A `size` of 0 with error code OPENDAL_OK means the end of content
+ has been reached.
+
+ # Safety
+
+ The `buf` must be valid for writes of `len` bytes.
+
+ # Panic
+
+ * If the `buf` points to NULL, this function panics
+ */
+struct opendal_result_reader_read opendal_reader_read(struct opendal_reader* self,
+    uint8_t* buf,
+    uintptr_t len);
+
+/*
+ Seek the reader to the `offset` relative to `whence`, returns the new
+ position from the start of the content.
+ */
+struct opendal_result_reader_seek opendal_reader_seek(struct opendal_reader* self,
+    int64_t offset,
+    enum opendal_seek_whence whence);
+
+/*
+ Frees the heap memory used by the [`opendal_reader`]
+
+ # Safety
+
+ The `reader` must be either NULL or returned by
+ [`opendal_operator_blocking_reader`], and must not be used after freed.
+ */
+void opendal_reader_free(struct opendal_reader* reader);
+
+/*
+ Append the `bytes` into the writer, the bytes are copied so the
+ caller still owns them after this call.
+
+ The bytes are buffered until 8 MiB are collected or the writer
+ is closed, so errors of the underlying storage could be returned
+ by later calls.
+
+ # Safety
+
+ The `bytes.data` must be valid for reads of `bytes.len` bytes, it's
+ allowed to be NULL if `bytes.len` is 0.
+ */
+enum opendal_code opendal_writer_write(struct opendal_writer* self, struct opendal_bytes bytes);
+
+/*
+ Close the writer and make sure all data have been stored.
+ */
+enum opendal_code opendal_writer_close(struct opendal_writer* self);
+
+/*
+ Frees the heap memory used by the [`opendal_writer`], the content
+ that is not closed will be discarded.
+
+ # Safety
+
+ The `writer` must be either NULL or returned by
+ [`opendal_operator_blocking_writer`], and must not be used after freed.
+ */
+void opendal_writer_free(struct opendal_writer* writer);
+
 #ifdef __cplusplus
 } // extern "C"
 #endif // __cplusplus
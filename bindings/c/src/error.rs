@@ -29,6 +29,7 @@ pub enum opendal_code {
     /// General error
     // todo: make details in the `opendal_error *`
     OPENDAL_ERROR,
+    /// OpenDAL don't know what happened here, and no actions other than just
     /// returning it back. For example, s3 returns an internal service error.
     OPENDAL_UNEXPECTED,
     /// Underlying service doesn't support this operation.
@@ -49,6 +50,8 @@ pub enum opendal_code {
     OPENDAL_RATE_LIMITED,
     /// The given file paths are same.
     OPENDAL_IS_SAME_FILE,
+    /// The condition of this operation is not match.
+    OPENDAL_PRECONDITION_FAILED,
 }
 
 impl opendal_code {
@@ -73,9 +76,11 @@ impl opendal_error {
             od::ErrorKind::AlreadyExists => opendal_code::OPENDAL_ALREADY_EXISTS,
             od::ErrorKind::RateLimited => opendal_code::OPENDAL_RATE_LIMITED,
             od::ErrorKind::IsSameFile => opendal_code::OPENDAL_IS_SAME_FILE,
-            // if this is triggered, check the [`core`] crate and add a
-            // new error code accordingly
-            _ => panic!("The newly added ErrorKind in core crate is not handled in C bindings"),
+            od::ErrorKind::PreconditionFailed => opendal_code::OPENDAL_PRECONDITION_FAILED,
+            // unwinding across the FFI boundary is undefined behavior, so the
+            // newly added ErrorKind in core crate falls back to the general
+            // error code until a new one is added accordingly
+            _ => opendal_code::OPENDAL_ERROR,
         }
     }
 }
@@ -18,7 +18,6 @@
 #![allow(non_camel_case_types)]
 
 mod error;
-mod macros;
 mod result;
 mod types;
 
@@ -29,13 +28,25 @@ use std::str::FromStr;
 use ::opendal as od;
 use error::opendal_code;
 use result::opendal_result_is_exist;
+use result::opendal_result_list;
 use result::opendal_result_read;
+use result::opendal_result_reader;
+use result::opendal_result_stat;
+use result::opendal_result_writer;
 
+use crate::types::opendal_blocking_lister;
 use crate::types::opendal_bytes;
+use crate::types::opendal_metadata;
+use crate::types::opendal_operator_options;
 use crate::types::opendal_operator_ptr;
+use crate::types::opendal_reader;
+use crate::types::opendal_writer;
 
-/// Returns a result type [`opendal_result_op`], with operator_ptr. If the construction succeeds
-/// the error is nullptr, otherwise it contains the error information.
+/// Returns an [`opendal_operator_ptr`] built with the given `scheme` and `options`. If the
+/// construction fails, the returned pointer is NULL.
+///
+/// The `options` could be NULL, which means no option is set. The `options` is not
+/// consumed, so you still have to free it by [`opendal_operator_options_free`].
 ///
 /// # Safety
 ///
@@ -44,7 +55,10 @@ use crate::types::opendal_operator_ptr;
 ///   the string.
 /// * The `scheme` points to NULL, this function simply returns you a null opendal_operator_ptr
 #[no_mangle]
-pub unsafe extern "C" fn opendal_operator_new(scheme: *const c_char) -> opendal_operator_ptr {
+pub unsafe extern "C" fn opendal_operator_new(
+    scheme: *const c_char,
+    options: *const opendal_operator_options,
+) -> opendal_operator_ptr {
     if scheme.is_null() {
         return opendal_operator_ptr::null();
     }
@@ -57,16 +71,18 @@ pub unsafe extern "C" fn opendal_operator_new(scheme: *const c_char) -> opendal_
         }
     };
 
-    // todo: api for map construction
-    let map = HashMap::default();
+    let map = if options.is_null() {
+        HashMap::default()
+    } else {
+        unsafe { &*options }.to_map()
+    };
 
-    let op = match scheme {
-        od::Scheme::Memory => generate_operator!(od::services::Memory, map),
-        _ => {
+    let op = match od::Operator::via_map(scheme, map) {
+        Ok(op) => op.blocking(),
+        Err(_) => {
             return opendal_operator_ptr::null();
         }
-    }
-    .blocking();
+    };
 
     // this prevents the operator memory from being dropped by the Box
     let op = Box::leak(Box::new(op));
@@ -192,3 +208,250 @@ pub unsafe extern "C" fn opendal_operator_is_exist(
         },
     }
 }
+
+/// Stat the path, returns a result with the [`opendal_metadata`] of the path. If the
+/// error code is not OPENDAL_OK, the `meta` field of the result points to NULL.
+///
+/// The returned metadata must be freed by [`opendal_metadata_free`].
+///
+/// # Safety
+///
+/// It is [safe] under two cases below
+/// * The memory pointed to by `path` must contain a valid nul terminator at the end of
+///   the string.
+///
+/// # Panic
+///
+/// * If the `path` points to NULL, this function panics
+#[no_mangle]
+pub unsafe extern "C" fn opendal_operator_stat(
+    op_ptr: opendal_operator_ptr,
+    path: *const c_char,
+) -> opendal_result_stat {
+    if path.is_null() {
+        panic!("The path given is pointing at NULL");
+    }
+
+    let op = op_ptr.get_ref();
+    let path = unsafe { std::ffi::CStr::from_ptr(path).to_str().unwrap() };
+    match op.stat(path) {
+        Ok(m) => opendal_result_stat {
+            meta: Box::into_raw(Box::new(opendal_metadata::new(m))),
+            code: opendal_code::OPENDAL_OK,
+        },
+        Err(e) => opendal_result_stat {
+            meta: std::ptr::null_mut(),
+            code: opendal_code::from_opendal_error(e),
+        },
+    }
+}
+
+/// Delete the path blockingly by operator, returns the error code OPENDAL_OK
+/// if succeeds, others otherwise. Deleting a path that doesn't exist is
+/// also regarded as success.
+///
+/// # Safety
+///
+/// It is [safe] under two cases below
+/// * The memory pointed to by `path` must contain a valid nul terminator at the end of
+///   the string.
+///
+/// # Panic
+///
+/// * If the `path` points to NULL, this function panics
+#[no_mangle]
+pub unsafe extern "C" fn opendal_operator_blocking_delete(
+    op_ptr: opendal_operator_ptr,
+    path: *const c_char,
+) -> opendal_code {
+    if path.is_null() {
+        panic!("The path given is pointing at NULL");
+    }
+
+    let op = op_ptr.get_ref();
+    let path = unsafe { std::ffi::CStr::from_ptr(path).to_str().unwrap() };
+    match op.delete(path) {
+        Ok(_) => opendal_code::OPENDAL_OK,
+        Err(e) => opendal_code::from_opendal_error(e),
+    }
+}
+
+/// Create the directory blockingly by operator, returns the error code OPENDAL_OK
+/// if succeeds, others otherwise. The `path` must end with "/".
+///
+/// # Safety
+///
+/// It is [safe] under two cases below
+/// * The memory pointed to by `path` must contain a valid nul terminator at the end of
+///   the string.
+///
+/// # Panic
+///
+/// * If the `path` points to NULL, this function panics
+#[no_mangle]
+pub unsafe extern "C" fn opendal_operator_blocking_create_dir(
+    op_ptr: opendal_operator_ptr,
+    path: *const c_char,
+) -> opendal_code {
+    if path.is_null() {
+        panic!("The path given is pointing at NULL");
+    }
+
+    let op = op_ptr.get_ref();
+    let path = unsafe { std::ffi::CStr::from_ptr(path).to_str().unwrap() };
+    match op.create_dir(path) {
+        Ok(_) => opendal_code::OPENDAL_OK,
+        Err(e) => opendal_code::from_opendal_error(e),
+    }
+}
+
+/// List the entries directly under the directory `path`, returns a result with
+/// an [`opendal_blocking_lister`]. If the error code is not OPENDAL_OK, the `lister`
+/// field of the result points to NULL.
+///
+/// The returned lister must be freed by [`opendal_lister_free`].
+///
+/// # Safety
+///
+/// It is [safe] under two cases below
+/// * The memory pointed to by `path` must contain a valid nul terminator at the end of
+///   the string.
+///
+/// # Panic
+///
+/// * If the `path` points to NULL, this function panics
+#[no_mangle]
+pub unsafe extern "C" fn opendal_operator_blocking_list(
+    op_ptr: opendal_operator_ptr,
+    path: *const c_char,
+) -> opendal_result_list {
+    if path.is_null() {
+        panic!("The path given is pointing at NULL");
+    }
+
+    let op = op_ptr.get_ref();
+    let path = unsafe { std::ffi::CStr::from_ptr(path).to_str().unwrap() };
+    match op.list(path) {
+        Ok(l) => opendal_result_list {
+            lister: Box::into_raw(Box::new(opendal_blocking_lister::new(l))),
+            code: opendal_code::OPENDAL_OK,
+        },
+        Err(e) => opendal_result_list {
+            lister: std::ptr::null_mut(),
+            code: opendal_code::from_opendal_error(e),
+        },
+    }
+}
+
+/// Scan the entries under the directory `path` recursively, returns a result with
+/// an [`opendal_blocking_lister`]. If the error code is not OPENDAL_OK, the `lister`
+/// field of the result points to NULL.
+///
+/// The returned lister must be freed by [`opendal_lister_free`].
+///
+/// # Safety
+///
+/// It is [safe] under two cases below
+/// * The memory pointed to by `path` must contain a valid nul terminator at the end of
+///   the string.
+///
+/// # Panic
+///
+/// * If the `path` points to NULL, this function panics
+#[no_mangle]
+pub unsafe extern "C" fn opendal_operator_blocking_scan(
+    op_ptr: opendal_operator_ptr,
+    path: *const c_char,
+) -> opendal_result_list {
+    if path.is_null() {
+        panic!("The path given is pointing at NULL");
+    }
+
+    let op = op_ptr.get_ref();
+    let path = unsafe { std::ffi::CStr::from_ptr(path).to_str().unwrap() };
+    match op.scan(path) {
+        Ok(l) => opendal_result_list {
+            lister: Box::into_raw(Box::new(opendal_blocking_lister::new(l))),
+            code: opendal_code::OPENDAL_OK,
+        },
+        Err(e) => opendal_result_list {
+            lister: std::ptr::null_mut(),
+            code: opendal_code::from_opendal_error(e),
+        },
+    }
+}
+
+/// Open an [`opendal_reader`] on the path to read its content incrementally. If the
+/// error code is not OPENDAL_OK, the `reader` field of the result points to NULL.
+///
+/// The returned reader must be freed by [`opendal_reader_free`].
+///
+/// # Safety
+///
+/// It is [safe] under two cases below
+/// * The memory pointed to by `path` must contain a valid nul terminator at the end of
+///   the string.
+///
+/// # Panic
+///
+/// * If the `path` points to NULL, this function panics
+#[no_mangle]
+pub unsafe extern "C" fn opendal_operator_blocking_reader(
+    op_ptr: opendal_operator_ptr,
+    path: *const c_char,
+) -> opendal_result_reader {
+    if path.is_null() {
+        panic!("The path given is pointing at NULL");
+    }
+
+    let op = op_ptr.get_ref();
+    let path = unsafe { std::ffi::CStr::from_ptr(path).to_str().unwrap() };
+    match op.reader(path) {
+        Ok(r) => opendal_result_reader {
+            reader: Box::into_raw(Box::new(opendal_reader::new(r))),
+            code: opendal_code::OPENDAL_OK,
+        },
+        Err(e) => opendal_result_reader {
+            reader: std::ptr::null_mut(),
+            code: opendal_code::from_opendal_error(e),
+        },
+    }
+}
+
+/// Open an [`opendal_writer`] on the path to write its content incrementally. If the
+/// error code is not OPENDAL_OK, the `writer` field of the result points to NULL.
+///
+/// The returned writer must be closed by [`opendal_writer_close`] to store the content,
+/// and then freed by [`opendal_writer_free`].
+///
+/// # Safety
+///
+/// It is [safe] under two cases below
+/// * The memory pointed to by `path` must contain a valid nul terminator at the end of
+///   the string.
+///
+/// # Panic
+///
+/// * If the `path` points to NULL, this function panics
+#[no_mangle]
+pub unsafe extern "C" fn opendal_operator_blocking_writer(
+    op_ptr: opendal_operator_ptr,
+    path: *const c_char,
+) -> opendal_result_writer {
+    if path.is_null() {
+        panic!("The path given is pointing at NULL");
+    }
+
+    let op = op_ptr.get_ref();
+    let path = unsafe { std::ffi::CStr::from_ptr(path).to_str().unwrap() };
+    match op.writer(path) {
+        Ok(w) => opendal_result_writer {
+            writer: Box::into_raw(Box::new(opendal_writer::new(w))),
+            code: opendal_code::OPENDAL_OK,
+        },
+        Err(e) => opendal_result_writer {
+            writer: std::ptr::null_mut(),
+            code: opendal_code::from_opendal_error(e),
+        },
+    }
+}
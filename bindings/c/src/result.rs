@@ -21,7 +21,12 @@
 //! we are defining all Result types here
 
 use crate::error::opendal_code;
+use crate::types::opendal_blocking_lister;
 use crate::types::opendal_bytes;
+use crate::types::opendal_list_entry;
+use crate::types::opendal_metadata;
+use crate::types::opendal_reader;
+use crate::types::opendal_writer;
 
 /// The Rust-like Result type of opendal C binding, it contains
 /// the data that the read operation returns and a error code
@@ -41,3 +46,62 @@ pub struct opendal_result_is_exist {
     pub is_exist: bool,
     pub code: opendal_code,
 }
+
+/// The result type for [`opendal_operator_stat()`], the field `meta` contains
+/// the metadata of the path, and the field `code` contains the corresponding
+/// error code. If the error code is not OPENDAL_OK, the `meta` field points
+/// to NULL.
+#[repr(C)]
+pub struct opendal_result_stat {
+    pub meta: *mut opendal_metadata,
+    pub code: opendal_code,
+}
+
+/// The result type for [`opendal_operator_blocking_list()`] and
+/// [`opendal_operator_blocking_scan()`]. If the error code is not
+/// OPENDAL_OK, the `lister` field points to NULL.
+#[repr(C)]
+pub struct opendal_result_list {
+    pub lister: *mut opendal_blocking_lister,
+    pub code: opendal_code,
+}
+
+/// The result type for [`opendal_lister_next()`]. If the lister is exhausted
+/// or the error code is not OPENDAL_OK, the `entry` field points to NULL.
+#[repr(C)]
+pub struct opendal_result_lister_next {
+    pub entry: *mut opendal_list_entry,
+    pub code: opendal_code,
+}
+
+/// The result type for [`opendal_operator_blocking_reader()`]. If the error
+/// code is not OPENDAL_OK, the `reader` field points to NULL.
+#[repr(C)]
+pub struct opendal_result_reader {
+    pub reader: *mut opendal_reader,
+    pub code: opendal_code,
+}
+
+/// The result type for [`opendal_reader_read()`], the field `size` contains
+/// the number of bytes read into the buffer.
+#[repr(C)]
+pub struct opendal_result_reader_read {
+    pub size: usize,
+    pub code: opendal_code,
+}
+
+/// The result type for [`opendal_reader_seek()`], the field `pos` contains
+/// the new position from the start of the content.
+#[repr(C)]
+pub struct opendal_result_reader_seek {
+    pub pos: u64,
+    pub code: opendal_code,
+}
+
+/// The result type for [`opendal_operator_blocking_writer()`]. If the error
+/// code is not OPENDAL_OK, the `writer` field points to NULL.
+#[repr(C)]
+pub struct opendal_result_writer {
+    pub writer: *mut opendal_writer,
+    pub code: opendal_code,
+}
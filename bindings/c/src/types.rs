@@ -15,7 +15,19 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::io::SeekFrom;
+use std::os::raw::c_char;
+
 use ::opendal as od;
+use od::raw::oio;
+use od::raw::oio::BlockingRead;
+
+use crate::error::opendal_code;
+use crate::result::opendal_result_lister_next;
+use crate::result::opendal_result_reader_read;
+use crate::result::opendal_result_reader_seek;
 
 /// The [`opendal_operator_ptr`] owns a pointer to a [`od::BlockingOperator`].
 /// It is also the key struct that OpenDAL's APIs access the real
@@ -78,6 +90,8 @@ impl From<&mut od::BlockingOperator> for opendal_operator_ptr {
 /// in Rust, it will not be deallocated automatically like what
 /// has been done in Rust. Instead, you have to call [`opendal_free_bytes`]
 /// to free the heap memory to avoid memory leak.
+///
+/// When passed into OpenDAL, the `data` could be NULL if the `len` is 0.
 #[repr(C)]
 pub struct opendal_bytes {
     pub data: *const u8,
@@ -93,6 +107,15 @@ impl opendal_bytes {
         Self { data, len }
     }
 
+    /// Borrow the bytes as a slice, the `data` is allowed to be NULL
+    /// if the `len` is 0.
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        if self.data.is_null() || self.len == 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.data, self.len) }
+    }
+
     /// Frees the heap memory used by the [`opendal_bytes`]
     #[no_mangle]
     pub extern "C" fn opendal_bytes_free(&self) {
@@ -107,7 +130,409 @@ impl opendal_bytes {
 #[allow(clippy::from_over_into)]
 impl Into<bytes::Bytes> for opendal_bytes {
     fn into(self) -> bytes::Bytes {
+        if self.data.is_null() {
+            return bytes::Bytes::new();
+        }
         let slice = unsafe { std::slice::from_raw_parts(self.data, self.len) };
         bytes::Bytes::from_static(slice)
     }
 }
+
+/// The [`opendal_operator_options`] holds the options that are used to
+/// construct an operator in [`opendal_operator_new`]. It is created by
+/// [`opendal_operator_options_new`], filled by [`opendal_operator_options_set`]
+/// and must be freed by [`opendal_operator_options_free`] after use.
+pub struct opendal_operator_options {
+    inner: HashMap<String, String>,
+}
+
+impl opendal_operator_options {
+    /// Returns the options as a map that could be consumed by
+    /// [`od::Operator::via_map`].
+    pub(crate) fn to_map(&self) -> HashMap<String, String> {
+        self.inner.clone()
+    }
+
+    /// Construct a heap-allocated [`opendal_operator_options`] without
+    /// any option set.
+    #[no_mangle]
+    pub extern "C" fn opendal_operator_options_new() -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            inner: HashMap::default(),
+        }))
+    }
+
+    /// Set a key-value pair into the options, the value of an existing key
+    /// will be overwritten. For example, you could set the root of a fs
+    /// operator by key "root".
+    ///
+    /// # Safety
+    ///
+    /// It is [safe] under two cases below
+    /// * The memory pointed to by `key` and `value` must contain a valid nul
+    ///   terminator at the end of the string.
+    ///
+    /// # Panic
+    ///
+    /// * If the `key` or `value` points to NULL, this function panics
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_operator_options_set(
+        &mut self,
+        key: *const c_char,
+        value: *const c_char,
+    ) {
+        if key.is_null() || value.is_null() {
+            panic!("The key or value given is pointing at NULL");
+        }
+
+        let key = unsafe { std::ffi::CStr::from_ptr(key).to_str().unwrap() };
+        let value = unsafe { std::ffi::CStr::from_ptr(value).to_str().unwrap() };
+        self.inner.insert(key.to_string(), value.to_string());
+    }
+
+    /// Frees the heap memory used by the [`opendal_operator_options`]
+    ///
+    /// # Safety
+    ///
+    /// The `options` must be either NULL or returned by
+    /// [`opendal_operator_options_new`], and must not be used after freed.
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_operator_options_free(options: *mut Self) {
+        if !options.is_null() {
+            let _ = unsafe { Box::from_raw(options) };
+        }
+    }
+}
+
+/// The [`opendal_metadata`] carries the metadata of a path, which is returned
+/// by [`opendal_operator_stat`]. Use the `opendal_metadata_*` functions to
+/// access its fields, and free it by [`opendal_metadata_free`] after use.
+pub struct opendal_metadata {
+    inner: od::Metadata,
+}
+
+impl opendal_metadata {
+    /// Construct a [`opendal_metadata`] from the Rust [`od::Metadata`]
+    pub(crate) fn new(inner: od::Metadata) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the content length of the path, in bytes.
+    #[no_mangle]
+    pub extern "C" fn opendal_metadata_content_length(&self) -> u64 {
+        self.inner.content_length()
+    }
+
+    /// Returns whether the path is a file.
+    #[no_mangle]
+    pub extern "C" fn opendal_metadata_is_file(&self) -> bool {
+        self.inner.is_file()
+    }
+
+    /// Returns whether the path is a directory.
+    #[no_mangle]
+    pub extern "C" fn opendal_metadata_is_dir(&self) -> bool {
+        self.inner.is_dir()
+    }
+
+    /// Returns the last modified time of the path in milliseconds since
+    /// the unix epoch, or -1 if the underlying service doesn't provide it.
+    #[no_mangle]
+    pub extern "C" fn opendal_metadata_last_modified_ms(&self) -> i64 {
+        match self.inner.last_modified() {
+            Some(t) => t.timestamp_millis(),
+            None => -1,
+        }
+    }
+
+    /// Frees the heap memory used by the [`opendal_metadata`]
+    ///
+    /// # Safety
+    ///
+    /// The `meta` must be either NULL or returned by [`opendal_operator_stat`],
+    /// and must not be used after freed.
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_metadata_free(meta: *mut Self) {
+        if !meta.is_null() {
+            let _ = unsafe { Box::from_raw(meta) };
+        }
+    }
+}
+
+/// The [`opendal_blocking_lister`] iterates over the entries under a
+/// directory, it is returned by [`opendal_operator_blocking_list`] and
+/// [`opendal_operator_blocking_scan`]. Free it by [`opendal_lister_free`]
+/// after use.
+pub struct opendal_blocking_lister {
+    inner: od::BlockingLister,
+}
+
+impl opendal_blocking_lister {
+    /// Construct a [`opendal_blocking_lister`] from the Rust [`od::BlockingLister`]
+    pub(crate) fn new(inner: od::BlockingLister) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the next entry of the lister. If the lister has been
+    /// exhausted, the `entry` field of the result points to NULL and the
+    /// error code is OPENDAL_OK.
+    #[no_mangle]
+    pub extern "C" fn opendal_lister_next(&mut self) -> opendal_result_lister_next {
+        match self.inner.next() {
+            Some(Ok(entry)) => opendal_result_lister_next {
+                entry: Box::into_raw(Box::new(opendal_list_entry::new(entry))),
+                code: opendal_code::OPENDAL_OK,
+            },
+            Some(Err(e)) => opendal_result_lister_next {
+                entry: std::ptr::null_mut(),
+                code: opendal_code::from_opendal_error(e),
+            },
+            None => opendal_result_lister_next {
+                entry: std::ptr::null_mut(),
+                code: opendal_code::OPENDAL_OK,
+            },
+        }
+    }
+
+    /// Frees the heap memory used by the [`opendal_blocking_lister`]
+    ///
+    /// # Safety
+    ///
+    /// The `lister` must be either NULL or returned by the list functions,
+    /// and must not be used after freed.
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_lister_free(lister: *mut Self) {
+        if !lister.is_null() {
+            let _ = unsafe { Box::from_raw(lister) };
+        }
+    }
+}
+
+/// The [`opendal_list_entry`] is an entry returned by [`opendal_lister_next`].
+/// Free it by [`opendal_list_entry_free`] after use.
+pub struct opendal_list_entry {
+    path: CString,
+    name: CString,
+}
+
+impl opendal_list_entry {
+    /// Construct a [`opendal_list_entry`] from the Rust [`od::Entry`]
+    pub(crate) fn new(entry: od::Entry) -> Self {
+        // path of entry never contains nul since it comes from a C string
+        // or the underlying storage service.
+        Self {
+            path: CString::new(entry.path()).unwrap_or_default(),
+            name: CString::new(entry.name()).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the full path of the entry, directories end with "/". The
+    /// returned string is owned by the entry and is valid until it's freed.
+    #[no_mangle]
+    pub extern "C" fn opendal_list_entry_path(&self) -> *const c_char {
+        self.path.as_ptr()
+    }
+
+    /// Returns the last component of the entry's path, directories end with
+    /// "/". The returned string is owned by the entry and is valid until
+    /// it's freed.
+    #[no_mangle]
+    pub extern "C" fn opendal_list_entry_name(&self) -> *const c_char {
+        self.name.as_ptr()
+    }
+
+    /// Frees the heap memory used by the [`opendal_list_entry`]
+    ///
+    /// # Safety
+    ///
+    /// The `entry` must be either NULL or returned by [`opendal_lister_next`],
+    /// and must not be used after freed.
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_list_entry_free(entry: *mut Self) {
+        if !entry.is_null() {
+            let _ = unsafe { Box::from_raw(entry) };
+        }
+    }
+}
+
+/// The whence of [`opendal_reader_seek`], which has the same meaning
+/// as `SEEK_SET`, `SEEK_CUR` and `SEEK_END` of `fseek`.
+#[repr(C)]
+pub enum opendal_seek_whence {
+    /// Seek from the start of the content.
+    OPENDAL_SEEK_SET,
+    /// Seek from the current position.
+    OPENDAL_SEEK_CUR,
+    /// Seek from the end of the content.
+    OPENDAL_SEEK_END,
+}
+
+/// The [`opendal_reader`] reads the content of a path incrementally, it is
+/// returned by [`opendal_operator_blocking_reader`]. Free it by
+/// [`opendal_reader_free`] after use.
+pub struct opendal_reader {
+    inner: od::BlockingReader,
+}
+
+impl opendal_reader {
+    /// Construct a [`opendal_reader`] from the Rust [`od::BlockingReader`]
+    pub(crate) fn new(inner: od::BlockingReader) -> Self {
+        Self { inner }
+    }
+
+    /// Read at most `len` bytes into `buf`, returns the number of bytes read.
+    /// A `size` of 0 with error code OPENDAL_OK means the end of content
+    /// has been reached.
+    ///
+    /// # Safety
+    ///
+    /// The `buf` must be valid for writes of `len` bytes.
+    ///
+    /// # Panic
+    ///
+    /// * If the `buf` points to NULL, this function panics
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_reader_read(
+        &mut self,
+        buf: *mut u8,
+        len: usize,
+    ) -> opendal_result_reader_read {
+        if buf.is_null() {
+            panic!("The buf given is pointing at NULL");
+        }
+
+        let buf = unsafe { std::slice::from_raw_parts_mut(buf, len) };
+        match self.inner.read(buf) {
+            Ok(size) => opendal_result_reader_read {
+                size,
+                code: opendal_code::OPENDAL_OK,
+            },
+            Err(e) => opendal_result_reader_read {
+                size: 0,
+                code: opendal_code::from_opendal_error(e),
+            },
+        }
+    }
+
+    /// Seek the reader to the `offset` relative to `whence`, returns the new
+    /// position from the start of the content.
+    #[no_mangle]
+    pub extern "C" fn opendal_reader_seek(
+        &mut self,
+        offset: i64,
+        whence: opendal_seek_whence,
+    ) -> opendal_result_reader_seek {
+        let pos = match whence {
+            opendal_seek_whence::OPENDAL_SEEK_SET => {
+                if offset < 0 {
+                    return opendal_result_reader_seek {
+                        pos: 0,
+                        code: opendal_code::OPENDAL_UNEXPECTED,
+                    };
+                }
+                SeekFrom::Start(offset as u64)
+            }
+            opendal_seek_whence::OPENDAL_SEEK_CUR => SeekFrom::Current(offset),
+            opendal_seek_whence::OPENDAL_SEEK_END => SeekFrom::End(offset),
+        };
+
+        match self.inner.seek(pos) {
+            Ok(pos) => opendal_result_reader_seek {
+                pos,
+                code: opendal_code::OPENDAL_OK,
+            },
+            Err(e) => opendal_result_reader_seek {
+                pos: 0,
+                code: opendal_code::from_opendal_error(e),
+            },
+        }
+    }
+
+    /// Frees the heap memory used by the [`opendal_reader`]
+    ///
+    /// # Safety
+    ///
+    /// The `reader` must be either NULL or returned by
+    /// [`opendal_operator_blocking_reader`], and must not be used after freed.
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_reader_free(reader: *mut Self) {
+        if !reader.is_null() {
+            let _ = unsafe { Box::from_raw(reader) };
+        }
+    }
+}
+
+/// The [`opendal_writer`] writes content into a path incrementally, it is
+/// returned by [`opendal_operator_blocking_writer`]. The content is not
+/// guaranteed to be stored until [`opendal_writer_close`] succeeds. Free it
+/// by [`opendal_writer_free`] after use.
+///
+/// Written bytes are buffered and appended in chunks of 8 MiB, so that
+/// services with a minimum part size like s3 can accept small writes.
+pub struct opendal_writer {
+    inner: od::BlockingWriter,
+    buf: oio::AppendBuffer,
+}
+
+impl opendal_writer {
+    /// Construct a [`opendal_writer`] from the Rust [`od::BlockingWriter`]
+    pub(crate) fn new(inner: od::BlockingWriter) -> Self {
+        Self {
+            inner,
+            buf: oio::AppendBuffer::new(),
+        }
+    }
+
+    /// Append the `bytes` into the writer, the bytes are copied so the
+    /// caller still owns them after this call.
+    ///
+    /// The bytes are buffered until 8 MiB are collected or the writer
+    /// is closed, so errors of the underlying storage could be returned
+    /// by later calls.
+    ///
+    /// # Safety
+    ///
+    /// The `bytes.data` must be valid for reads of `bytes.len` bytes, it's
+    /// allowed to be NULL if `bytes.len` is 0.
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_writer_write(&mut self, bytes: opendal_bytes) -> opendal_code {
+        let chunk = match self
+            .buf
+            .push(bytes::Bytes::copy_from_slice(bytes.as_slice()))
+        {
+            Some(chunk) => chunk,
+            None => return opendal_code::OPENDAL_OK,
+        };
+        match self.inner.append(chunk) {
+            Ok(_) => opendal_code::OPENDAL_OK,
+            Err(e) => opendal_code::from_opendal_error(e),
+        }
+    }
+
+    /// Close the writer and make sure all data have been stored.
+    #[no_mangle]
+    pub extern "C" fn opendal_writer_close(&mut self) -> opendal_code {
+        let res = match self.buf.finish() {
+            Some(chunk) => self.inner.append(chunk),
+            None => Ok(()),
+        };
+        match res.and_then(|_| self.inner.close()) {
+            Ok(_) => opendal_code::OPENDAL_OK,
+            Err(e) => opendal_code::from_opendal_error(e),
+        }
+    }
+
+    /// Frees the heap memory used by the [`opendal_writer`], the content
+    /// that is not closed will be discarded.
+    ///
+    /// # Safety
+    ///
+    /// The `writer` must be either NULL or returned by
+    /// [`opendal_operator_blocking_writer`], and must not be used after freed.
+    #[no_mangle]
+    pub unsafe extern "C" fn opendal_writer_free(writer: *mut Self) {
+        if !writer.is_null() {
+            let _ = unsafe { Box::from_raw(writer) };
+        }
+    }
+}
@@ -20,6 +20,7 @@
 #include "assert.h"
 #include "opendal.h"
 #include "stdio.h"
+#include "string.h"
 
 // Tests the basic IO operations work as expected
 //
@@ -55,17 +56,170 @@ void test_operator_rw(opendal_operator_ptr ptr)
     opendal_bytes_free(r.data);
 }
 
+// Tests the stat, create_dir, list and delete operations work as expected
+//
+// Asserts:
+// * The metadata returned by stat is correct
+// * The list operation returns all entries under the directory
+// * The deleted path is not existing anymore
+void test_operator_stat_list_delete(opendal_operator_ptr ptr)
+{
+    char dir[] = "dir/";
+    char path[] = "dir/file";
+    char content[] = "Hello World";
+    const opendal_bytes data = {
+        .len = sizeof(content) - 1,
+        .data = (uint8_t*)content,
+    };
+    assert(opendal_operator_blocking_create_dir(ptr, dir) == OPENDAL_OK);
+    assert(opendal_operator_blocking_write(ptr, path, data) == OPENDAL_OK);
+
+    // stat the file, must be a file with correct content length
+    struct opendal_result_stat s = opendal_operator_stat(ptr, path);
+    assert(s.code == OPENDAL_OK);
+    assert(opendal_metadata_is_file(s.meta));
+    assert(!opendal_metadata_is_dir(s.meta));
+    assert(opendal_metadata_content_length(s.meta) == sizeof(content) - 1);
+    opendal_metadata_free(s.meta);
+
+    // stat a path that doesn't exist, must be not found
+    struct opendal_result_stat ns = opendal_operator_stat(ptr, "not_exist");
+    assert(ns.code == OPENDAL_NOT_FOUND);
+    assert(!ns.meta);
+
+    // list the directory, must contain the file
+    struct opendal_result_list l = opendal_operator_blocking_list(ptr, dir);
+    assert(l.code == OPENDAL_OK);
+    int found = 0;
+    while (1) {
+        struct opendal_result_lister_next n = opendal_lister_next(l.lister);
+        assert(n.code == OPENDAL_OK);
+        if (!n.entry) {
+            break;
+        }
+        if (strcmp(opendal_list_entry_path(n.entry), path) == 0) {
+            assert(strcmp(opendal_list_entry_name(n.entry), "file") == 0);
+            found++;
+        }
+        opendal_list_entry_free(n.entry);
+    }
+    assert(found == 1);
+    opendal_lister_free(l.lister);
+
+    // delete the file, must be not existing anymore
+    assert(opendal_operator_blocking_delete(ptr, path) == OPENDAL_OK);
+    struct opendal_result_is_exist e = opendal_operator_is_exist(ptr, path);
+    assert(e.code == OPENDAL_OK);
+    assert(!e.is_exist);
+}
+
+// Tests the reader and writer handles work as expected
+//
+// Asserts:
+// * The content written by several writes is stored after close
+// * The reader reads and seeks the content correctly
+void test_operator_reader_writer(opendal_operator_ptr ptr)
+{
+    char path[] = "stream";
+    char content[] = "Hello World";
+    const opendal_bytes data = {
+        .len = sizeof(content) - 1,
+        .data = (uint8_t*)content,
+    };
+
+    struct opendal_result_writer w = opendal_operator_blocking_writer(ptr, path);
+    assert(w.code == OPENDAL_OK);
+    assert(opendal_writer_write(w.writer, data) == OPENDAL_OK);
+    assert(opendal_writer_write(w.writer, data) == OPENDAL_OK);
+    assert(opendal_writer_close(w.writer) == OPENDAL_OK);
+    opendal_writer_free(w.writer);
+
+    struct opendal_result_reader r = opendal_operator_blocking_reader(ptr, path);
+    assert(r.code == OPENDAL_OK);
+
+    uint8_t buf[64];
+    size_t total = 0;
+    while (1) {
+        struct opendal_result_reader_read rr = opendal_reader_read(r.reader, buf + total, sizeof(buf) - total);
+        assert(rr.code == OPENDAL_OK);
+        if (rr.size == 0) {
+            break;
+        }
+        total += rr.size;
+    }
+    assert(total == 2 * (sizeof(content) - 1));
+    assert(memcmp(buf, content, sizeof(content) - 1) == 0);
+
+    // seek back to the start of the second "World"
+    struct opendal_result_reader_seek sk = opendal_reader_seek(r.reader, -5, OPENDAL_SEEK_END);
+    assert(sk.code == OPENDAL_OK);
+    assert(sk.pos == total - 5);
+    struct opendal_result_reader_read rr = opendal_reader_read(r.reader, buf, 5);
+    assert(rr.code == OPENDAL_OK);
+    assert(rr.size == 5);
+    assert(memcmp(buf, "World", 5) == 0);
+
+    opendal_reader_free(r.reader);
+}
+
+// Tests the empty bytes with NULL data work as expected
+//
+// Asserts:
+// * The blocking write and writer write accept NULL data with zero length
+// * The written path is empty
+void test_operator_write_empty(opendal_operator_ptr ptr)
+{
+    char path[] = "empty";
+    const opendal_bytes data = {
+        .len = 0,
+        .data = NULL,
+    };
+    assert(opendal_operator_blocking_write(ptr, path, data) == OPENDAL_OK);
+
+    struct opendal_result_writer w = opendal_operator_blocking_writer(ptr, path);
+    assert(w.code == OPENDAL_OK);
+    assert(opendal_writer_write(w.writer, data) == OPENDAL_OK);
+    opendal_writer_free(w.writer);
+
+    struct opendal_result_stat s = opendal_operator_stat(ptr, path);
+    assert(s.code == OPENDAL_OK);
+    assert(opendal_metadata_content_length(s.meta) == 0);
+    opendal_metadata_free(s.meta);
+}
+
 int main(int argc, char* argv[])
 {
     // construct the memory operator
     char scheme1[] = "memory";
-    opendal_operator_ptr p1 = opendal_operator_new(scheme1);
+    opendal_operator_ptr p1 = opendal_operator_new(scheme1, NULL);
     assert(p1);
 
     test_operator_rw(p1);
+    test_operator_stat_list_delete(p1);
+    test_operator_reader_writer(p1);
+    test_operator_write_empty(p1);
 
     // free the operator
     opendal_operator_free(p1);
 
+    // construct the fs operator with options
+    char scheme2[] = "fs";
+    opendal_operator_options* options = opendal_operator_options_new();
+    opendal_operator_options_set(options, "root", "/tmp/opendal-c-test/");
+    opendal_operator_ptr p2 = opendal_operator_new(scheme2, options);
+    opendal_operator_options_free(options);
+    assert(p2);
+
+    test_operator_rw(p2);
+    test_operator_stat_list_delete(p2);
+    test_operator_reader_writer(p2);
+    test_operator_write_empty(p2);
+
+    opendal_operator_free(p2);
+
+    // construct an operator with unknown scheme, must be NULL
+    opendal_operator_ptr p3 = opendal_operator_new("unknown", NULL);
+    assert(!p3);
+
     return 0;
 }
@@ -0,0 +1,143 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+package opendal
+
+/*
+#include "opendal.h"
+*/
+import "C"
+
+import "io/fs"
+
+// Code is the kind of an Error, mapped from the ErrorKind of OpenDAL.
+type Code int
+
+// The codes of Error, which are the same as the opendal_code of the C
+// binding.
+const (
+	CodeError              Code = C.OPENDAL_ERROR
+	CodeUnexpected         Code = C.OPENDAL_UNEXPECTED
+	CodeUnsupported        Code = C.OPENDAL_UNSUPPORTED
+	CodeConfigInvalid      Code = C.OPENDAL_CONFIG_INVALID
+	CodeNotFound           Code = C.OPENDAL_NOT_FOUND
+	CodePermissionDenied   Code = C.OPENDAL_PERMISSION_DENIED
+	CodeIsADirectory       Code = C.OPENDAL_IS_A_DIRECTORY
+	CodeNotADirectory      Code = C.OPENDAL_NOT_A_DIRECTORY
+	CodeAlreadyExists      Code = C.OPENDAL_ALREADY_EXISTS
+	CodeRateLimited        Code = C.OPENDAL_RATE_LIMITED
+	CodeIsSameFile         Code = C.OPENDAL_IS_SAME_FILE
+	CodePreconditionFailed Code = C.OPENDAL_PRECONDITION_FAILED
+)
+
+func (c Code) String() string {
+	switch c {
+	case CodeError:
+		return "error"
+	case CodeUnexpected:
+		return "unexpected"
+	case CodeUnsupported:
+		return "unsupported"
+	case CodeConfigInvalid:
+		return "config invalid"
+	case CodeNotFound:
+		return "not found"
+	case CodePermissionDenied:
+		return "permission denied"
+	case CodeIsADirectory:
+		return "is a directory"
+	case CodeNotADirectory:
+		return "not a directory"
+	case CodeAlreadyExists:
+		return "already exists"
+	case CodeRateLimited:
+		return "rate limited"
+	case CodeIsSameFile:
+		return "is same file"
+	case CodePreconditionFailed:
+		return "precondition failed"
+	default:
+		return "unknown"
+	}
+}
+
+// Errors that can be compared with the errors returned by this package
+// via errors.Is.
+var (
+	ErrUnexpected         = &Error{Code: CodeUnexpected}
+	ErrUnsupported        = &Error{Code: CodeUnsupported}
+	ErrConfigInvalid      = &Error{Code: CodeConfigInvalid}
+	ErrNotFound           = &Error{Code: CodeNotFound}
+	ErrPermissionDenied   = &Error{Code: CodePermissionDenied}
+	ErrIsADirectory       = &Error{Code: CodeIsADirectory}
+	ErrNotADirectory      = &Error{Code: CodeNotADirectory}
+	ErrAlreadyExists      = &Error{Code: CodeAlreadyExists}
+	ErrRateLimited        = &Error{Code: CodeRateLimited}
+	ErrIsSameFile         = &Error{Code: CodeIsSameFile}
+	ErrPreconditionFailed = &Error{Code: CodePreconditionFailed}
+)
+
+// Error is the error returned by the operations of OpenDAL.
+//
+// Use errors.Is to check the kind of an Error, for example:
+//
+//	if errors.Is(err, opendal.ErrNotFound) {
+//		// ...
+//	}
+//
+// ErrNotFound, ErrAlreadyExists and ErrPermissionDenied also match
+// fs.ErrNotExist, fs.ErrExist and fs.ErrPermission respectively.
+type Error struct {
+	Code Code
+	// Op is the operation that failed, such as "read" and "write".
+	Op string
+	// Path is the path that the operation failed on.
+	Path string
+}
+
+func (e *Error) Error() string {
+	if e.Op == "" {
+		return "opendal: " + e.Code.String()
+	}
+	return "opendal: " + e.Op + " " + e.Path + ": " + e.Code.String()
+}
+
+// Is reports whether the target is an Error with the same Code, or the fs
+// error of the same meaning.
+func (e *Error) Is(target error) bool {
+	if t, ok := target.(*Error); ok {
+		return e.Code == t.Code
+	}
+	switch target {
+	case fs.ErrNotExist:
+		return e.Code == CodeNotFound
+	case fs.ErrExist:
+		return e.Code == CodeAlreadyExists
+	case fs.ErrPermission:
+		return e.Code == CodePermissionDenied
+	}
+	return false
+}
+
+// parseError returns the Error of the code returned by the C binding, or
+// nil if the code is OPENDAL_OK.
+func parseError(code C.enum_opendal_code, op, path string) error {
+	if code == C.OPENDAL_OK {
+		return nil
+	}
+	return &Error{Code: Code(code), Op: op, Path: path}
+}
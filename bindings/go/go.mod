@@ -15,17 +15,6 @@
 // specific language governing permissions and limitations
 // under the License.
 
-/// Macro used to generate operator upon construction and return C-compatible
-/// error if failed
-#[macro_export]
-macro_rules! generate_operator {
-    ($type:ty, $map:expr) => {{
-        let b = od::Operator::from_map::<$type>($map);
-        match b {
-            Ok(b) => b.finish(),
-            Err(_) => {
-                return opendal_operator_ptr::null();
-            }
-        }
-    }};
-}
+module github.com/apache/incubator-opendal/bindings/go
+
+go 1.20
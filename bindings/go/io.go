@@ -0,0 +1,130 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+package opendal
+
+/*
+#include "opendal.h"
+*/
+import "C"
+
+import (
+	"errors"
+	"io"
+	"unsafe"
+)
+
+var (
+	_ io.ReadSeekCloser = (*Reader)(nil)
+	_ io.WriteCloser    = (*Writer)(nil)
+)
+
+// Reader reads the content of a path, which implements io.Reader, io.Seeker
+// and io.Closer.
+type Reader struct {
+	inner *C.struct_opendal_reader
+	path  string
+}
+
+// Read reads up to len(p) bytes into p, and returns io.EOF at the end of
+// the content.
+func (r *Reader) Read(p []byte) (int, error) {
+	if r.inner == nil {
+		return 0, errors.New("opendal: read on closed reader")
+	}
+	if len(p) == 0 {
+		return 0, nil
+	}
+	res := C.opendal_reader_read(r.inner, (*C.uint8_t)(unsafe.Pointer(&p[0])), C.uintptr_t(len(p)))
+	if err := parseError(res.code, "read", r.path); err != nil {
+		return 0, err
+	}
+	if res.size == 0 {
+		return 0, io.EOF
+	}
+	return int(res.size), nil
+}
+
+// Seek sets the offset for the next Read, and returns the new offset
+// relative to the start of the content.
+func (r *Reader) Seek(offset int64, whence int) (int64, error) {
+	if r.inner == nil {
+		return 0, errors.New("opendal: seek on closed reader")
+	}
+	var w C.enum_opendal_seek_whence
+	switch whence {
+	case io.SeekStart:
+		w = C.OPENDAL_SEEK_SET
+	case io.SeekCurrent:
+		w = C.OPENDAL_SEEK_CUR
+	case io.SeekEnd:
+		w = C.OPENDAL_SEEK_END
+	default:
+		return 0, errors.New("opendal: invalid whence")
+	}
+	res := C.opendal_reader_seek(r.inner, C.int64_t(offset), w)
+	if err := parseError(res.code, "seek", r.path); err != nil {
+		return 0, err
+	}
+	return int64(res.pos), nil
+}
+
+// Close frees the Reader, it's safe to close a Reader more than once.
+func (r *Reader) Close() error {
+	if r.inner != nil {
+		C.opendal_reader_free(r.inner)
+		r.inner = nil
+	}
+	return nil
+}
+
+// Writer writes the content of a path, which implements io.Writer and
+// io.Closer.
+//
+// The content is stored only after Close returns nil, and will be
+// discarded if the Writer is not closed.
+type Writer struct {
+	inner *C.struct_opendal_writer
+	path  string
+}
+
+// Write appends p to the content.
+//
+// p is copied into the buffer of the C binding, which is appended to the
+// service once 8 MiB are collected, and the rest is appended by Close.
+func (w *Writer) Write(p []byte) (int, error) {
+	if w.inner == nil {
+		return 0, errors.New("opendal: write on closed writer")
+	}
+	code := C.opendal_writer_write(w.inner, toBytes(p))
+	if err := parseError(code, "write", w.path); err != nil {
+		return 0, err
+	}
+	return len(p), nil
+}
+
+// Close stores the content and frees the Writer, it's safe to close a
+// Writer more than once.
+func (w *Writer) Close() error {
+	if w.inner == nil {
+		return nil
+	}
+	code := C.opendal_writer_close(w.inner)
+	C.opendal_writer_free(w.inner)
+	w.inner = nil
+	return parseError(code, "close", w.path)
+}
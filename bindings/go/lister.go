@@ -0,0 +1,105 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+package opendal
+
+/*
+#include "opendal.h"
+*/
+import "C"
+
+// Lister iterates the entries returned by Operator.List and Operator.Scan.
+//
+// Call Next to advance the Lister, and Entry to get the current entry:
+//
+//	lister, err := op.List("dir/")
+//	if err != nil {
+//		return err
+//	}
+//	defer lister.Close()
+//	for lister.Next() {
+//		fmt.Println(lister.Entry().Path())
+//	}
+//	if err := lister.Err(); err != nil {
+//		return err
+//	}
+type Lister struct {
+	inner *C.struct_opendal_blocking_lister
+	op    string
+	path  string
+	entry *Entry
+	err   error
+}
+
+// Next advances the Lister to the next entry, which will be available
+// through Entry. It returns false when the listing ends, either by
+// reaching the end or an error.
+func (l *Lister) Next() bool {
+	if l.inner == nil || l.err != nil {
+		return false
+	}
+	res := C.opendal_lister_next(l.inner)
+	if err := parseError(res.code, l.op, l.path); err != nil {
+		l.entry, l.err = nil, err
+		return false
+	}
+	if res.entry == nil {
+		l.entry = nil
+		return false
+	}
+	defer C.opendal_list_entry_free(res.entry)
+	l.entry = &Entry{
+		path: C.GoString(C.opendal_list_entry_path(res.entry)),
+		name: C.GoString(C.opendal_list_entry_name(res.entry)),
+	}
+	return true
+}
+
+// Entry returns the current entry of the Lister.
+func (l *Lister) Entry() *Entry {
+	return l.entry
+}
+
+// Err returns the first error encountered by the Lister.
+func (l *Lister) Err() error {
+	return l.err
+}
+
+// Close frees the Lister, it's safe to close a Lister more than once.
+func (l *Lister) Close() error {
+	if l.inner != nil {
+		C.opendal_lister_free(l.inner)
+		l.inner = nil
+	}
+	return nil
+}
+
+// Entry is an entry returned by the Lister.
+type Entry struct {
+	path string
+	name string
+}
+
+// Path returns the full path of the entry, directories end with "/".
+func (e *Entry) Path() string {
+	return e.path
+}
+
+// Name returns the last component of the path of the entry.
+func (e *Entry) Name() string {
+	return e.name
+}
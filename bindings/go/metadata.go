@@ -0,0 +1,68 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+package opendal
+
+/*
+#include "opendal.h"
+*/
+import "C"
+
+import "time"
+
+// Metadata is the metadata of a path.
+type Metadata struct {
+	contentLength uint64
+	isFile        bool
+	isDir         bool
+	lastModified  time.Time
+}
+
+// newMetadata copies the fields out of the C metadata, which is still
+// owned by the caller.
+func newMetadata(meta *C.struct_opendal_metadata) *Metadata {
+	m := &Metadata{
+		contentLength: uint64(C.opendal_metadata_content_length(meta)),
+		isFile:        bool(C.opendal_metadata_is_file(meta)),
+		isDir:         bool(C.opendal_metadata_is_dir(meta)),
+	}
+	if ms := int64(C.opendal_metadata_last_modified_ms(meta)); ms != -1 {
+		m.lastModified = time.UnixMilli(ms)
+	}
+	return m
+}
+
+// ContentLength returns the length of the content in bytes.
+func (m *Metadata) ContentLength() uint64 {
+	return m.contentLength
+}
+
+// IsFile reports whether the path is a file.
+func (m *Metadata) IsFile() bool {
+	return m.isFile
+}
+
+// IsDir reports whether the path is a directory.
+func (m *Metadata) IsDir() bool {
+	return m.isDir
+}
+
+// LastModified returns the last modified time of the path, it's the zero
+// time if the service doesn't provide it.
+func (m *Metadata) LastModified() time.Time {
+	return m.lastModified
+}
@@ -0,0 +1,260 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+// Package opendal provides the Go binding of OpenDAL, which is built on
+// top of the C binding.
+//
+// The C binding must be built before using this package:
+//
+//	cd bindings/c && cargo build
+//
+// All operations are blocking, and values returned by an Operator such as
+// Reader, Writer and Lister must be closed after use.
+package opendal
+
+/*
+#cgo CFLAGS: -I${SRCDIR}/../c/include
+#cgo LDFLAGS: -L${SRCDIR}/../../target/debug -Wl,-rpath,${SRCDIR}/../../target/debug -lopendal_c
+#include <stdlib.h>
+#include "opendal.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"unsafe"
+)
+
+// Options are the options used to construct an Operator, for example the
+// "root" of the fs service.
+type Options map[string]string
+
+// Operator is the entry for all public APIs.
+//
+// An Operator is safe for concurrent use, and must be closed by Close
+// after use.
+type Operator struct {
+	inner C.opendal_operator_ptr
+}
+
+// NewOperator creates an Operator of the given scheme, such as "memory"
+// and "fs", with the options of the service.
+func NewOperator(scheme string, opts Options) (*Operator, error) {
+	cscheme := C.CString(scheme)
+	defer C.free(unsafe.Pointer(cscheme))
+
+	copts := C.opendal_operator_options_new()
+	defer C.opendal_operator_options_free(copts)
+	for k, v := range opts {
+		ck, cv := C.CString(k), C.CString(v)
+		C.opendal_operator_options_set(copts, ck, cv)
+		C.free(unsafe.Pointer(ck))
+		C.free(unsafe.Pointer(cv))
+	}
+
+	inner := C.opendal_operator_new(cscheme, copts)
+	if inner == nil {
+		// The C binding doesn't tell why the operator can't be created, it's
+		// either an unknown scheme or invalid options.
+		return nil, fmt.Errorf("opendal: create operator of scheme %q: %w", scheme, ErrConfigInvalid)
+	}
+	return &Operator{inner: inner}, nil
+}
+
+// Close frees the Operator, it's safe to close an Operator more than once.
+// Operations on a closed Operator return an error.
+func (op *Operator) Close() error {
+	if op.inner != nil {
+		C.opendal_operator_free(op.inner)
+		op.inner = nil
+	}
+	return nil
+}
+
+// Read reads the whole content of the path.
+func (op *Operator) Read(path string) ([]byte, error) {
+	if op.inner == nil {
+		return nil, errClosed("read")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	res := C.opendal_operator_blocking_read(op.inner, cpath)
+	if err := parseError(res.code, "read", path); err != nil {
+		return nil, err
+	}
+	defer C.opendal_bytes_free(res.data)
+
+	// C.GoBytes takes the length as C.int, which truncates content larger
+	// than 2 GiB, so copy the full length via unsafe.Slice instead.
+	data := make([]byte, int(res.data.len))
+	if len(data) > 0 {
+		copy(data, unsafe.Slice((*byte)(unsafe.Pointer(res.data.data)), len(data)))
+	}
+	return data, nil
+}
+
+// Write writes the data into the path, the existing content will be
+// overwritten.
+func (op *Operator) Write(path string, data []byte) error {
+	if op.inner == nil {
+		return errClosed("write")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	code := C.opendal_operator_blocking_write(op.inner, cpath, toBytes(data))
+	return parseError(code, "write", path)
+}
+
+// Stat returns the Metadata of the path.
+func (op *Operator) Stat(path string) (*Metadata, error) {
+	if op.inner == nil {
+		return nil, errClosed("stat")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	res := C.opendal_operator_stat(op.inner, cpath)
+	if err := parseError(res.code, "stat", path); err != nil {
+		return nil, err
+	}
+	defer C.opendal_metadata_free(res.meta)
+	return newMetadata(res.meta), nil
+}
+
+// IsExist checks whether the path exists.
+func (op *Operator) IsExist(path string) (bool, error) {
+	if op.inner == nil {
+		return false, errClosed("is_exist")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	res := C.opendal_operator_is_exist(op.inner, cpath)
+	if err := parseError(res.code, "is_exist", path); err != nil {
+		return false, err
+	}
+	return bool(res.is_exist), nil
+}
+
+// Delete deletes the path, deleting a path that doesn't exist is not an
+// error.
+func (op *Operator) Delete(path string) error {
+	if op.inner == nil {
+		return errClosed("delete")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	code := C.opendal_operator_blocking_delete(op.inner, cpath)
+	return parseError(code, "delete", path)
+}
+
+// CreateDir creates the directory of the path, which must end with "/".
+func (op *Operator) CreateDir(path string) error {
+	if op.inner == nil {
+		return errClosed("create_dir")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	code := C.opendal_operator_blocking_create_dir(op.inner, cpath)
+	return parseError(code, "create_dir", path)
+}
+
+// List returns a Lister of the entries directly under the directory of
+// the path, which must end with "/".
+func (op *Operator) List(path string) (*Lister, error) {
+	if op.inner == nil {
+		return nil, errClosed("list")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	res := C.opendal_operator_blocking_list(op.inner, cpath)
+	if err := parseError(res.code, "list", path); err != nil {
+		return nil, err
+	}
+	return &Lister{inner: res.lister, op: "list", path: path}, nil
+}
+
+// Scan returns a Lister of all the entries under the directory of the path
+// recursively, the path must end with "/".
+func (op *Operator) Scan(path string) (*Lister, error) {
+	if op.inner == nil {
+		return nil, errClosed("scan")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	res := C.opendal_operator_blocking_scan(op.inner, cpath)
+	if err := parseError(res.code, "scan", path); err != nil {
+		return nil, err
+	}
+	return &Lister{inner: res.lister, op: "scan", path: path}, nil
+}
+
+// Reader opens a Reader to read the content of the path incrementally.
+func (op *Operator) Reader(path string) (*Reader, error) {
+	if op.inner == nil {
+		return nil, errClosed("reader")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	res := C.opendal_operator_blocking_reader(op.inner, cpath)
+	if err := parseError(res.code, "reader", path); err != nil {
+		return nil, err
+	}
+	return &Reader{inner: res.reader, path: path}, nil
+}
+
+// Writer opens a Writer to write the content of the path incrementally.
+// The content is stored only after the Writer is closed successfully.
+//
+// Written bytes are buffered by the C binding and appended in chunks of
+// 8 MiB, so small writes don't turn into small appends to the service.
+func (op *Operator) Writer(path string) (*Writer, error) {
+	if op.inner == nil {
+		return nil, errClosed("writer")
+	}
+	cpath := C.CString(path)
+	defer C.free(unsafe.Pointer(cpath))
+
+	res := C.opendal_operator_blocking_writer(op.inner, cpath)
+	if err := parseError(res.code, "writer", path); err != nil {
+		return nil, err
+	}
+	return &Writer{inner: res.writer, path: path}, nil
+}
+
+// toBytes borrows the data as opendal_bytes, which must not be used after
+// the call to C returns.
+func toBytes(data []byte) C.struct_opendal_bytes {
+	var bs C.struct_opendal_bytes
+	if len(data) > 0 {
+		bs.data = (*C.uint8_t)(unsafe.Pointer(&data[0]))
+		bs.len = C.uintptr_t(len(data))
+	}
+	return bs
+}
+
+// errClosed returns the error of calling op on a closed Operator.
+func errClosed(op string) error {
+	return fmt.Errorf("opendal: %s on closed operator", op)
+}
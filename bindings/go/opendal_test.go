@@ -0,0 +1,246 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+package opendal_test
+
+import (
+	"errors"
+	"io"
+	"io/fs"
+	"sort"
+	"testing"
+
+	opendal "github.com/apache/incubator-opendal/bindings/go"
+)
+
+func TestOperator(t *testing.T) {
+	t.Run("memory", func(t *testing.T) {
+		testOperator(t, "memory", nil)
+	})
+	t.Run("fs", func(t *testing.T) {
+		testOperator(t, "fs", opendal.Options{"root": t.TempDir() + "/"})
+	})
+}
+
+func testOperator(t *testing.T, scheme string, opts opendal.Options) {
+	op, err := opendal.NewOperator(scheme, opts)
+	if err != nil {
+		t.Fatalf("new operator: %v", err)
+	}
+	defer op.Close()
+
+	t.Run("read_write", func(t *testing.T) { testReadWrite(t, op) })
+	t.Run("not_found", func(t *testing.T) { testNotFound(t, op) })
+	t.Run("reader_writer", func(t *testing.T) { testReaderWriter(t, op) })
+	t.Run("list_scan", func(t *testing.T) { testListScan(t, op) })
+}
+
+func testReadWrite(t *testing.T, op *opendal.Operator) {
+	path, content := "test", []byte("Hello, World!")
+	if err := op.Write(path, content); err != nil {
+		t.Fatalf("write: %v", err)
+	}
+
+	data, err := op.Read(path)
+	if err != nil {
+		t.Fatalf("read: %v", err)
+	}
+	if string(data) != string(content) {
+		t.Errorf("read got %q, want %q", data, content)
+	}
+
+	meta, err := op.Stat(path)
+	if err != nil {
+		t.Fatalf("stat: %v", err)
+	}
+	if !meta.IsFile() || meta.IsDir() {
+		t.Errorf("stat got is_file %v and is_dir %v", meta.IsFile(), meta.IsDir())
+	}
+	if meta.ContentLength() != uint64(len(content)) {
+		t.Errorf("stat got content length %d, want %d", meta.ContentLength(), len(content))
+	}
+
+	if err := op.Write("empty", nil); err != nil {
+		t.Fatalf("write empty: %v", err)
+	}
+	if data, err := op.Read("empty"); err != nil || len(data) != 0 {
+		t.Errorf("read empty got %q, %v", data, err)
+	}
+
+	if err := op.Delete(path); err != nil {
+		t.Fatalf("delete: %v", err)
+	}
+	exist, err := op.IsExist(path)
+	if err != nil {
+		t.Fatalf("is_exist: %v", err)
+	}
+	if exist {
+		t.Errorf("path %q still exists after delete", path)
+	}
+}
+
+func testNotFound(t *testing.T, op *opendal.Operator) {
+	_, err := op.Read("not_exist")
+	if !errors.Is(err, opendal.ErrNotFound) {
+		t.Errorf("read got %v, want %v", err, opendal.ErrNotFound)
+	}
+	if !errors.Is(err, fs.ErrNotExist) {
+		t.Errorf("read got %v, want %v", err, fs.ErrNotExist)
+	}
+	if errors.Is(err, opendal.ErrAlreadyExists) {
+		t.Errorf("read got %v, must not be %v", err, opendal.ErrAlreadyExists)
+	}
+
+	if _, err := op.Stat("not_exist"); !errors.Is(err, opendal.ErrNotFound) {
+		t.Errorf("stat got %v, want %v", err, opendal.ErrNotFound)
+	}
+	if _, err := op.Reader("not_exist"); !errors.Is(err, opendal.ErrNotFound) {
+		t.Errorf("reader got %v, want %v", err, opendal.ErrNotFound)
+	}
+}
+
+func testReaderWriter(t *testing.T, op *opendal.Operator) {
+	path := "stream"
+	w, err := op.Writer(path)
+	if err != nil {
+		t.Fatalf("writer: %v", err)
+	}
+	for _, s := range []string{"Hello, ", "World!"} {
+		if _, err := io.WriteString(w, s); err != nil {
+			t.Fatalf("writer write: %v", err)
+		}
+	}
+	if err := w.Close(); err != nil {
+		t.Fatalf("writer close: %v", err)
+	}
+
+	r, err := op.Reader(path)
+	if err != nil {
+		t.Fatalf("reader: %v", err)
+	}
+	defer r.Close()
+
+	data, err := io.ReadAll(r)
+	if err != nil {
+		t.Fatalf("reader read: %v", err)
+	}
+	if string(data) != "Hello, World!" {
+		t.Errorf("reader read got %q", data)
+	}
+
+	pos, err := r.Seek(-6, io.SeekEnd)
+	if err != nil {
+		t.Fatalf("reader seek: %v", err)
+	}
+	if pos != 7 {
+		t.Errorf("reader seek got %d, want 7", pos)
+	}
+	data, err = io.ReadAll(r)
+	if err != nil {
+		t.Fatalf("reader read after seek: %v", err)
+	}
+	if string(data) != "World!" {
+		t.Errorf("reader read after seek got %q", data)
+	}
+}
+
+func testListScan(t *testing.T, op *opendal.Operator) {
+	if err := op.CreateDir("dir/sub/"); err != nil {
+		t.Fatalf("create_dir: %v", err)
+	}
+	for _, path := range []string{"dir/a", "dir/sub/b"} {
+		if err := op.Write(path, []byte(path)); err != nil {
+			t.Fatalf("write: %v", err)
+		}
+	}
+
+	// The order of entries is not specified.
+	got := collect(t, op.List, "dir/", false)
+	assertPaths(t, "list", got, []string{"dir/a", "dir/sub/"})
+
+	// Whether scan returns the directories depends on the service.
+	got = collect(t, op.Scan, "dir/", true)
+	assertPaths(t, "scan", got, []string{"dir/a", "dir/sub/b"})
+
+	got = collect(t, op.List, "not_exist/", false)
+	assertPaths(t, "list not exist", got, nil)
+}
+
+func collect(t *testing.T, list func(string) (*opendal.Lister, error), path string, filesOnly bool) []string {
+	lister, err := list(path)
+	if err != nil {
+		t.Fatalf("list %q: %v", path, err)
+	}
+	defer lister.Close()
+
+	var paths []string
+	for lister.Next() {
+		p := lister.Entry().Path()
+		if filesOnly && p[len(p)-1] == '/' {
+			continue
+		}
+		paths = append(paths, p)
+	}
+	if err := lister.Err(); err != nil {
+		t.Fatalf("list %q: %v", path, err)
+	}
+	sort.Strings(paths)
+	return paths
+}
+
+func assertPaths(t *testing.T, name string, got, want []string) {
+	if len(got) != len(want) {
+		t.Errorf("%s got %q, want %q", name, got, want)
+		return
+	}
+	for i := range got {
+		if got[i] != want[i] {
+			t.Errorf("%s got %q, want %q", name, got, want)
+			return
+		}
+	}
+}
+
+func TestNewOperatorUnknownScheme(t *testing.T) {
+	_, err := opendal.NewOperator("unknown", nil)
+	if !errors.Is(err, opendal.ErrConfigInvalid) {
+		t.Errorf("new operator got %v, want %v", err, opendal.ErrConfigInvalid)
+	}
+}
+
+func TestClosedOperator(t *testing.T) {
+	op, err := opendal.NewOperator("memory", nil)
+	if err != nil {
+		t.Fatalf("new operator: %v", err)
+	}
+	if err := op.Close(); err != nil {
+		t.Fatalf("close: %v", err)
+	}
+	if err := op.Close(); err != nil {
+		t.Errorf("close twice got %v, want nil", err)
+	}
+
+	if _, err := op.Read("test"); err == nil {
+		t.Error("read on closed operator got nil error")
+	}
+	if err := op.Write("test", []byte("Hello")); err == nil {
+		t.Error("write on closed operator got nil error")
+	}
+	if _, err := op.Writer("test"); err == nil {
+		t.Error("writer on closed operator got nil error")
+	}
+}
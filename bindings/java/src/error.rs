@@ -0,0 +1,105 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use jni::objects::JThrowable;
+use jni::objects::JValue;
+use jni::JNIEnv;
+use opendal::ErrorKind;
+
+/// Error is the error type of java binding, which wraps [`opendal::Error`]
+/// and could be thrown as a java `ODException` with the corresponding code.
+pub(crate) struct Error {
+    inner: opendal::Error,
+}
+
+impl Error {
+    /// Throw this error as a java exception. If there is a pending java
+    /// exception already, e.g. raised by a failed JNI call, it will be kept.
+    pub(crate) fn throw(&self, env: &mut JNIEnv) {
+        if let Err(err) = self.do_throw(env) {
+            env.fatal_error(err.to_string());
+        }
+    }
+
+    /// Create the java exception of this error.
+    pub(crate) fn to_exception<'local>(
+        &self,
+        env: &mut JNIEnv<'local>,
+    ) -> jni::errors::Result<JThrowable<'local>> {
+        let code = match self.inner.kind() {
+            ErrorKind::Unsupported => "Unsupported",
+            ErrorKind::ConfigInvalid => "ConfigInvalid",
+            ErrorKind::NotFound => "NotFound",
+            ErrorKind::PermissionDenied => "PermissionDenied",
+            ErrorKind::IsADirectory => "IsADirectory",
+            ErrorKind::NotADirectory => "NotADirectory",
+            ErrorKind::AlreadyExists => "AlreadyExists",
+            ErrorKind::RateLimited => "RateLimited",
+            ErrorKind::IsSameFile => "IsSameFile",
+            ErrorKind::PreconditionFailed => "PreconditionFailed",
+            _ => "Unexpected",
+        };
+        let code = env.new_string(code)?;
+        let message = env.new_string(self.inner.to_string())?;
+        let exception = env.new_object(
+            "org/apache/opendal/ODException",
+            "(Ljava/lang/String;Ljava/lang/String;)V",
+            &[JValue::Object(&code), JValue::Object(&message)],
+        )?;
+        Ok(JThrowable::from(exception))
+    }
+
+    fn do_throw(&self, env: &mut JNIEnv) -> jni::errors::Result<()> {
+        if env.exception_check()? {
+            return Ok(());
+        }
+
+        let exception = self.to_exception(env)?;
+        env.throw(exception)
+    }
+}
+
+impl From<opendal::Error> for Error {
+    fn from(err: opendal::Error) -> Self {
+        Self { inner: err }
+    }
+}
+
+impl From<jni::errors::Error> for Error {
+    fn from(err: jni::errors::Error) -> Self {
+        opendal::Error::new(ErrorKind::Unexpected, "jni call failed")
+            .set_source(err)
+            .into()
+    }
+}
+
+impl From<std::string::FromUtf8Error> for Error {
+    fn from(err: std::string::FromUtf8Error) -> Self {
+        opendal::Error::new(ErrorKind::Unexpected, "content is not valid utf-8")
+            .set_source(err)
+            .into()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+}
@@ -16,236 +16,124 @@
 // under the License.
 
 use std::collections::HashMap;
-use std::str::FromStr;
+use std::future::Future;
 
-use jni::objects::JClass;
 use jni::objects::JMap;
 use jni::objects::JObject;
 use jni::objects::JString;
-use jni::sys::jboolean;
-use jni::sys::jlong;
+use jni::objects::JValue;
 use jni::JNIEnv;
-use opendal::BlockingOperator;
-use opendal::Operator;
-use opendal::Scheme;
-
-#[no_mangle]
-pub extern "system" fn Java_org_apache_opendal_Operator_getOperator(
-    mut env: JNIEnv,
-    _class: JClass,
-    input: JString,
-    params: JObject,
-) -> jlong {
-    let input: String = env
-        .get_string(&input)
-        .expect("Couldn't get java string!")
-        .into();
-
-    let scheme = Scheme::from_str(&input).unwrap();
-
-    let map = convert_map(&mut env, &params);
-    if let Ok(operator) = build_operator(scheme, map) {
-        Box::into_raw(Box::new(operator)) as jlong
-    } else {
-        env.exception_clear().expect("Couldn't clear exception");
-        env.throw_new(
-            "java/lang/IllegalArgumentException",
-            "Unsupported operator.",
-        )
-        .expect("Couldn't throw exception");
-        0 as jlong
-    }
-}
-
-/// # Safety
-///
-/// This function should not be called before the Operator are ready.
-#[no_mangle]
-pub unsafe extern "system" fn Java_org_apache_opendal_Operator_freeOperator(
-    mut _env: JNIEnv,
-    _class: JClass,
-    ptr: *mut Operator,
-) {
-    // Take ownership of the pointer by wrapping it with a Box
-    let _ = Box::from_raw(ptr);
-}
-
-/// # Safety
-///
-/// This function should not be called before the Operator are ready.
-#[no_mangle]
-pub unsafe extern "system" fn Java_org_apache_opendal_Operator_write(
-    mut env: JNIEnv,
-    _class: JClass,
-    ptr: *mut BlockingOperator,
-    file: JString,
-    content: JString,
-) {
-    let op = &mut *ptr;
-    let file: String = env
-        .get_string(&file)
-        .expect("Couldn't get java string!")
-        .into();
-    let content: String = env
-        .get_string(&content)
-        .expect("Couldn't get java string!")
-        .into();
-    op.write(&file, content).unwrap();
-}
-
-/// # Safety
-///
-/// This function should not be called before the Operator are ready.
-#[no_mangle]
-pub unsafe extern "system" fn Java_org_apache_opendal_Operator_read<'local>(
-    mut env: JNIEnv<'local>,
-    _class: JClass<'local>,
-    ptr: *mut BlockingOperator,
-    file: JString<'local>,
-) -> JString<'local> {
-    let op = &mut *ptr;
-    let file: String = env
-        .get_string(&file)
-        .expect("Couldn't get java string!")
-        .into();
-    let content = String::from_utf8(op.read(&file).unwrap()).expect("Couldn't convert to string");
-
-    let output = env
-        .new_string(content)
-        .expect("Couldn't create java string!");
-    output
+use once_cell::sync::Lazy;
+use tokio::runtime::Builder;
+use tokio::runtime::Runtime;
+
+mod error;
+mod lister;
+mod metadata;
+mod operator;
+mod operator_input_stream;
+mod operator_output_stream;
+
+use error::Error;
+
+pub(crate) type Result<T> = std::result::Result<T, Error>;
+
+/// The runtime shared by all async operations of java binding.
+static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    Builder::new_multi_thread()
+        .thread_name("opendal-java")
+        .enable_all()
+        .build()
+        .expect("build tokio runtime must succeed")
+});
+
+fn jstring_to_string(env: &mut JNIEnv, s: &JString) -> Result<String> {
+    Ok(env.get_string(s)?.into())
 }
 
-/// # Safety
-///
-/// This function should not be called before the Operator are ready.
-#[no_mangle]
-pub unsafe extern "system" fn Java_org_apache_opendal_Operator_stat(
-    mut env: JNIEnv,
-    _class: JClass,
-    ptr: *mut BlockingOperator,
-    file: JString,
-) -> jlong {
-    let op = &mut *ptr;
-    let file: String = env
-        .get_string(&file)
-        .expect("Couldn't get java string!")
-        .into();
-    let metadata = op.stat(&file).unwrap();
-    Box::into_raw(Box::new(metadata)) as jlong
-}
-
-/// # Safety
-///
-/// This function should not be called before the Stat are ready.
-#[no_mangle]
-pub unsafe extern "system" fn Java_org_apache_opendal_Metadata_isFile(
-    mut _env: JNIEnv,
-    _class: JClass,
-    ptr: *mut opendal::Metadata,
-) -> jboolean {
-    let metadata = &mut *ptr;
-    metadata.is_file() as jboolean
+fn convert_map(env: &mut JNIEnv, params: &JObject) -> Result<HashMap<String, String>> {
+    let mut result: HashMap<String, String> = HashMap::new();
+    let map = JMap::from_env(env, params)?;
+    let mut iter = map.iter(env)?;
+    while let Some(e) = iter.next(env)? {
+        let key = JString::from(e.0);
+        let value = JString::from(e.1);
+        let key = jstring_to_string(env, &key)?;
+        let value = jstring_to_string(env, &value)?;
+        result.insert(key, value);
+    }
+    Ok(result)
 }
 
-/// # Safety
-///
-/// This function should not be called before the Stat are ready.
-#[no_mangle]
-pub unsafe extern "system" fn Java_org_apache_opendal_Metadata_getContentLength(
-    mut _env: JNIEnv,
-    _class: JClass,
-    ptr: *mut opendal::Metadata,
-) -> jlong {
-    let metadata = &mut *ptr;
-    metadata.content_length() as jlong
-}
+/// Spawn `fut` on the shared runtime, and complete the java `CompletableFuture`
+/// with its output converted by `convert`, or complete it exceptionally with
+/// the corresponding exception if failed.
+fn spawn_future<T, F, C>(env: &mut JNIEnv, future: &JObject, fut: F, convert: C) -> Result<()>
+where
+    T: Send + 'static,
+    F: Future<Output = opendal::Result<T>> + Send + 'static,
+    C: for<'local> FnOnce(&mut JNIEnv<'local>, T) -> Result<JObject<'local>> + Send + 'static,
+{
+    let vm = env.get_java_vm()?;
+    let future = env.new_global_ref(future)?;
+
+    RUNTIME.spawn(async move {
+        let output = fut.await;
+
+        // There is no way to reach the future if the thread can't be
+        // attached to the jvm, which only happens when the jvm is exiting.
+        let mut env = match vm.attach_current_thread_as_daemon() {
+            Ok(env) => env,
+            Err(_) => return,
+        };
+        // local references created by a thread attached as daemon won't be
+        // freed until the thread exits, so we must create them in a frame.
+        let res = env.with_local_frame(4, |env| -> Result<()> {
+            match output.map_err(Error::from).and_then(|v| convert(env, v)) {
+                Ok(v) => complete_future(env, &future, v),
+                Err(err) => complete_future_exceptionally(env, &future, &err),
+            }
+        });
+        // Completing the future failed, retry to complete it exceptionally
+        // with the error so that the future won't hang forever.
+        if let Err(err) = res {
+            let res = env.with_local_frame(4, |env| -> Result<()> {
+                complete_future_exceptionally(env, &future, &err)
+            });
+            if let Err(err) = res {
+                env.fatal_error(format!("complete future failed: {err}"));
+            }
+        }
+    });
 
-/// # Safety
-///
-/// This function should not be called before the Stat are ready.
-#[no_mangle]
-pub unsafe extern "system" fn Java_org_apache_opendal_Metadata_freeStat(
-    mut _env: JNIEnv,
-    _class: JClass,
-    ptr: *mut opendal::Metadata,
-) {
-    // Take ownership of the pointer by wrapping it with a Box
-    let _ = Box::from_raw(ptr);
+    Ok(())
 }
 
-/// # Safety
-///
-/// This function should not be called before the Operator are ready.
-#[no_mangle]
-pub unsafe extern "system" fn Java_org_apache_opendal_Operator_delete<'local>(
-    mut env: JNIEnv<'local>,
-    _class: JClass<'local>,
-    ptr: *mut BlockingOperator,
-    file: JString<'local>,
-) {
-    let op = &mut *ptr;
-    let file: String = env
-        .get_string(&file)
-        .expect("Couldn't get java string!")
-        .into();
-    op.delete(&file).unwrap();
+fn complete_future(env: &mut JNIEnv, future: &JObject, value: JObject) -> Result<()> {
+    env.call_method(
+        future,
+        "complete",
+        "(Ljava/lang/Object;)Z",
+        &[JValue::Object(&value)],
+    )?;
+    Ok(())
 }
 
-fn build_operator(
-    scheme: opendal::Scheme,
-    map: HashMap<String, String>,
-) -> Result<opendal::Operator, opendal::Error> {
-    use opendal::services::*;
-
-    let op = match scheme {
-        opendal::Scheme::Azblob => opendal::Operator::from_map::<Azblob>(map).unwrap().finish(),
-        opendal::Scheme::Azdfs => opendal::Operator::from_map::<Azdfs>(map).unwrap().finish(),
-        opendal::Scheme::Fs => opendal::Operator::from_map::<Fs>(map).unwrap().finish(),
-        opendal::Scheme::Gcs => opendal::Operator::from_map::<Gcs>(map).unwrap().finish(),
-        opendal::Scheme::Ghac => opendal::Operator::from_map::<Ghac>(map).unwrap().finish(),
-        opendal::Scheme::Http => opendal::Operator::from_map::<Http>(map).unwrap().finish(),
-        opendal::Scheme::Ipmfs => opendal::Operator::from_map::<Ipmfs>(map).unwrap().finish(),
-        opendal::Scheme::Memory => opendal::Operator::from_map::<Memory>(map).unwrap().finish(),
-        opendal::Scheme::Obs => opendal::Operator::from_map::<Obs>(map).unwrap().finish(),
-        opendal::Scheme::Oss => opendal::Operator::from_map::<Oss>(map).unwrap().finish(),
-        opendal::Scheme::S3 => opendal::Operator::from_map::<S3>(map).unwrap().finish(),
-        opendal::Scheme::Webdav => opendal::Operator::from_map::<Webdav>(map).unwrap().finish(),
-        opendal::Scheme::Webhdfs => opendal::Operator::from_map::<Webhdfs>(map)
-            .unwrap()
-            .finish(),
-
-        _ => {
-            return Err(opendal::Error::new(
-                opendal::ErrorKind::Unexpected,
-                "Scheme not supported",
-            ));
-        }
+/// Complete the future with the pending java exception raised by failed JNI
+/// calls if there is one, or the exception created from `err`.
+fn complete_future_exceptionally(env: &mut JNIEnv, future: &JObject, err: &Error) -> Result<()> {
+    let exception = if env.exception_check()? {
+        let exception = env.exception_occurred()?;
+        env.exception_clear()?;
+        exception
+    } else {
+        err.to_exception(env)?
     };
-
-    Ok(op)
-}
-
-fn convert_map(env: &mut JNIEnv, params: &JObject) -> HashMap<String, String> {
-    let mut result: HashMap<String, String> = HashMap::new();
-    let _ = JMap::from_env(env, params)
-        .unwrap()
-        .iter(env)
-        .and_then(|mut iter| {
-            while let Some(e) = iter.next(env)? {
-                let key = JString::from(e.0);
-                let value = JString::from(e.1);
-                let key: String = env
-                    .get_string(&key)
-                    .expect("Couldn't get java string!")
-                    .into();
-                let value: String = env
-                    .get_string(&value)
-                    .expect("Couldn't get java string!")
-                    .into();
-                result.insert(key, value);
-            }
-            Ok(())
-        });
-    result
+    env.call_method(
+        future,
+        "completeExceptionally",
+        "(Ljava/lang/Throwable;)Z",
+        &[JValue::Object(&exception)],
+    )?;
+    Ok(())
 }
@@ -0,0 +1,69 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use jni::objects::JClass;
+use jni::objects::JObject;
+use jni::objects::JValue;
+use jni::sys::jobject;
+use jni::JNIEnv;
+use opendal::BlockingLister;
+
+use crate::Result;
+
+/// # Safety
+///
+/// This function should not be called before the Lister are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Lister_next(
+    mut env: JNIEnv,
+    _class: JClass,
+    lister: *mut BlockingLister,
+) -> jobject {
+    intern_next(&mut env, &mut *lister).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        JObject::null().into_raw()
+    })
+}
+
+fn intern_next(env: &mut JNIEnv, lister: &mut BlockingLister) -> Result<jobject> {
+    let entry = match lister.next() {
+        Some(entry) => entry?,
+        None => return Ok(JObject::null().into_raw()),
+    };
+
+    let path = env.new_string(entry.path())?;
+    let name = env.new_string(entry.name())?;
+    let object = env.new_object(
+        "org/apache/opendal/Entry",
+        "(Ljava/lang/String;Ljava/lang/String;)V",
+        &[JValue::Object(&path), JValue::Object(&name)],
+    )?;
+    Ok(object.into_raw())
+}
+
+/// # Safety
+///
+/// This function should not be called before the Lister are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Lister_freeLister(
+    mut _env: JNIEnv,
+    _class: JClass,
+    lister: *mut BlockingLister,
+) {
+    // Take ownership of the pointer by wrapping it with a Box
+    let _ = Box::from_raw(lister);
+}
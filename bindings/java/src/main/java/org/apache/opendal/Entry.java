@@ -0,0 +1,56 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal;
+
+public class Entry {
+
+    private final String path;
+
+    private final String name;
+
+
+    public Entry(String path, String name) {
+        this.path = path;
+        this.name = name;
+    }
+
+    /**
+     * Returns the path relative to the operator's root, dirs end with "/".
+     */
+    public String getPath() {
+        return path;
+    }
+
+    /**
+     * Returns the last segment of the path, dirs end with "/".
+     */
+    public String getName() {
+        return name;
+    }
+
+    public boolean isDir() {
+        return path.endsWith("/");
+    }
+
+    @Override
+    public String toString() {
+        return path;
+    }
+}
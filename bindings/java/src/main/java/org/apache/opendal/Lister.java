@@ -0,0 +1,70 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal;
+
+import java.util.Iterator;
+import java.util.NoSuchElementException;
+
+public class Lister implements Iterator<Entry>, AutoCloseable {
+
+    long ptr;
+
+    private Entry next;
+
+    private native Entry next(long ptr);
+
+    private native void freeLister(long ptr);
+
+
+    public Lister(long ptr) {
+        this.ptr = ptr;
+    }
+
+    @Override
+    public boolean hasNext() {
+        if (next == null && ptr != 0) {
+            next = next(ptr);
+        }
+        return next != null;
+    }
+
+    @Override
+    public Entry next() {
+        if (!hasNext()) {
+            throw new NoSuchElementException();
+        }
+        Entry entry = next;
+        next = null;
+        return entry;
+    }
+
+    @Override
+    public void close() {
+        if (ptr != 0) {
+            freeLister(ptr);
+            ptr = 0;
+        }
+    }
+
+    @Override
+    protected void finalize() {
+        close();
+    }
+}
@@ -19,31 +19,95 @@
 
 package org.apache.opendal;
 
+import java.time.Instant;
+
 public class Metadata {
 
-    long ptr;
+    public enum EntryMode {
+        FILE,
+        DIR,
+        UNKNOWN,
+    }
+
+    private final EntryMode mode;
+
+    private final long contentLength;
+
+    private final String contentDisposition;
 
-    private native void freeStat(long statPtr);
+    private final String contentMd5;
 
-    private native boolean isFile(long statPtr);
+    private final String contentType;
 
-    private native long getContentLength(long statPtr);
+    private final String etag;
 
+    private final Instant lastModified;
 
-    public Metadata(long ptr) {
-        this.ptr = ptr;
+
+    public Metadata(int mode,
+                    long contentLength,
+                    String contentDisposition,
+                    String contentMd5,
+                    String contentType,
+                    String etag,
+                    long lastModified) {
+        this.mode = EntryMode.values()[mode];
+        this.contentLength = contentLength;
+        this.contentDisposition = contentDisposition;
+        this.contentMd5 = contentMd5;
+        this.contentType = contentType;
+        this.etag = etag;
+        this.lastModified = lastModified < 0 ? null : Instant.ofEpochMilli(lastModified);
+    }
+
+    public EntryMode getMode() {
+        return mode;
     }
 
     public boolean isFile() {
-        return isFile(this.ptr);
+        return mode == EntryMode.FILE;
     }
 
-    @Override
-    protected void finalize() {
-        freeStat(this.ptr);
+    public boolean isDir() {
+        return mode == EntryMode.DIR;
     }
 
     public long getContentLength() {
-        return getContentLength(this.ptr);
+        return contentLength;
+    }
+
+    /**
+     * Returns the content disposition, or null if the service doesn't provide it.
+     */
+    public String getContentDisposition() {
+        return contentDisposition;
+    }
+
+    /**
+     * Returns the content md5, or null if the service doesn't provide it.
+     */
+    public String getContentMd5() {
+        return contentMd5;
+    }
+
+    /**
+     * Returns the content type, or null if the service doesn't provide it.
+     */
+    public String getContentType() {
+        return contentType;
+    }
+
+    /**
+     * Returns the etag, or null if the service doesn't provide it.
+     */
+    public String getEtag() {
+        return etag;
+    }
+
+    /**
+     * Returns the last modified time, or null if the service doesn't provide it.
+     */
+    public Instant getLastModified() {
+        return lastModified;
     }
 }
@@ -17,12 +17,12 @@
  * under the License.
  */
 
-
 package org.apache.opendal;
 
 import io.questdb.jar.jni.JarJniLoader;
 
 import java.util.Map;
+import java.util.concurrent.CompletableFuture;
 
 
 public class Operator {
@@ -50,28 +50,112 @@ public class Operator {
 
     private native void write(long ptr, String fileName, String content);
 
+    private native void writeAsync(long ptr, CompletableFuture<Void> future, String fileName, String content);
+
     private native String read(long ptr, String fileName);
 
+    private native void readAsync(long ptr, CompletableFuture<String> future, String fileName);
+
     private native void delete(long ptr, String fileName);
 
-    private native long stat(long ptr, String file);
+    private native void deleteAsync(long ptr, CompletableFuture<Void> future, String fileName);
+
+    private native Metadata stat(long ptr, String file);
+
+    private native void statAsync(long ptr, CompletableFuture<Metadata> future, String file);
+
+    private native void createDir(long ptr, String path);
+
+    private native void createDirAsync(long ptr, CompletableFuture<Void> future, String path);
+
+    private native long list(long ptr, String path);
+
+    private native long scan(long ptr, String path);
+
+    private native long reader(long ptr, String fileName);
+
+    private native long writer(long ptr, String fileName);
 
 
     public void write(String fileName, String content) {
         write(this.ptr, fileName, content);
     }
 
+    public CompletableFuture<Void> writeAsync(String fileName, String content) {
+        CompletableFuture<Void> future = new CompletableFuture<>();
+        writeAsync(this.ptr, future, fileName, content);
+        return future;
+    }
+
     public String read(String s) {
         return read(this.ptr, s);
     }
 
+    public CompletableFuture<String> readAsync(String s) {
+        CompletableFuture<String> future = new CompletableFuture<>();
+        readAsync(this.ptr, future, s);
+        return future;
+    }
+
     public void delete(String s) {
         delete(this.ptr, s);
     }
 
+    public CompletableFuture<Void> deleteAsync(String s) {
+        CompletableFuture<Void> future = new CompletableFuture<>();
+        deleteAsync(this.ptr, future, s);
+        return future;
+    }
+
     public Metadata stat(String fileName) {
-        long statPtr = stat(this.ptr, fileName);
-        return new Metadata(statPtr);
+        return stat(this.ptr, fileName);
+    }
+
+    public CompletableFuture<Metadata> statAsync(String fileName) {
+        CompletableFuture<Metadata> future = new CompletableFuture<>();
+        statAsync(this.ptr, future, fileName);
+        return future;
+    }
+
+    /**
+     * Create a dir at the given path, the path must end with "/".
+     */
+    public void createDir(String path) {
+        createDir(this.ptr, path);
+    }
+
+    public CompletableFuture<Void> createDirAsync(String path) {
+        CompletableFuture<Void> future = new CompletableFuture<>();
+        createDirAsync(this.ptr, future, path);
+        return future;
+    }
+
+    /**
+     * List the entries directly under the given dir, the returned lister
+     * should be closed after use.
+     */
+    public Lister list(String path) {
+        return new Lister(list(this.ptr, path));
+    }
+
+    /**
+     * List the entries under the given dir recursively, the returned lister
+     * should be closed after use.
+     */
+    public Lister scan(String path) {
+        return new Lister(scan(this.ptr, path));
+    }
+
+    public OperatorInputStream createInputStream(String fileName) {
+        return new OperatorInputStream(reader(this.ptr, fileName));
+    }
+
+    /**
+     * Create an output stream to write the file, the content is stored
+     * only after the stream is closed.
+     */
+    public OperatorOutputStream createOutputStream(String fileName) {
+        return new OperatorOutputStream(writer(this.ptr, fileName));
     }
 
     @Override
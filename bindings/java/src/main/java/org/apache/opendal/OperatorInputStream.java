@@ -0,0 +1,71 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+public class OperatorInputStream extends InputStream {
+
+    long ptr;
+
+    private native int read(long ptr, byte[] b, int off, int len);
+
+    private native void freeReader(long ptr);
+
+
+    public OperatorInputStream(long ptr) {
+        this.ptr = ptr;
+    }
+
+    @Override
+    public int read() throws IOException {
+        byte[] b = new byte[1];
+        int n = read(b, 0, 1);
+        return n == -1 ? -1 : b[0] & 0xff;
+    }
+
+    @Override
+    public int read(byte[] b, int off, int len) throws IOException {
+        if (ptr == 0) {
+            throw new IOException("stream is closed");
+        }
+        if (off < 0 || len < 0 || len > b.length - off) {
+            throw new IndexOutOfBoundsException();
+        }
+        if (len == 0) {
+            return 0;
+        }
+        return read(ptr, b, off, len);
+    }
+
+    @Override
+    public void close() {
+        if (ptr != 0) {
+            freeReader(ptr);
+            ptr = 0;
+        }
+    }
+
+    @Override
+    protected void finalize() {
+        close();
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal;
+
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+import java.io.OutputStream;
+
+public class OperatorOutputStream extends OutputStream {
+
+    /**
+     * Content is appended to the underlying writer in blocks of this size
+     * (except the last one), because services like s3 require every part
+     * except the last one to be at least 5 MiB.
+     */
+    private static final int BLOCK_SIZE = 8 * 1024 * 1024;
+
+    long ptr;
+
+    private final ByteArrayOutputStream buffer = new ByteArrayOutputStream();
+
+    private native void append(long ptr, byte[] content);
+
+    private native void close(long ptr);
+
+    private native void freeWriter(long ptr);
+
+
+    public OperatorOutputStream(long ptr) {
+        this.ptr = ptr;
+    }
+
+    @Override
+    public void write(int b) throws IOException {
+        write(new byte[]{(byte) b}, 0, 1);
+    }
+
+    @Override
+    public void write(byte[] b, int off, int len) throws IOException {
+        if (ptr == 0) {
+            throw new IOException("stream is closed");
+        }
+        while (len > 0) {
+            int n = Math.min(len, BLOCK_SIZE - buffer.size());
+            buffer.write(b, off, n);
+            off += n;
+            len -= n;
+            if (buffer.size() == BLOCK_SIZE) {
+                appendBuffer();
+            }
+        }
+    }
+
+    @Override
+    public void close() {
+        if (ptr == 0) {
+            return;
+        }
+        try {
+            if (buffer.size() > 0) {
+                appendBuffer();
+            }
+            close(ptr);
+        } finally {
+            freeWriter(ptr);
+            ptr = 0;
+        }
+    }
+
+    private void appendBuffer() {
+        append(ptr, buffer.toByteArray());
+        buffer.reset();
+    }
+}
@@ -0,0 +1,70 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use jni::objects::JObject;
+use jni::objects::JValue;
+use jni::JNIEnv;
+use opendal::EntryMode;
+use opendal::Metadata;
+
+use crate::Result;
+
+/// Convert [`Metadata`] into java `org.apache.opendal.Metadata`.
+pub(crate) fn make_metadata<'local>(
+    env: &mut JNIEnv<'local>,
+    metadata: Metadata,
+) -> Result<JObject<'local>> {
+    let mode = match metadata.mode() {
+        EntryMode::FILE => 0,
+        EntryMode::DIR => 1,
+        EntryMode::Unknown => 2,
+    };
+    let last_modified = metadata
+        .last_modified()
+        .map(|t| t.timestamp_millis())
+        .unwrap_or(-1);
+
+    let content_disposition = make_optional_string(env, metadata.content_disposition())?;
+    let content_md5 = make_optional_string(env, metadata.content_md5())?;
+    let content_type = make_optional_string(env, metadata.content_type())?;
+    let etag = make_optional_string(env, metadata.etag())?;
+
+    let object = env.new_object(
+        "org/apache/opendal/Metadata",
+        "(IJLjava/lang/String;Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;J)V",
+        &[
+            JValue::Int(mode),
+            JValue::Long(metadata.content_length() as i64),
+            JValue::Object(&content_disposition),
+            JValue::Object(&content_md5),
+            JValue::Object(&content_type),
+            JValue::Object(&etag),
+            JValue::Long(last_modified),
+        ],
+    )?;
+    Ok(object)
+}
+
+fn make_optional_string<'local>(
+    env: &mut JNIEnv<'local>,
+    s: Option<&str>,
+) -> Result<JObject<'local>> {
+    Ok(match s {
+        Some(s) => env.new_string(s)?.into(),
+        None => JObject::null(),
+    })
+}
@@ -0,0 +1,408 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::str::FromStr;
+
+use jni::objects::JClass;
+use jni::objects::JObject;
+use jni::objects::JString;
+use jni::sys::jlong;
+use jni::sys::jobject;
+use jni::sys::jstring;
+use jni::JNIEnv;
+use opendal::Operator;
+use opendal::Scheme;
+
+use crate::convert_map;
+use crate::jstring_to_string;
+use crate::metadata::make_metadata;
+use crate::spawn_future;
+use crate::Result;
+
+#[no_mangle]
+pub extern "system" fn Java_org_apache_opendal_Operator_getOperator(
+    mut env: JNIEnv,
+    _class: JClass,
+    scheme: JString,
+    params: JObject,
+) -> jlong {
+    intern_get_operator(&mut env, scheme, params).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        0
+    })
+}
+
+fn intern_get_operator(env: &mut JNIEnv, scheme: JString, params: JObject) -> Result<jlong> {
+    let scheme = Scheme::from_str(&jstring_to_string(env, &scheme)?)?;
+    let map = convert_map(env, &params)?;
+    let op = Operator::via_map(scheme, map)?;
+    Ok(Box::into_raw(Box::new(op)) as jlong)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_freeOperator(
+    mut _env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+) {
+    // Take ownership of the pointer by wrapping it with a Box
+    let _ = Box::from_raw(op);
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_write(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+    content: JString,
+) {
+    intern_write(&mut env, &*op, path, content).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_write(env: &mut JNIEnv, op: &Operator, path: JString, content: JString) -> Result<()> {
+    let path = jstring_to_string(env, &path)?;
+    let content = jstring_to_string(env, &content)?;
+    Ok(op.blocking().write(&path, content)?)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_writeAsync(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    future: JObject,
+    path: JString,
+    content: JString,
+) {
+    intern_write_async(&mut env, &*op, &future, path, content).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_write_async(
+    env: &mut JNIEnv,
+    op: &Operator,
+    future: &JObject,
+    path: JString,
+    content: JString,
+) -> Result<()> {
+    let path = jstring_to_string(env, &path)?;
+    let content = jstring_to_string(env, &content)?;
+    let op = op.clone();
+    spawn_future(
+        env,
+        future,
+        async move { op.write(&path, content).await },
+        |_, _| Ok(JObject::null()),
+    )
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_read(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+) -> jstring {
+    intern_read(&mut env, &*op, path).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        JObject::null().into_raw()
+    })
+}
+
+fn intern_read(env: &mut JNIEnv, op: &Operator, path: JString) -> Result<jstring> {
+    let path = jstring_to_string(env, &path)?;
+    let content = String::from_utf8(op.blocking().read(&path)?)?;
+    Ok(env.new_string(content)?.into_raw())
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_readAsync(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    future: JObject,
+    path: JString,
+) {
+    intern_read_async(&mut env, &*op, &future, path).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_read_async(
+    env: &mut JNIEnv,
+    op: &Operator,
+    future: &JObject,
+    path: JString,
+) -> Result<()> {
+    let path = jstring_to_string(env, &path)?;
+    let op = op.clone();
+    spawn_future(
+        env,
+        future,
+        async move { op.read(&path).await },
+        |env, content| Ok(env.new_string(String::from_utf8(content)?)?.into()),
+    )
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_stat(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+) -> jobject {
+    intern_stat(&mut env, &*op, path).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        JObject::null().into_raw()
+    })
+}
+
+fn intern_stat(env: &mut JNIEnv, op: &Operator, path: JString) -> Result<jobject> {
+    let path = jstring_to_string(env, &path)?;
+    let metadata = op.blocking().stat(&path)?;
+    Ok(make_metadata(env, metadata)?.into_raw())
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_statAsync(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    future: JObject,
+    path: JString,
+) {
+    intern_stat_async(&mut env, &*op, &future, path).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_stat_async(
+    env: &mut JNIEnv,
+    op: &Operator,
+    future: &JObject,
+    path: JString,
+) -> Result<()> {
+    let path = jstring_to_string(env, &path)?;
+    let op = op.clone();
+    spawn_future(
+        env,
+        future,
+        async move { op.stat(&path).await },
+        make_metadata,
+    )
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_delete(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+) {
+    intern_delete(&mut env, &*op, path).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_delete(env: &mut JNIEnv, op: &Operator, path: JString) -> Result<()> {
+    let path = jstring_to_string(env, &path)?;
+    Ok(op.blocking().delete(&path)?)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_deleteAsync(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    future: JObject,
+    path: JString,
+) {
+    intern_delete_async(&mut env, &*op, &future, path).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_delete_async(
+    env: &mut JNIEnv,
+    op: &Operator,
+    future: &JObject,
+    path: JString,
+) -> Result<()> {
+    let path = jstring_to_string(env, &path)?;
+    let op = op.clone();
+    spawn_future(
+        env,
+        future,
+        async move { op.delete(&path).await },
+        |_, _| Ok(JObject::null()),
+    )
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_createDir(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+) {
+    intern_create_dir(&mut env, &*op, path).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_create_dir(env: &mut JNIEnv, op: &Operator, path: JString) -> Result<()> {
+    let path = jstring_to_string(env, &path)?;
+    Ok(op.blocking().create_dir(&path)?)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_createDirAsync(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    future: JObject,
+    path: JString,
+) {
+    intern_create_dir_async(&mut env, &*op, &future, path).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_create_dir_async(
+    env: &mut JNIEnv,
+    op: &Operator,
+    future: &JObject,
+    path: JString,
+) -> Result<()> {
+    let path = jstring_to_string(env, &path)?;
+    let op = op.clone();
+    spawn_future(
+        env,
+        future,
+        async move { op.create_dir(&path).await },
+        |_, _| Ok(JObject::null()),
+    )
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_list(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+) -> jlong {
+    intern_list(&mut env, &*op, path).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        0
+    })
+}
+
+fn intern_list(env: &mut JNIEnv, op: &Operator, path: JString) -> Result<jlong> {
+    let path = jstring_to_string(env, &path)?;
+    let lister = op.blocking().list(&path)?;
+    Ok(Box::into_raw(Box::new(lister)) as jlong)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_scan(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+) -> jlong {
+    intern_scan(&mut env, &*op, path).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        0
+    })
+}
+
+fn intern_scan(env: &mut JNIEnv, op: &Operator, path: JString) -> Result<jlong> {
+    let path = jstring_to_string(env, &path)?;
+    let lister = op.blocking().scan(&path)?;
+    Ok(Box::into_raw(Box::new(lister)) as jlong)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_reader(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+) -> jlong {
+    intern_reader(&mut env, &*op, path).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        0
+    })
+}
+
+fn intern_reader(env: &mut JNIEnv, op: &Operator, path: JString) -> Result<jlong> {
+    let path = jstring_to_string(env, &path)?;
+    let reader = op.blocking().reader(&path)?;
+    Ok(Box::into_raw(Box::new(reader)) as jlong)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Operator are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_Operator_writer(
+    mut env: JNIEnv,
+    _class: JClass,
+    op: *mut Operator,
+    path: JString,
+) -> jlong {
+    intern_writer(&mut env, &*op, path).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        0
+    })
+}
+
+fn intern_writer(env: &mut JNIEnv, op: &Operator, path: JString) -> Result<jlong> {
+    let path = jstring_to_string(env, &path)?;
+    let writer = op.blocking().writer(&path)?;
+    Ok(Box::into_raw(Box::new(writer)) as jlong)
+}
@@ -0,0 +1,74 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use jni::objects::JByteArray;
+use jni::objects::JClass;
+use jni::sys::jint;
+use jni::JNIEnv;
+use opendal::raw::oio::BlockingRead;
+use opendal::BlockingReader;
+
+use crate::Result;
+
+/// # Safety
+///
+/// This function should not be called before the Reader are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_OperatorInputStream_read(
+    mut env: JNIEnv,
+    _class: JClass,
+    reader: *mut BlockingReader,
+    buf: JByteArray,
+    off: jint,
+    len: jint,
+) -> jint {
+    intern_read(&mut env, &mut *reader, buf, off, len).unwrap_or_else(|e| {
+        e.throw(&mut env);
+        -1
+    })
+}
+
+fn intern_read(
+    env: &mut JNIEnv,
+    reader: &mut BlockingReader,
+    buf: JByteArray,
+    off: jint,
+    len: jint,
+) -> Result<jint> {
+    let mut bs = vec![0; len as usize];
+    let n = reader.read(&mut bs)?;
+    if n == 0 && len > 0 {
+        return Ok(-1);
+    }
+
+    let bs: Vec<i8> = bs[..n].iter().map(|v| *v as i8).collect();
+    env.set_byte_array_region(buf, off, &bs)?;
+    Ok(n as jint)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Reader are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_OperatorInputStream_freeReader(
+    mut _env: JNIEnv,
+    _class: JClass,
+    reader: *mut BlockingReader,
+) {
+    // Take ownership of the pointer by wrapping it with a Box
+    let _ = Box::from_raw(reader);
+}
@@ -0,0 +1,70 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use jni::objects::JByteArray;
+use jni::objects::JClass;
+use jni::JNIEnv;
+use opendal::BlockingWriter;
+
+use crate::Result;
+
+/// # Safety
+///
+/// This function should not be called before the Writer are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_OperatorOutputStream_append(
+    mut env: JNIEnv,
+    _class: JClass,
+    writer: *mut BlockingWriter,
+    content: JByteArray,
+) {
+    intern_append(&mut env, &mut *writer, content).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_append(env: &mut JNIEnv, writer: &mut BlockingWriter, content: JByteArray) -> Result<()> {
+    let content = env.convert_byte_array(content)?;
+    Ok(writer.append(content)?)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Writer are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_OperatorOutputStream_close(
+    mut env: JNIEnv,
+    _class: JClass,
+    writer: *mut BlockingWriter,
+) {
+    intern_close(&mut *writer).unwrap_or_else(|e| e.throw(&mut env))
+}
+
+fn intern_close(writer: &mut BlockingWriter) -> Result<()> {
+    Ok(writer.close()?)
+}
+
+/// # Safety
+///
+/// This function should not be called before the Writer are ready.
+#[no_mangle]
+pub unsafe extern "system" fn Java_org_apache_opendal_OperatorOutputStream_freeWriter(
+    mut _env: JNIEnv,
+    _class: JClass,
+    writer: *mut BlockingWriter,
+) {
+    // Take ownership of the pointer by wrapping it with a Box
+    let _ = Box::from_raw(writer);
+}
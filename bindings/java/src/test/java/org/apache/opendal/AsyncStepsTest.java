@@ -23,31 +23,48 @@ import io.cucumber.java.en.Given;
 import io.cucumber.java.en.Then;
 import io.cucumber.java.en.When;
 
+import java.util.HashMap;
+import java.util.Map;
+
+import static org.junit.jupiter.api.Assertions.*;
 
 public class AsyncStepsTest {
 
+    Operator operator;
 
     @Given("A new OpenDAL Async Operator")
     public void a_new_open_dal_async_operator() {
+        Map<String, String> params = new HashMap<>();
+        params.put("root", "/tmp");
+        this.operator = new Operator("Memory", params);
     }
 
     @When("Async write path {string} with content {string}")
     public void async_write_path_test_with_content_hello_world(String fileName, String content) {
+        this.operator.writeAsync(fileName, content).join();
     }
 
     @Then("The async file {string} should exist")
     public void the_async_file_test_should_exist(String fileName) {
+        Metadata metadata = this.operator.statAsync(fileName).join();
+        assertNotNull(metadata);
     }
 
     @Then("The async file {string} entry mode must be file")
     public void the_async_file_test_entry_mode_must_be_file(String fileName) {
+        Metadata metadata = this.operator.statAsync(fileName).join();
+        assertTrue(metadata.isFile());
     }
 
     @Then("The async file {string} content length must be {int}")
     public void the_async_file_test_content_length_must_be_13(String fileName, int length) {
+        Metadata metadata = this.operator.statAsync(fileName).join();
+        assertEquals(metadata.getContentLength(), length);
     }
 
     @Then("The async file {string} must have content {string}")
     public void the_async_file_test_must_have_content_hello_world(String fileName, String content) {
+        String readContent = this.operator.readAsync(fileName).join();
+        assertEquals(content, readContent);
     }
 }
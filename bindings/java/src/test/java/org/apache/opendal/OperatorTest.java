@@ -0,0 +1,128 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+package org.apache.opendal;
+
+import org.junit.jupiter.api.BeforeEach;
+import org.junit.jupiter.api.Test;
+
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.OutputStream;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.concurrent.CompletionException;
+
+import static org.junit.jupiter.api.Assertions.*;
+
+public class OperatorTest {
+
+    Operator operator;
+
+    @BeforeEach
+    public void setUp() {
+        this.operator = new Operator("Memory", new HashMap<>());
+    }
+
+    @Test
+    public void testListAndScan() {
+        this.operator.createDir("dir/");
+        this.operator.write("dir/file", "Hello");
+        this.operator.write("dir/sub/file", "World");
+
+        List<String> paths = new ArrayList<>();
+        try (Lister lister = this.operator.list("dir/")) {
+            lister.forEachRemaining(e -> paths.add(e.getPath()));
+        }
+        paths.sort(String::compareTo);
+        assertEquals(2, paths.size());
+        assertEquals("dir/file", paths.get(0));
+        assertEquals("dir/sub/", paths.get(1));
+
+        List<Entry> entries = new ArrayList<>();
+        try (Lister lister = this.operator.scan("dir/")) {
+            lister.forEachRemaining(entries::add);
+        }
+        assertTrue(entries.stream().anyMatch(e -> e.getPath().equals("dir/sub/file") && e.getName().equals("file")));
+    }
+
+    @Test
+    public void testStreams() throws IOException {
+        byte[] content = new byte[5 * 1024 * 1024];
+        for (int i = 0; i < content.length; i++) {
+            content[i] = (byte) i;
+        }
+        try (OutputStream os = this.operator.createOutputStream("stream")) {
+            os.write(content, 0, 1024);
+            os.write(content, 1024, content.length - 1024);
+        }
+        assertEquals(content.length, this.operator.stat("stream").getContentLength());
+
+        ByteArrayOutputStream read = new ByteArrayOutputStream();
+        try (InputStream is = this.operator.createInputStream("stream")) {
+            byte[] buf = new byte[8192];
+            int n;
+            while ((n = is.read(buf)) != -1) {
+                read.write(buf, 0, n);
+            }
+        }
+        assertArrayEquals(content, read.toByteArray());
+    }
+
+    @Test
+    public void testMetadata() {
+        this.operator.write("meta", "Hello, World!");
+        Metadata metadata = this.operator.stat("meta");
+        assertEquals(Metadata.EntryMode.FILE, metadata.getMode());
+        assertFalse(metadata.isDir());
+        assertEquals(13, metadata.getContentLength());
+
+        this.operator.createDirAsync("meta_dir/").join();
+        assertTrue(this.operator.statAsync("meta_dir/").join().isDir());
+    }
+
+    @Test
+    public void testNotFound() {
+        ODException e = assertThrows(ODException.class, () -> this.operator.stat("not_exist"));
+        assertEquals(ODException.Code.NotFound, e.getCode());
+
+        CompletionException ce = assertThrows(CompletionException.class,
+                () -> this.operator.readAsync("not_exist").join());
+        assertInstanceOf(ODException.class, ce.getCause());
+        assertEquals(ODException.Code.NotFound, ((ODException) ce.getCause()).getCode());
+
+        this.operator.write("delete", "Hello");
+        this.operator.deleteAsync("delete").join();
+        e = assertThrows(ODException.class, () -> this.operator.read("delete"));
+        assertEquals(ODException.Code.NotFound, e.getCode());
+    }
+
+    @Test
+    public void testSingleByte() throws IOException {
+        try (OutputStream os = this.operator.createOutputStream("binary")) {
+            os.write(0xff);
+        }
+        try (InputStream is = this.operator.createInputStream("binary")) {
+            assertEquals(0xff, is.read());
+            assertEquals(-1, is.read());
+        }
+    }
+}
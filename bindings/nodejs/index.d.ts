@@ -17,399 +17,38 @@
  * under the License.
  */
 
-/* tslint:disable */
-/* eslint-disable */
+/// <reference types="node" />
 
-/* auto-generated by NAPI-RS */
+import { Readable, Writable } from 'stream'
+import { ReadRange, WriteOptions } from './generated'
 
-export interface PresignedRequest {
-  /** HTTP method of this request. */
-  method: string
-  /** URL of this request. */
-  url: string
-  /** HTTP headers of this request. */
-  headers: Record<string, string>
-}
-export class Operator {
-  constructor(scheme: string, options?: Record<string, string> | undefined | null)
-  /**
-   * Get current path's metadata **without cache** directly.
-   *
-   * ### Notes
-   * Use stat if you:
-   *
-   * - Want detect the outside changes of path.
-   * - Don’t want to read from cached metadata.
-   *
-   * You may want to use `metadata` if you are working with entries returned by `Lister`. It’s highly possible that metadata you want has already been cached.
-   *
-   * ### Example
-   * ```javascript
-   * const meta = await op.stat("test");
-   * if (meta.isDir) {
-   *   // do something
-   * }
-   * ```
-   */
-  stat(path: string): Promise<Metadata>
-  /**
-   * Get current path's metadata **without cache** directly and synchronously.
-   *
-   * ### Example
-   * ```javascript
-   * const meta = op.statSync("test");
-   * if (meta.isDir) {
-   *   // do something
-   * }
-   * ```
-   */
-  statSync(path: string): Metadata
-  /**
-   * Check if this operator can work correctly.
-   *
-   * We will send a `list` request to path and return any errors we met.
-   *
-   * ### Example
-   * ```javascript
-   * await op.check();
-   * ```
-   */
-  check(): Promise<void>
-  /**
-   * Check if this path exists or not.
-   *
-   * ### Example
-   * ```javascript
-   * await op.isExist("test");
-   * ```
-   */
-  isExist(path: string): Promise<boolean>
-  /**
-   * Check if this path exists or not synchronously.
-   *
-   * ### Example
-   * ```javascript
-   * op.isExistSync("test");
-   * ```
-   */
-  isExistSync(path: string): boolean
-  /**
-   * Create dir with given path.
-   *
-   * ### Example
-   * ```javascript
-   * await op.createDir("path/to/dir/");
-   * ```
-   */
-  createDir(path: string): Promise<void>
-  /**
-   * Create dir with given path synchronously.
-   *
-   * ### Example
-   * ```javascript
-   * op.createDirSync("path/to/dir/");
-   * ```
-   */
-  createDirSync(path: string): void
-  /**
-   * Read the whole path into a buffer.
-   *
-   * ### Example
-   * ```javascript
-   * const buf = await op.read("path/to/file");
-   * ```
-   */
-  read(path: string): Promise<Buffer>
-  /**
-   * Read the whole path into a buffer synchronously.
-   *
-   * ### Example
-   * ```javascript
-   * const buf = op.readSync("path/to/file");
-   * ```
-   */
-  readSync(path: string): Buffer
-  /**
-   * Write bytes into path.
-   *
-   * ### Example
-   * ```javascript
-   * await op.write("path/to/file", Buffer.from("hello world"));
-   * // or
-   * await op.write("path/to/file", "hello world");
-   * ```
-   */
-  write(path: string, content: Buffer | string): Promise<void>
-  /**
-   * Write bytes into path synchronously.
-   *
-   * ### Example
-   * ```javascript
-   * op.writeSync("path/to/file", Buffer.from("hello world"));
-   * // or
-   * op.writeSync("path/to/file", "hello world");
-   * ```
-   */
-  writeSync(path: string, content: Buffer | string): void
-  /**
-   * Copy file according to given `from` and `to` path.
-   *
-   * ### Example
-   * ```javascript
-   * await op.copy("path/to/file", "path/to/dest");
-   * ```
-   */
-  copy(from: string, to: string): Promise<void>
-  /**
-   * Copy file according to given `from` and `to` path synchronously.
-   *
-   * ### Example
-   * ```javascript
-   * op.copySync("path/to/file", "path/to/dest");
-   * ```
-   */
-  copySync(from: string, to: string): void
-  /**
-   * Rename file according to given `from` and `to` path.
-   *
-   * It's similar to `mv` command.
-   *
-   * ### Example
-   * ```javascript
-   * await op.rename("path/to/file", "path/to/dest");
-   * ```
-   */
-  rename(from: string, to: string): Promise<void>
-  /**
-   * Rename file according to given `from` and `to` path synchronously.
-   *
-   * It's similar to `mv` command.
-   *
-   * ### Example
-   * ```javascript
-   * op.renameSync("path/to/file", "path/to/dest");
-   * ```
-   */
-  renameSync(from: string, to: string): void
-  /**
-   * List dir in flat way.
-   *
-   * This function will create a new handle to list entries.
-   *
-   * An error will be returned if given path doesn’t end with /.
-   *
-   * ### Example
-   * ```javascript
-   * const lister = await op.scan("/path/to/dir/");
-   * while (true)) {
-   *   const entry = await lister.next();
-   *   if (entry === null) {
-   *     break;
-   *   }
-   *   let meta = await op.stat(entry.path);
-   *   if (meta.is_file) {
-   *     // do something
-   *   }
-   * }
-   * `````
-   */
-  scan(path: string): Promise<Lister>
-  /**
-   * List dir in flat way synchronously.
-   *
-   * This function will create a new handle to list entries.
-   *
-   * An error will be returned if given path doesn’t end with /.
-   *
-   * ### Example
-   * ```javascript
-   * const lister = op.scan_sync(/path/to/dir/");
-   * while (true)) {
-   *   const entry = lister.next();
-   *   if (entry === null) {
-   *     break;
-   *   }
-   *   let meta = op.statSync(entry.path);
-   *   if (meta.is_file) {
-   *     // do something
-   *   }
-   * }
-   * `````
-   */
-  scanSync(path: string): BlockingLister
-  /**
-   * Delete the given path.
-   *
-   * ### Notes
-   * Delete not existing error won’t return errors.
-   *
-   * ### Example
-   * ```javascript
-   * await op.delete("test");
-   * ```
-   */
-  delete(path: string): Promise<void>
-  /**
-   * Delete the given path synchronously.
-   *
-   * ### Example
-   * ```javascript
-   * op.deleteSync("test");
-   * ```
-   */
-  deleteSync(path: string): void
-  /**
-   * Remove given paths.
-   *
-   * ### Notes
-   * If underlying services support delete in batch, we will use batch delete instead.
-   *
-   * ### Examples
-   * ```javascript
-   * await op.remove(["abc", "def"]);
-   * ```
-   */
-  remove(paths: Array<string>): Promise<void>
-  /**
-   * Remove the path and all nested dirs and files recursively.
-   *
-   * ### Notes
-   * If underlying services support delete in batch, we will use batch delete instead.
-   *
-   * ### Examples
-   * ```javascript
-   * await op.removeAll("path/to/dir/");
-   * ```
-   */
-  removeAll(path: string): Promise<void>
-  /**
-   * List given path.
-   *
-   * This function will create a new handle to list entries.
-   *
-   * An error will be returned if given path doesn't end with `/`.
-   *
-   * ### Example
-   * ```javascript
-   * const lister = await op.list("path/to/dir/");
-   * while (true)) {
-   *   const entry = await lister.next();
-   *   if (entry === null) {
-   *     break;
-   *   }
-   *   let meta = await op.stat(entry.path);
-   *   if (meta.isFile) {
-   *     // do something
-   *   }
-   * }
-   * ```
-   */
-  list(path: string): Promise<Lister>
-  /**
-   * List given path synchronously.
-   *
-   * This function will create a new handle to list entries.
-   *
-   * An error will be returned if given path doesn't end with `/`.
-   *
-   * ### Example
-   * ```javascript
-   * const lister = op.listSync("path/to/dir/");
-   * while (true)) {
-   *   const entry = lister.next();
-   *   if (entry === null) {
-   *     break;
-   *   }
-   *   let meta = op.statSync(entry.path);
-   *   if (meta.isFile) {
-   *     // do something
-   *   }
-   * }
-   * ```
-   */
-  listSync(path: string): BlockingLister
-  /**
-   * Get a presigned request for read.
-   *
-   * Unit of expires is seconds.
-   *
-   * ### Example
-   *
-   * ```javascript
-   * const req = op.presignRead(path, parseInt(expires));
-   *
-   * console.log("method: ", req.method);
-   * console.log("url: ", req.url);
-   * console.log("headers: ", req.headers);
-   * ```
-   */
-  presignRead(path: string, expires: number): PresignedRequest
-  /**
-   * Get a presigned request for write.
-   *
-   * Unit of expires is seconds.
-   *
-   * ### Example
-   *
-   * ```javascript
-   * const req = op.presignWrite(path, parseInt(expires));
-   *
-   * console.log("method: ", req.method);
-   * console.log("url: ", req.url);
-   * console.log("headers: ", req.headers);
-   * ```
-   */
-  presignWrite(path: string, expires: number): PresignedRequest
-  /**
-   * Get a presigned request for stat.
-   *
-   * Unit of expires is seconds.
-   *
-   * ### Example
-   *
-   * ```javascript
-   * const req = op.presignStat(path, parseInt(expires));
-   *
-   * console.log("method: ", req.method);
-   * console.log("url: ", req.url);
-   * console.log("headers: ", req.headers);
-   * ```
-   */
-  presignStat(path: string, expires: number): PresignedRequest
-}
-export class Entry {
-  /** Return the path of this entry. */
-  path(): string
-}
-export class Metadata {
-  /** Returns true if the <op.stat> object describes a file system directory. */
-  isDirectory(): boolean
-  /** Returns true if the <op.stat> object describes a regular file. */
-  isFile(): boolean
-  /** Content-Disposition of this object */
-  get contentDisposition(): string | null
-  /** Content Length of this object */
-  get contentLength(): bigint | null
-  /** Content MD5 of this object. */
-  get contentMd5(): string | null
-  /** Content Type of this object. */
-  get contentType(): string | null
-  /** ETag of this object. */
-  get etag(): string | null
-  /** Last Modified of this object.(UTC) */
-  get lastModified(): string | null
-}
-export class Lister {
-  /**
-   * # Safety
-   *
-   * > &mut self in async napi methods should be marked as unsafe
-   *
-   * napi will make sure the function is safe, and we didn't do unsafe
-   * thing internally.
-   */
-  next(): Promise<Entry | null>
-}
-export class BlockingLister {
-  next(): Entry | null
+export * from './generated'
+
+declare module './generated' {
+  interface Operator {
+    /**
+     * Open a `Readable` stream on given path, reading only the given range if specified.
+     *
+     * ### Example
+     * ```javascript
+     * const { pipeline } = require("stream/promises");
+     *
+     * await pipeline(op.reader("path/to/file", { offset: 1024 }), fs.createWriteStream("local"));
+     * ```
+     */
+    reader(path: string, range?: ReadRange): Readable
+    /**
+     * Open a `Writable` stream on given path, content is stored after the stream finished.
+     *
+     * Content will be discarded if the stream is destroyed before finishing.
+     *
+     * ### Example
+     * ```javascript
+     * const { pipeline } = require("stream/promises");
+     *
+     * await pipeline(fs.createReadStream("local"), op.writer("path/to/file", { contentType: "text/plain" }));
+     * ```
+     */
+    writer(path: string, options?: WriteOptions): Writable
+  }
 }
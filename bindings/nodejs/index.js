@@ -19,6 +19,93 @@
 
 /// <reference types="node" />
 
-const { Operator } = require('./generated.js')
+const { Readable, Writable } = require('stream')
+const { Operator, layers } = require('./generated.js')
+
+/**
+ * Open a `Readable` stream on given path, reading only the given range if specified.
+ *
+ * The underlying reader is opened lazily when the stream starts flowing.
+ */
+Operator.prototype.reader = function (path, range) {
+  const op = this
+  let reader = null
+
+  return new Readable({
+    async read(size) {
+      try {
+        if (reader === null) {
+          reader = await op.openReader(path, range)
+        }
+        this.push(await reader.read(size))
+      } catch (err) {
+        this.destroy(err)
+      }
+    },
+  })
+}
+
+/**
+ * Content is appended to the underlying writer in chunks of at least this size (except the last one),
+ * because services like s3 require every part except the last one to be at least 5 MiB.
+ */
+const WRITE_CHUNK_SIZE = 8 * 1024 * 1024
+
+/**
+ * Open a `Writable` stream on given path, content is stored after the stream finished.
+ *
+ * Written content is buffered and appended in chunks of 8 MiB.
+ * Content will be discarded if the stream is destroyed before finishing.
+ */
+Operator.prototype.writer = function (path, options) {
+  const op = this
+  let writer = null
+  let chunks = []
+  let size = 0
+  const open = async () => {
+    if (writer === null) {
+      writer = await op.openWriter(path, options)
+    }
+    return writer
+  }
+  const flush = async () => {
+    const w = await open()
+    if (size > 0) {
+      const buf = Buffer.concat(chunks, size)
+      chunks = []
+      size = 0
+      await w.append(buf)
+    }
+    return w
+  }
+
+  return new Writable({
+    write(chunk, _encoding, callback) {
+      chunks.push(chunk)
+      size += chunk.length
+      if (size < WRITE_CHUNK_SIZE) {
+        return callback()
+      }
+      flush().then(() => callback(), callback)
+    },
+    final(callback) {
+      flush()
+        .then((w) => w.close())
+        .then(() => callback(), callback)
+    },
+    destroy(err, callback) {
+      chunks = []
+      size = 0
+      if (err === null || writer === null) {
+        return callback(err)
+      }
+      writer.abort().then(
+        () => callback(err),
+        () => callback(err),
+      )
+    },
+  })
+}
 
 module.exports.Operator = Operator
+module.exports.layers = layers
@@ -0,0 +1,95 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+
+use napi::bindgen_prelude::*;
+
+/// Options of `RetryLayer`, all delays are in milliseconds.
+#[napi(object, namespace = "layers")]
+pub struct RetryLayerOptions {
+    /// Max retry times, default to `3`.
+    pub max_times: Option<u32>,
+    /// Factor of the exponential backoff, default to `2`.
+    pub factor: Option<f64>,
+    /// Add random jitter to the delay, default to `false`.
+    pub jitter: Option<bool>,
+    /// Max delay between retries, default to `60000`.
+    pub max_delay: Option<u32>,
+    /// Min delay between retries, default to `1000`.
+    pub min_delay: Option<u32>,
+}
+
+/// Retry failed operations with exponential backoff.
+#[napi(namespace = "layers")]
+pub struct RetryLayer(pub(crate) opendal::layers::RetryLayer);
+
+#[napi(namespace = "layers")]
+impl RetryLayer {
+    #[napi(constructor)]
+    pub fn new(options: Option<RetryLayerOptions>) -> Self {
+        let mut retry = opendal::layers::RetryLayer::default();
+        let options = match options {
+            Some(options) => options,
+            None => return Self(retry),
+        };
+        if let Some(max_times) = options.max_times {
+            retry = retry.with_max_times(max_times as usize);
+        }
+        if let Some(factor) = options.factor {
+            retry = retry.with_factor(factor as f32);
+        }
+        if options.jitter.unwrap_or_default() {
+            retry = retry.with_jitter();
+        }
+        if let Some(max_delay) = options.max_delay {
+            retry = retry.with_max_delay(Duration::from_millis(max_delay as u64));
+        }
+        if let Some(min_delay) = options.min_delay {
+            retry = retry.with_min_delay(Duration::from_millis(min_delay as u64));
+        }
+        Self(retry)
+    }
+}
+
+/// Log every operation, logs are written to stderr and filtered by the
+/// `RUST_LOG` env.
+#[napi(namespace = "layers")]
+pub struct LoggingLayer(pub(crate) opendal::layers::LoggingLayer);
+
+#[napi(namespace = "layers")]
+impl LoggingLayer {
+    #[napi(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        // Ignore the error if logger has been initiated.
+        let _ = env_logger::try_init();
+        Self(opendal::layers::LoggingLayer::default())
+    }
+}
+
+/// Limit the number of concurrent operations.
+#[napi(namespace = "layers")]
+pub struct ConcurrentLimitLayer(pub(crate) opendal::layers::ConcurrentLimitLayer);
+
+#[napi(namespace = "layers")]
+impl ConcurrentLimitLayer {
+    #[napi(constructor)]
+    pub fn new(permits: u32) -> Self {
+        Self(opendal::layers::ConcurrentLimitLayer::new(permits as usize))
+    }
+}
@@ -19,67 +19,22 @@
 extern crate napi_derive;
 
 use std::collections::HashMap;
+use std::ops::Bound;
 use std::str::FromStr;
 use std::time::Duration;
 
 use futures::TryStreamExt;
 use napi::bindgen_prelude::*;
+use opendal::ops::OpWrite;
+use opendal::raw::oio::ReadExt;
+
+mod layers;
 
 fn build_operator(
     scheme: opendal::Scheme,
     map: HashMap<String, String>,
 ) -> Result<opendal::Operator> {
-    use opendal::services::*;
-
-    let op = match scheme {
-        opendal::Scheme::Azblob => opendal::Operator::from_map::<Azblob>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Azdfs => opendal::Operator::from_map::<Azdfs>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Fs => opendal::Operator::from_map::<Fs>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Gcs => opendal::Operator::from_map::<Gcs>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Ghac => opendal::Operator::from_map::<Ghac>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Http => opendal::Operator::from_map::<Http>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Ipmfs => opendal::Operator::from_map::<Ipmfs>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Memory => opendal::Operator::from_map::<Memory>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Obs => opendal::Operator::from_map::<Obs>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Oss => opendal::Operator::from_map::<Oss>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::S3 => opendal::Operator::from_map::<S3>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Webdav => opendal::Operator::from_map::<Webdav>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        opendal::Scheme::Webhdfs => opendal::Operator::from_map::<Webhdfs>(map)
-            .map_err(format_napi_error)?
-            .finish(),
-        _ => {
-            return Err(format_napi_error(opendal::Error::new(
-                opendal::ErrorKind::Unexpected,
-                "not supported scheme",
-            )))
-        }
-    };
-
-    Ok(op)
+    opendal::Operator::via_map(scheme, map).map_err(format_napi_error)
 }
 
 #[napi]
@@ -100,6 +55,36 @@ impl Operator {
         build_operator(scheme, options).map(Operator)
     }
 
+    /// Return a new operator with given layer applied.
+    ///
+    /// ### Example
+    /// ```javascript
+    /// const { Operator, layers } = require("opendal");
+    ///
+    /// const op = new Operator("memory")
+    ///   .layer(new layers.RetryLayer({ maxTimes: 3, jitter: true }))
+    ///   .layer(new layers.LoggingLayer());
+    /// ```
+    #[napi(
+        ts_args_type = "layer: layers.RetryLayer | layers.LoggingLayer | layers.ConcurrentLimitLayer"
+    )]
+    pub fn layer(
+        &self,
+        layer: Either3<
+            ClassInstance<layers::RetryLayer>,
+            ClassInstance<layers::LoggingLayer>,
+            ClassInstance<layers::ConcurrentLimitLayer>,
+        >,
+    ) -> Operator {
+        let op = self.0.clone();
+        let op = match layer {
+            Either3::A(layer) => op.layer(layer.0.clone()),
+            Either3::B(layer) => op.layer(layer.0),
+            Either3::C(layer) => op.layer(layer.0.clone()),
+        };
+        Operator(op)
+    }
+
     /// Get current path's metadata **without cache** directly.
     ///
     /// ### Notes
@@ -224,6 +209,25 @@ impl Operator {
         Ok(res.into())
     }
 
+    /// Open a native reader on given path, reading only the given range if
+    /// specified.
+    ///
+    /// Use `reader` instead if a Node.js `Readable` is wanted.
+    ///
+    /// ### Example
+    /// ```javascript
+    /// const reader = await op.openReader("path/to/file", { offset: 1024, size: 4096 });
+    /// const buf = await reader.read(1024);
+    /// ```
+    #[napi]
+    pub async fn open_reader(&self, path: String, range: Option<ReadRange>) -> Result<Reader> {
+        let r = match range {
+            Some(range) => self.0.range_reader(&path, range.to_bounds()?).await,
+            None => self.0.reader(&path).await,
+        };
+        Ok(Reader(r.map_err(format_napi_error)?))
+    }
+
     /// Write bytes into path.
     ///
     /// ### Example
@@ -231,14 +235,24 @@ impl Operator {
     /// await op.write("path/to/file", Buffer.from("hello world"));
     /// // or
     /// await op.write("path/to/file", "hello world");
+    /// // or with options
+    /// await op.write("path/to/file", "hello world", { contentType: "text/plain" });
     /// ```
     #[napi]
-    pub async fn write(&self, path: String, content: Either<Buffer, String>) -> Result<()> {
+    pub async fn write(
+        &self,
+        path: String,
+        content: Either<Buffer, String>,
+        options: Option<WriteOptions>,
+    ) -> Result<()> {
         let c = match content {
             Either::A(buf) => buf.as_ref().to_owned(),
             Either::B(s) => s.into_bytes(),
         };
-        self.0.write(&path, c).await.map_err(format_napi_error)
+        self.0
+            .write_with(&path, build_op_write(options), c)
+            .await
+            .map_err(format_napi_error)
     }
 
     /// Write bytes into path synchronously.
@@ -248,14 +262,46 @@ impl Operator {
     /// op.writeSync("path/to/file", Buffer.from("hello world"));
     /// // or
     /// op.writeSync("path/to/file", "hello world");
+    /// // or with options
+    /// op.writeSync("path/to/file", "hello world", { contentType: "text/plain" });
     /// ```
     #[napi]
-    pub fn write_sync(&self, path: String, content: Either<Buffer, String>) -> Result<()> {
+    pub fn write_sync(
+        &self,
+        path: String,
+        content: Either<Buffer, String>,
+        options: Option<WriteOptions>,
+    ) -> Result<()> {
         let c = match content {
             Either::A(buf) => buf.as_ref().to_owned(),
             Either::B(s) => s.into_bytes(),
         };
-        self.0.blocking().write(&path, c).map_err(format_napi_error)
+        self.0
+            .blocking()
+            .write_with(&path, build_op_write(options), c)
+            .map_err(format_napi_error)
+    }
+
+    /// Open a native writer on given path, the content is stored after
+    /// `close` is called.
+    ///
+    /// Use `writer` instead if a Node.js `Writable` is wanted.
+    ///
+    /// ### Example
+    /// ```javascript
+    /// const writer = await op.openWriter("path/to/file", { contentType: "text/plain" });
+    /// await writer.append(Buffer.from("hello "));
+    /// await writer.append(Buffer.from("world"));
+    /// await writer.close();
+    /// ```
+    #[napi]
+    pub async fn open_writer(&self, path: String, options: Option<WriteOptions>) -> Result<Writer> {
+        let w = self
+            .0
+            .writer_with(&path, build_op_write(options))
+            .await
+            .map_err(format_napi_error)?;
+        Ok(Writer(w))
     }
 
     /// Copy file according to given `from` and `to` path.
@@ -649,6 +695,139 @@ impl BlockingLister {
     }
 }
 
+/// Range of content to read.
+#[napi(object)]
+pub struct ReadRange {
+    /// Offset to start reading from, default to `0`.
+    pub offset: Option<i64>,
+    /// Max bytes to read, read until EOF if not set.
+    pub size: Option<i64>,
+}
+
+impl ReadRange {
+    fn to_bounds(&self) -> Result<(Bound<u64>, Bound<u64>)> {
+        let to_u64 = |name: &str, v: i64| {
+            u64::try_from(v).map_err(|_| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!("range {name} must be non-negative, got {v}"),
+                )
+            })
+        };
+        let offset = to_u64("offset", self.offset.unwrap_or_default())?;
+        let end = match self.size {
+            Some(size) => Bound::Excluded(offset.saturating_add(to_u64("size", size)?)),
+            None => Bound::Unbounded,
+        };
+        Ok((Bound::Included(offset), end))
+    }
+}
+
+/// Extra options for writing.
+#[napi(object)]
+pub struct WriteOptions {
+    /// Content-Type of the written object.
+    pub content_type: Option<String>,
+    /// Content-Disposition of the written object.
+    pub content_disposition: Option<String>,
+    /// Cache-Control of the written object.
+    pub cache_control: Option<String>,
+}
+
+fn build_op_write(options: Option<WriteOptions>) -> OpWrite {
+    let mut op = OpWrite::new();
+    let options = match options {
+        Some(options) => options,
+        None => return op,
+    };
+    if let Some(v) = &options.content_type {
+        op = op.with_content_type(v);
+    }
+    if let Some(v) = &options.content_disposition {
+        op = op.with_content_disposition(v);
+    }
+    if let Some(v) = &options.cache_control {
+        op = op.with_cache_control(v);
+    }
+    op
+}
+
+/// Reader is the native handle behind `Operator.reader`.
+#[napi]
+pub struct Reader(opendal::Reader);
+
+#[napi]
+impl Reader {
+    /// Read at most `size` bytes, returns `null` if reaching EOF.
+    ///
+    /// # Safety
+    ///
+    /// > &mut self in async napi methods should be marked as unsafe
+    ///
+    /// napi will make sure the function is safe, and we didn't do unsafe
+    /// thing internally.
+    #[napi]
+    pub async unsafe fn read(&mut self, size: u32) -> Result<Option<Buffer>> {
+        let mut buf = vec![0; size as usize];
+        let n = self.0.read(&mut buf).await.map_err(format_napi_error)?;
+        if n == 0 && size != 0 {
+            return Ok(None);
+        }
+        buf.truncate(n);
+        Ok(Some(buf.into()))
+    }
+}
+
+/// Writer is the native handle behind `Operator.writer`.
+#[napi]
+pub struct Writer(opendal::Writer);
+
+#[napi]
+impl Writer {
+    /// Append bytes into this writer.
+    ///
+    /// # Safety
+    ///
+    /// > &mut self in async napi methods should be marked as unsafe
+    ///
+    /// napi will make sure the function is safe, and we didn't do unsafe
+    /// thing internally.
+    #[napi]
+    pub async unsafe fn append(&mut self, content: Either<Buffer, String>) -> Result<()> {
+        let c = match content {
+            Either::A(buf) => buf.as_ref().to_owned(),
+            Either::B(s) => s.into_bytes(),
+        };
+        self.0.append(c).await.map_err(format_napi_error)
+    }
+
+    /// Abort this writer and discard all appended content.
+    ///
+    /// # Safety
+    ///
+    /// > &mut self in async napi methods should be marked as unsafe
+    ///
+    /// napi will make sure the function is safe, and we didn't do unsafe
+    /// thing internally.
+    #[napi]
+    pub async unsafe fn abort(&mut self) -> Result<()> {
+        self.0.abort().await.map_err(format_napi_error)
+    }
+
+    /// Close this writer and make sure all content has been stored.
+    ///
+    /// # Safety
+    ///
+    /// > &mut self in async napi methods should be marked as unsafe
+    ///
+    /// napi will make sure the function is safe, and we didn't do unsafe
+    /// thing internally.
+    #[napi]
+    pub async unsafe fn close(&mut self) -> Result<()> {
+        self.0.close().await.map_err(format_napi_error)
+    }
+}
+
 #[napi(object)]
 pub struct PresignedRequest {
     /// HTTP method of this request.
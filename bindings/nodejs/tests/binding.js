@@ -18,7 +18,9 @@
  */
 
 const assert = require('assert')
-const { Operator } = require('../index.js')
+const { Readable } = require('stream')
+const { pipeline } = require('stream/promises')
+const { Operator, layers } = require('../index.js')
 const { Given, When, Then } = require('@cucumber/cucumber')
 
 Given('A new OpenDAL Blocking Operator', function () {
@@ -74,3 +76,32 @@ Then('The async file {string} must have content {string}', async function (path,
   let bs = await this.op.read(path)
   assert(bs.toString() == content)
 })
+
+Given('A new OpenDAL Async Operator with layers', function () {
+  this.op = new Operator('memory')
+    .layer(new layers.RetryLayer({ maxTimes: 3 }))
+    .layer(new layers.ConcurrentLimitLayer(8))
+})
+
+When('Stream write path {string} with content {string}', async function (path, content) {
+  await pipeline(Readable.from([content.slice(0, 5), content.slice(5)]), this.op.writer(path))
+})
+
+Then('The stream file {string} must have content {string}', async function (path, content) {
+  let chunks = []
+  for await (const chunk of this.op.reader(path)) {
+    chunks.push(chunk)
+  }
+  assert(Buffer.concat(chunks).toString() == content)
+})
+
+Then(
+  'The stream file {string} in range {int} to {int} must have content {string}',
+  async function (path, start, end, content) {
+    let chunks = []
+    for await (const chunk of this.op.reader(path, { offset: start, size: end - start })) {
+      chunks.push(chunk)
+    }
+    assert(Buffer.concat(chunks).toString() == content)
+  },
+)
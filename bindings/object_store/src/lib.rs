@@ -15,38 +15,126 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::io;
 use std::ops::Range;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::task::Context;
 use std::task::Poll;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::ready;
 use futures::stream::BoxStream;
+use futures::FutureExt;
 use futures::Stream;
 use futures::StreamExt;
+use futures::TryStreamExt;
 use object_store::path::Path;
+use object_store::GetOptions;
+use object_store::GetRange;
 use object_store::GetResult;
+use object_store::GetResultPayload;
 use object_store::ListResult;
 use object_store::MultipartId;
 use object_store::ObjectMeta;
 use object_store::ObjectStore;
+use object_store::PutMode;
+use object_store::PutOptions;
+use object_store::PutResult;
 use object_store::Result;
+use opendal::ops::OpRead;
+use opendal::raw::oio;
 use opendal::Metadata;
 use opendal::Metakey;
 use opendal::Operator;
 use opendal::Reader;
+use opendal::Writer;
 use tokio::io::AsyncWrite;
+use uuid::Uuid;
 
-#[derive(Debug)]
 pub struct OpendalStore {
     inner: Operator,
+    /// Writers of uploads that are neither completed nor aborted, so that
+    /// `abort_multipart` could abort them.
+    uploads: Arc<Mutex<HashMap<MultipartId, Upload>>>,
 }
 
+/// Upload holds the writer shared by [`OpendalWriter`] and the store, the
+/// writer is taken once the upload is completed or aborted.
+type Upload = Arc<tokio::sync::Mutex<Option<Writer>>>;
+
 impl OpendalStore {
     /// Create OpendalStore by given Operator.
     pub fn new(op: Operator) -> Self {
-        Self { inner: op }
+        Self {
+            inner: op,
+            uploads: Arc::default(),
+        }
+    }
+
+    /// Copy by streaming the content from source to target if the
+    /// underlying service doesn't support copy natively.
+    async fn copy_with_fallback(&self, from: &str, to: &str) -> opendal::Result<()> {
+        match self.inner.copy(from, to).await {
+            Err(err) if err.kind() == opendal::ErrorKind::Unsupported => {}
+            res => return res,
+        }
+
+        let mut r = self.inner.reader(from).await?;
+        let mut w = self.inner.writer(to).await?;
+        let res = async {
+            let mut buf = oio::AppendBuffer::new();
+            while let Some(bs) = oio::ReadExt::next(&mut r).await {
+                if let Some(bs) = buf.push(bs?) {
+                    w.append(bs).await?;
+                }
+            }
+            if let Some(bs) = buf.finish() {
+                w.append(bs).await?;
+            }
+            w.close().await
+        }
+        .await;
+
+        if res.is_err() {
+            // The partial object will be left on target if not aborted.
+            let _ = w.abort().await;
+        }
+        res
+    }
+
+    /// Return an error if the object at path exists.
+    ///
+    /// OpenDAL doesn't support conditional write, so the check and the
+    /// following write are not atomic.
+    async fn ensure_not_exist(&self, path: &str) -> Result<()> {
+        let exist = self
+            .inner
+            .is_exist(path)
+            .await
+            .map_err(|err| format_object_store_error(err, path))?;
+        if exist {
+            return Err(object_store::Error::AlreadyExists {
+                path: path.to_string(),
+                source: Box::new(opendal::Error::new(
+                    opendal::ErrorKind::AlreadyExists,
+                    "target already exists",
+                )),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for OpendalStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpendalStore")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
     }
 }
 
@@ -58,43 +146,140 @@ impl std::fmt::Display for OpendalStore {
 
 #[async_trait]
 impl ObjectStore for OpendalStore {
-    async fn put(&self, location: &Path, bytes: Bytes) -> Result<()> {
-        Ok(self
-            .inner
+    /// # Notes
+    ///
+    /// - [`PutMode::Create`] is not atomic, see [`ObjectStore::copy_if_not_exists`].
+    /// - [`PutMode::Update`] is not supported.
+    /// - Tags are ignored.
+    async fn put_opts(&self, location: &Path, bytes: Bytes, opts: PutOptions) -> Result<PutResult> {
+        match opts.mode {
+            PutMode::Overwrite => {}
+            PutMode::Create => self.ensure_not_exist(location.as_ref()).await?,
+            PutMode::Update(_) => return Err(object_store::Error::NotImplemented),
+        }
+
+        self.inner
             .write(location.as_ref(), bytes)
             .await
-            .map_err(|err| format_object_store_error(err, location.as_ref()))?)
+            .map_err(|err| format_object_store_error(err, location.as_ref()))?;
+
+        // OpenDAL doesn't return the etag of written object.
+        Ok(PutResult {
+            e_tag: None,
+            version: None,
+        })
     }
 
     async fn put_multipart(
         &self,
-        _location: &Path,
+        location: &Path,
     ) -> Result<(MultipartId, Box<dyn AsyncWrite + Unpin + Send>)> {
-        Err(object_store::Error::NotSupported {
-            source: Box::new(opendal::Error::new(
-                opendal::ErrorKind::Unsupported,
-                "put_multipart is not implemented so far",
-            )),
-        })
+        let w = self
+            .inner
+            .writer(location.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))?;
+
+        let id = Uuid::new_v4().to_string();
+        let upload = Upload::new(tokio::sync::Mutex::new(Some(w)));
+        self.uploads
+            .lock()
+            .expect("lock must succeed")
+            .insert(id.clone(), upload.clone());
+        let w = OpendalWriter {
+            id: id.clone(),
+            state: WriterState::Idle,
+            buf: oio::AppendBuffer::new(),
+            upload,
+            uploads: self.uploads.clone(),
+        };
+
+        Ok((id, Box::new(w)))
     }
 
-    async fn abort_multipart(&self, _location: &Path, _multipart_id: &MultipartId) -> Result<()> {
-        Err(object_store::Error::NotSupported {
-            source: Box::new(opendal::Error::new(
-                opendal::ErrorKind::Unsupported,
-                "abort_multipart is not implemented so far",
-            )),
-        })
+    async fn abort_multipart(&self, location: &Path, multipart_id: &MultipartId) -> Result<()> {
+        let upload = self
+            .uploads
+            .lock()
+            .expect("lock must succeed")
+            .remove(multipart_id);
+
+        // The upload has been completed or aborted, nothing to clean.
+        let upload = match upload {
+            Some(upload) => upload,
+            None => return Ok(()),
+        };
+        // Wait for the in-flight write of a live writer, which will fail
+        // afterwards since its writer has been taken.
+        let w = upload.lock().await.take();
+        let mut w = match w {
+            Some(w) => w,
+            None => return Ok(()),
+        };
+
+        // Content of dropped writer will never be committed, so it's fine
+        // to drop it directly if the service can't abort.
+        match w.abort().await {
+            Err(err) if err.kind() != opendal::ErrorKind::Unsupported => {
+                Err(format_object_store_error(err, location.as_ref()))
+            }
+            _ => Ok(()),
+        }
     }
 
-    async fn get(&self, location: &Path) -> Result<GetResult> {
+    /// # Notes
+    ///
+    /// Conditions are checked against the metadata of the object, and
+    /// `if_match` and `if_none_match` are sent to services that support
+    /// them too. Versions are not supported.
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        if options.version.is_some() {
+            return Err(object_store::Error::NotSupported {
+                source: Box::new(opendal::Error::new(
+                    opendal::ErrorKind::Unsupported,
+                    "object versions are not supported",
+                )),
+            });
+        }
+
+        let meta = self
+            .inner
+            .stat(location.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, location.as_ref()))?;
+        let meta = format_object_meta(location.clone(), &meta);
+        check_preconditions(&options, &meta)?;
+
+        let range = match &options.range {
+            Some(range) => format_get_range(range, meta.size, location.as_ref())?,
+            None => 0..meta.size,
+        };
+        if options.head || range.is_empty() {
+            return Ok(GetResult {
+                payload: GetResultPayload::Stream(futures::stream::empty().boxed()),
+                meta,
+                range,
+            });
+        }
+
+        let mut args = OpRead::new().with_range((range.start as u64..range.end as u64).into());
+        if let Some(v) = &options.if_match {
+            args = args.with_if_match(v);
+        }
+        if let Some(v) = &options.if_none_match {
+            args = args.with_if_none_match(v);
+        }
         let r = self
             .inner
-            .reader(location.as_ref())
+            .reader_with(location.as_ref(), args)
             .await
             .map_err(|err| format_object_store_error(err, location.as_ref()))?;
 
-        Ok(GetResult::Stream(Box::pin(OpendalReader { inner: r })))
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(OpendalReader { inner: r }.boxed()),
+            meta,
+            range,
+        })
     }
 
     async fn get_range(&self, location: &Path, range: Range<usize>) -> Result<Bytes> {
@@ -104,6 +289,21 @@ impl ObjectStore for OpendalStore {
             .await
             .map_err(|err| format_object_store_error(err, location.as_ref()))?;
 
+        // Services will return less data instead of error if the range
+        // is out of the content.
+        if bs.len() != range.len() {
+            return Err(object_store::Error::Generic {
+                store: "OpenDAL",
+                source: Box::new(opendal::Error::new(
+                    opendal::ErrorKind::Unexpected,
+                    &format!(
+                        "range {range:?} is out of the content, only {} bytes returned",
+                        bs.len()
+                    ),
+                )),
+            });
+        }
+
         Ok(Bytes::from(bs))
     }
 
@@ -114,11 +314,7 @@ impl ObjectStore for OpendalStore {
             .await
             .map_err(|err| format_object_store_error(err, location.as_ref()))?;
 
-        Ok(ObjectMeta {
-            location: location.clone(),
-            last_modified: meta.last_modified().unwrap_or_default(),
-            size: meta.content_length() as usize,
-        })
+        Ok(format_object_meta(location.clone(), &meta))
     }
 
     async fn delete(&self, location: &Path) -> Result<()> {
@@ -130,28 +326,43 @@ impl ObjectStore for OpendalStore {
         Ok(())
     }
 
-    async fn list(&self, prefix: Option<&Path>) -> Result<BoxStream<'_, Result<ObjectMeta>>> {
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
         // object_store `Path` always removes trailing slash
         // need to add it back
         let path = prefix.map_or("".into(), |x| format!("{}/", x));
-        let stream = self
-            .inner
-            .scan(&path)
-            .await
-            .map_err(|err| format_object_store_error(err, &path))?;
-
-        let stream = stream.then(|res| async {
-            let entry = res.map_err(|err| format_object_store_error(err, ""))?;
-            let meta = self
+        let stream = futures::stream::once(async move {
+            match self.inner.scan(&path).await {
+                Ok(lister) => Ok(lister.map_err(|err| format_object_store_error(err, ""))),
+                Err(err) => Err(format_object_store_error(err, &path)),
+            }
+        })
+        .try_flatten();
+
+        let stream = stream.filter_map(|res| async {
+            let entry = match res {
+                Ok(entry) => entry,
+                Err(err) => return Some(Err(err)),
+            };
+            let meta = match self
                 .inner
-                .metadata(&entry, Metakey::ContentLength | Metakey::LastModified)
+                .metadata(
+                    &entry,
+                    Metakey::Mode | Metakey::ContentLength | Metakey::LastModified | Metakey::Etag,
+                )
                 .await
-                .map_err(|err| format_object_store_error(err, entry.path()))?;
+            {
+                Ok(meta) => meta,
+                Err(err) => return Some(Err(format_object_store_error(err, entry.path()))),
+            };
 
-            Ok(format_object_meta(entry.path(), &meta))
+            // object_store only lists objects.
+            if meta.is_dir() {
+                return None;
+            }
+            Some(format_object_path(entry.path()).map(|path| format_object_meta(path, &meta)))
         });
 
-        Ok(stream.boxed())
+        stream.boxed()
     }
 
     async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
@@ -171,49 +382,58 @@ impl ObjectStore for OpendalStore {
                 .inner
                 .metadata(
                     &entry,
-                    Metakey::Mode | Metakey::ContentLength | Metakey::LastModified,
+                    Metakey::Mode | Metakey::ContentLength | Metakey::LastModified | Metakey::Etag,
                 )
                 .await
                 .map_err(|err| format_object_store_error(err, entry.path()))?;
 
             if meta.is_dir() {
-                common_prefixes.push(entry.path().into());
+                common_prefixes.push(format_object_path(entry.path())?);
             } else {
-                objects.push(format_object_meta(entry.path(), &meta));
+                let path = format_object_path(entry.path())?;
+                objects.push(format_object_meta(path, &meta));
             }
         }
 
+        // Object stores return results in lexicographical order, while
+        // services like fs don't.
+        common_prefixes.sort_unstable();
+        objects.sort_unstable_by(|a, b| a.location.cmp(&b.location));
+
         Ok(ListResult {
             common_prefixes,
             objects,
         })
     }
 
-    async fn copy(&self, _from: &Path, _to: &Path) -> Result<()> {
-        Err(object_store::Error::NotSupported {
-            source: Box::new(opendal::Error::new(
-                opendal::ErrorKind::Unsupported,
-                "copy is not implemented so far",
-            )),
-        })
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        self.copy_with_fallback(from.as_ref(), to.as_ref())
+            .await
+            .map_err(|err| format_object_store_error(err, from.as_ref()))
     }
 
-    async fn rename(&self, _from: &Path, _to: &Path) -> Result<()> {
-        Err(object_store::Error::NotSupported {
-            source: Box::new(opendal::Error::new(
-                opendal::ErrorKind::Unsupported,
-                "rename is not implemented so far",
-            )),
-        })
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        match self.inner.rename(from.as_ref(), to.as_ref()).await {
+            Err(err) if err.kind() == opendal::ErrorKind::Unsupported => {
+                self.copy_with_fallback(from.as_ref(), to.as_ref())
+                    .await
+                    .map_err(|err| format_object_store_error(err, from.as_ref()))?;
+                self.inner
+                    .delete(from.as_ref())
+                    .await
+                    .map_err(|err| format_object_store_error(err, from.as_ref()))
+            }
+            res => res.map_err(|err| format_object_store_error(err, from.as_ref())),
+        }
     }
 
-    async fn copy_if_not_exists(&self, _from: &Path, _to: &Path) -> Result<()> {
-        Err(object_store::Error::NotSupported {
-            source: Box::new(opendal::Error::new(
-                opendal::ErrorKind::Unsupported,
-                "copy_if_not_exists is not implemented so far",
-            )),
-        })
+    /// # Notes
+    ///
+    /// OpenDAL doesn't support conditional copy, so the check of target
+    /// and the copy are not atomic.
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.ensure_not_exist(to.as_ref()).await?;
+        self.copy(from, to).await
     }
 }
 
@@ -231,6 +451,10 @@ fn format_object_store_error(err: opendal::Error, path: &str) -> object_store::E
             path: path.to_string(),
             source: Box::new(err),
         },
+        ErrorKind::PreconditionFailed => object_store::Error::Precondition {
+            path: path.to_string(),
+            source: Box::new(err),
+        },
         kind => object_store::Error::Generic {
             store: kind.into_static(),
             source: Box::new(err),
@@ -238,14 +462,90 @@ fn format_object_store_error(err: opendal::Error, path: &str) -> object_store::E
     }
 }
 
-fn format_object_meta(path: &str, meta: &Metadata) -> ObjectMeta {
+/// Paths returned by OpenDAL are already encoded, so we parse them
+/// instead of building from them which will encode them again.
+fn format_object_path(path: &str) -> Result<Path> {
+    Path::parse(path).map_err(|err| object_store::Error::InvalidPath { source: err })
+}
+
+fn format_object_meta(location: Path, meta: &Metadata) -> ObjectMeta {
     ObjectMeta {
-        location: path.into(),
+        location,
         last_modified: meta.last_modified().unwrap_or_default(),
         size: meta.content_length() as usize,
+        e_tag: meta.etag().map(String::from),
+        version: None,
+    }
+}
+
+/// Convert the range into `[start, end)` against the object size, which
+/// follows the rules of [`GetRange`].
+fn format_get_range(range: &GetRange, size: usize, path: &str) -> Result<Range<usize>> {
+    let invalid = |msg: String| object_store::Error::Generic {
+        store: "OpenDAL",
+        source: Box::new(
+            opendal::Error::new(opendal::ErrorKind::Unexpected, &msg).with_context("path", path),
+        ),
+    };
+
+    match range {
+        GetRange::Bounded(r) if r.end <= r.start => {
+            Err(invalid(format!("range {r:?} is inconsistent")))
+        }
+        GetRange::Bounded(r) if r.start >= size => Err(invalid(format!(
+            "range {r:?} starts after the object of {size} bytes"
+        ))),
+        GetRange::Bounded(r) => Ok(r.start..r.end.min(size)),
+        GetRange::Offset(o) if *o >= size => Err(invalid(format!(
+            "offset {o} is after the object of {size} bytes"
+        ))),
+        GetRange::Offset(o) => Ok(*o..size),
+        GetRange::Suffix(n) => Ok(size.saturating_sub(*n)..size),
     }
 }
 
+/// Check the conditions of options against the object, which follows
+/// [rfc9110](https://datatracker.ietf.org/doc/html/rfc9110#section-13.2.2)
+/// the same as object_store.
+fn check_preconditions(options: &GetOptions, meta: &ObjectMeta) -> Result<()> {
+    // `*` never matches an object without etag.
+    let etag = meta.e_tag.as_deref().unwrap_or("*");
+    let path = meta.location.to_string();
+
+    if let Some(m) = &options.if_match {
+        if m != "*" && m.split(',').map(str::trim).all(|v| v != etag) {
+            return Err(object_store::Error::Precondition {
+                path,
+                source: format!("{etag} does not match {m}").into(),
+            });
+        }
+    } else if let Some(date) = options.if_unmodified_since {
+        if meta.last_modified > date {
+            return Err(object_store::Error::Precondition {
+                path,
+                source: format!("{} is after {date}", meta.last_modified).into(),
+            });
+        }
+    }
+
+    if let Some(m) = &options.if_none_match {
+        if m == "*" || m.split(',').map(str::trim).any(|v| v == etag) {
+            return Err(object_store::Error::NotModified {
+                path,
+                source: format!("{etag} matches {m}").into(),
+            });
+        }
+    } else if let Some(date) = options.if_modified_since {
+        if meta.last_modified <= date {
+            return Err(object_store::Error::NotModified {
+                path,
+                source: format!("{} is not after {date}", meta.last_modified).into(),
+            });
+        }
+    }
+    Ok(())
+}
+
 struct OpendalReader {
     inner: Reader,
 }
@@ -265,6 +565,169 @@ impl Stream for OpendalReader {
     }
 }
 
+enum WriterState {
+    Idle,
+    Write(BoxFuture<'static, opendal::Result<()>>),
+    Close(BoxFuture<'static, opendal::Result<()>>),
+    Closed,
+}
+
+/// OpendalWriter adapts [`Writer`] into tokio's [`AsyncWrite`].
+///
+/// Written bytes are buffered and appended in chunks of 8 MiB, because
+/// services like s3 require every part except the last one to be at least
+/// 5 MiB. Writer dropped before shutdown will be aborted.
+struct OpendalWriter {
+    id: MultipartId,
+    state: WriterState,
+    buf: oio::AppendBuffer,
+    upload: Upload,
+    uploads: Arc<Mutex<HashMap<MultipartId, Upload>>>,
+}
+
+impl OpendalWriter {
+    fn append(&self, bs: Bytes) -> BoxFuture<'static, opendal::Result<()>> {
+        let upload = self.upload.clone();
+        Box::pin(async move {
+            match upload.lock().await.as_mut() {
+                Some(w) => w.append(bs).await,
+                None => Err(aborted_error()),
+            }
+        })
+    }
+
+    fn close(&self) -> BoxFuture<'static, opendal::Result<()>> {
+        let upload = self.upload.clone();
+        Box::pin(async move {
+            let mut w = upload.lock().await;
+            w.as_mut().ok_or_else(aborted_error)?.close().await?;
+            w.take();
+            Ok(())
+        })
+    }
+}
+
+impl AsyncWrite for OpendalWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                WriterState::Idle => {
+                    // The chunk is appended by following calls, so that we
+                    // don't need to keep `buf` until the append finished.
+                    if let Some(chunk) = this.buf.push(Bytes::copy_from_slice(buf)) {
+                        this.state = WriterState::Write(this.append(chunk));
+                    }
+                    return Poll::Ready(Ok(buf.len()));
+                }
+                WriterState::Write(fut) => {
+                    let res = ready!(fut.poll_unpin(cx));
+                    this.state = WriterState::Idle;
+                    if let Err(err) = res {
+                        return Poll::Ready(Err(format_io_error(err)));
+                    }
+                }
+                WriterState::Close(_) | WriterState::Closed => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "writer has been shutdown",
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Flush waits for the pending chunk to be appended, bytes that are not
+    /// enough for a chunk are kept in buffer until shutdown.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let WriterState::Write(fut) = &mut this.state {
+            let res = ready!(fut.poll_unpin(cx));
+            this.state = WriterState::Idle;
+            return Poll::Ready(res.map_err(format_io_error));
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                WriterState::Idle => match this.buf.finish() {
+                    Some(chunk) => this.state = WriterState::Write(this.append(chunk)),
+                    None => this.state = WriterState::Close(this.close()),
+                },
+                WriterState::Write(fut) => {
+                    let res = ready!(fut.poll_unpin(cx));
+                    this.state = WriterState::Idle;
+                    if let Err(err) = res {
+                        return Poll::Ready(Err(format_io_error(err)));
+                    }
+                }
+                WriterState::Close(fut) => {
+                    let res = ready!(fut.poll_unpin(cx));
+                    this.state = match res {
+                        Ok(()) => {
+                            this.uploads
+                                .lock()
+                                .expect("lock must succeed")
+                                .remove(&this.id);
+                            WriterState::Closed
+                        }
+                        Err(_) => WriterState::Idle,
+                    };
+                    return Poll::Ready(res.map_err(format_io_error));
+                }
+                WriterState::Closed => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
+
+impl Drop for OpendalWriter {
+    fn drop(&mut self) {
+        // Drop the in-flight future first to release the writer.
+        if let WriterState::Closed = std::mem::replace(&mut self.state, WriterState::Closed) {
+            return;
+        }
+
+        let upload = self.upload.clone();
+        let uploads = self.uploads.clone();
+        let id = self.id.clone();
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    let w = upload.lock().await.take();
+                    if let Some(mut w) = w {
+                        let _ = w.abort().await;
+                    }
+                    uploads.lock().expect("lock must succeed").remove(&id);
+                });
+            }
+            // Without a runtime, the writer is dropped without closing so
+            // that the content will never be committed.
+            Err(_) => {
+                if let Ok(mut w) = upload.try_lock() {
+                    w.take();
+                }
+                uploads.lock().expect("lock must succeed").remove(&id);
+            }
+        }
+    }
+}
+
+fn aborted_error() -> opendal::Error {
+    opendal::Error::new(opendal::ErrorKind::Unexpected, "upload has been aborted")
+}
+
+fn format_io_error(err: opendal::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -272,6 +735,7 @@ mod tests {
     use object_store::path::Path;
     use object_store::ObjectStore;
     use opendal::services;
+    use tokio::io::AsyncWriteExt;
 
     use super::*;
 
@@ -310,12 +774,7 @@ mod tests {
     async fn test_list() {
         let object_store = create_test_object_store().await;
         let path: Path = "data/".try_into().unwrap();
-        let results = object_store
-            .list(Some(&path))
-            .await
-            .unwrap()
-            .collect::<Vec<_>>()
-            .await;
+        let results = object_store.list(Some(&path)).collect::<Vec<_>>().await;
         assert_eq!(results.len(), 2);
         let mut locations = results
             .iter()
@@ -325,6 +784,78 @@ mod tests {
         assert_eq!(locations, &["data/nested/test.txt", "data/test.txt"]);
     }
 
+    #[tokio::test]
+    async fn test_get_opts() {
+        let object_store = create_test_object_store().await;
+        let path = Path::from("data/test.txt");
+
+        let opts = GetOptions {
+            range: Some(GetRange::Suffix(6)),
+            ..Default::default()
+        };
+        let res = object_store.get_opts(&path, opts).await.unwrap();
+        assert_eq!(res.range, 7..13);
+        assert_eq!(res.bytes().await.unwrap(), Bytes::from_static(b"world!"));
+
+        let opts = GetOptions {
+            head: true,
+            ..Default::default()
+        };
+        let res = object_store.get_opts(&path, opts).await.unwrap();
+        let meta = res.meta.clone();
+        assert_eq!(meta.size, 13);
+        assert!(res.bytes().await.unwrap().is_empty());
+
+        let opts = GetOptions {
+            if_match: Some("not-the-etag".to_string()),
+            ..Default::default()
+        };
+        let err = object_store.get_opts(&path, opts).await.unwrap_err();
+        assert!(
+            matches!(err, object_store::Error::Precondition { .. }),
+            "{err}"
+        );
+
+        let opts = GetOptions {
+            if_none_match: Some("*".to_string()),
+            ..Default::default()
+        };
+        let err = object_store.get_opts(&path, opts).await.unwrap_err();
+        assert!(
+            matches!(err, object_store::Error::NotModified { .. }),
+            "{err}"
+        );
+
+        let opts = GetOptions {
+            if_modified_since: Some(meta.last_modified),
+            ..Default::default()
+        };
+        let err = object_store.get_opts(&path, opts).await.unwrap_err();
+        assert!(
+            matches!(err, object_store::Error::NotModified { .. }),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_opts_create() {
+        let object_store = create_test_object_store().await;
+        let path = Path::from("data/test.txt");
+
+        let opts = PutOptions {
+            mode: PutMode::Create,
+            ..Default::default()
+        };
+        let err = object_store
+            .put_opts(&path, Bytes::from_static(b"new"), opts)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, object_store::Error::AlreadyExists { .. }),
+            "{err}"
+        );
+    }
+
     #[tokio::test]
     async fn test_list_with_delimiter() {
         let object_store = create_test_object_store().await;
@@ -335,4 +866,46 @@ mod tests {
         assert_eq!(result.objects[0].location.as_ref(), "data/test.txt");
         assert_eq!(result.common_prefixes[0].as_ref(), "data/nested");
     }
+
+    #[tokio::test]
+    async fn test_abort_live_multipart() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let store = OpendalStore::new(op);
+        let path = Path::from("data/upload.txt");
+
+        let (id, mut w) = store.put_multipart(&path).await.unwrap();
+        w.write_all(b"hello").await.unwrap();
+        store.abort_multipart(&path, &id).await.unwrap();
+        assert!(store.uploads.lock().unwrap().is_empty());
+
+        assert!(w.shutdown().await.is_err());
+        assert!(store.head(&path).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_drop_multipart() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let store = OpendalStore::new(op);
+        let path = Path::from("data/upload.txt");
+
+        let (_, mut w) = store.put_multipart(&path).await.unwrap();
+        w.write_all(b"hello").await.unwrap();
+        drop(w);
+
+        // Dropped writers are aborted in background.
+        for _ in 0..100 {
+            if store.uploads.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(store.uploads.lock().unwrap().is_empty());
+        assert!(store.head(&path).await.is_err());
+
+        let (_, mut w) = store.put_multipart(&path).await.unwrap();
+        w.write_all(b"hello").await.unwrap();
+        w.shutdown().await.unwrap();
+        assert!(store.uploads.lock().unwrap().is_empty());
+        assert_eq!(store.head(&path).await.unwrap().size, 5);
+    }
 }
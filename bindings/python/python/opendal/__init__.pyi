@@ -15,36 +15,91 @@
 # specific language governing permissions and limitations
 # under the License.
 
-from typing import AsyncIterable, Iterable, Optional
+from datetime import datetime
+from typing import AsyncIterable, Dict, Iterable, Optional
 
 class Error(Exception): ...
 
 class Operator:
     def __init__(self, scheme: str, **kwargs): ...
-    def read(self, path: str) -> bytes: ...
+    def read(
+        self, path: str, *, offset: Optional[int] = None, size: Optional[int] = None
+    ) -> bytes: ...
     def open_reader(self, path: str) -> Reader: ...
-    def write(self, path: str, bs: bytes): ...
+    def write(
+        self,
+        path: str,
+        bs: bytes,
+        *,
+        content_type: Optional[str] = None,
+        content_disposition: Optional[str] = None,
+        cache_control: Optional[str] = None,
+    ): ...
+    def open_writer(
+        self,
+        path: str,
+        *,
+        content_type: Optional[str] = None,
+        content_disposition: Optional[str] = None,
+        cache_control: Optional[str] = None,
+    ) -> Writer: ...
     def stat(self, path: str) -> Metadata: ...
+    def copy(self, source: str, target: str): ...
+    def rename(self, source: str, target: str): ...
+    def remove_all(self, path: str): ...
     def create_dir(self, path: str): ...
     def delete(self, path: str): ...
     def list(self, path: str) -> Iterable[Entry]: ...
     def scan(self, path: str) -> Iterable[Entry]: ...
+    def presign_stat(self, path: str, expire: int) -> PresignedRequest: ...
+    def presign_read(self, path: str, expire: int) -> PresignedRequest: ...
+    def presign_write(self, path: str, expire: int) -> PresignedRequest: ...
 
 class AsyncOperator:
     def __init__(self, scheme: str, **kwargs): ...
-    async def read(self, path: str) -> bytes: ...
+    async def read(
+        self, path: str, *, offset: Optional[int] = None, size: Optional[int] = None
+    ) -> bytes: ...
     def open_reader(self, path: str) -> AsyncReader: ...
-    async def write(self, path: str, bs: bytes): ...
+    async def write(
+        self,
+        path: str,
+        bs: bytes,
+        *,
+        content_type: Optional[str] = None,
+        content_disposition: Optional[str] = None,
+        cache_control: Optional[str] = None,
+    ): ...
+    def open_writer(
+        self,
+        path: str,
+        *,
+        content_type: Optional[str] = None,
+        content_disposition: Optional[str] = None,
+        cache_control: Optional[str] = None,
+    ) -> AsyncWriter: ...
     async def stat(self, path: str) -> Metadata: ...
+    async def copy(self, source: str, target: str): ...
+    async def rename(self, source: str, target: str): ...
+    async def remove_all(self, path: str): ...
     async def create_dir(self, path: str): ...
     async def delete(self, path: str): ...
     async def list(self, path: str) -> AsyncIterable[Entry]: ...
     async def scan(self, path: str) -> AsyncIterable[Entry]: ...
+    async def presign_stat(self, path: str, expire: int) -> PresignedRequest: ...
+    async def presign_read(self, path: str, expire: int) -> PresignedRequest: ...
+    async def presign_write(self, path: str, expire: int) -> PresignedRequest: ...
 
 class Reader:
     def read(self, size: Optional[int] = None) -> bytes: ...
     def seek(self, offset: int, whence: int = 0) -> int: ...
     def tell(self) -> int: ...
+    def readable(self) -> bool: ...
+    def seekable(self) -> bool: ...
+    def writable(self) -> bool: ...
+    def close(self) -> None: ...
+    @property
+    def closed(self) -> bool: ...
     def __enter__(self) -> Reader: ...
     def __exit__(self, exc_type, exc_value, traceback) -> None: ...
 
@@ -55,6 +110,24 @@ class AsyncReader:
     def __aenter__(self) -> AsyncReader: ...
     def __aexit__(self, exc_type, exc_value, traceback) -> None: ...
 
+class Writer:
+    def write(self, bs: bytes) -> int: ...
+    def readable(self) -> bool: ...
+    def seekable(self) -> bool: ...
+    def writable(self) -> bool: ...
+    def close(self) -> None: ...
+    def abort(self) -> None: ...
+    @property
+    def closed(self) -> bool: ...
+    def __enter__(self) -> Writer: ...
+    def __exit__(self, exc_type, exc_value, traceback) -> None: ...
+
+class AsyncWriter:
+    async def write(self, bs: bytes) -> int: ...
+    async def close(self) -> None: ...
+    def __aenter__(self) -> AsyncWriter: ...
+    def __aexit__(self, exc_type, exc_value, traceback) -> None: ...
+
 class Entry:
     @property
     def path(self) -> str: ...
@@ -71,8 +144,18 @@ class Metadata:
     @property
     def etag(self) -> Optional[str]: ...
     @property
+    def last_modified(self) -> Optional[datetime]: ...
+    @property
     def mode(self) -> EntryMode: ...
 
 class EntryMode:
     def is_file(self) -> bool: ...
     def is_dir(self) -> bool: ...
+
+class PresignedRequest:
+    @property
+    def url(self) -> str: ...
+    @property
+    def method(self) -> str: ...
+    @property
+    def headers(self) -> Dict[str, str]: ...
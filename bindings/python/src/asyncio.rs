@@ -19,9 +19,12 @@ use std::collections::HashMap;
 use std::io::SeekFrom;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use ::opendal as od;
 use futures::TryStreamExt;
+use od::ops::OpWrite;
+use od::raw::oio;
 use pyo3::exceptions::PyIOError;
 use pyo3::exceptions::PyNotImplementedError;
 use pyo3::exceptions::PyStopAsyncIteration;
@@ -34,11 +37,14 @@ use tokio::io::AsyncReadExt;
 use tokio::io::AsyncSeekExt;
 use tokio::sync::Mutex;
 
+use crate::build_op_write;
 use crate::build_operator;
+use crate::build_range;
 use crate::format_pyerr;
 use crate::layers;
 use crate::Entry;
 use crate::Metadata;
+use crate::PresignedRequest;
 
 /// `AsyncOperator` is the entry for all public async APIs
 ///
@@ -67,10 +73,23 @@ impl AsyncOperator {
     }
 
     /// Read the whole path into bytes.
-    pub fn read<'p>(&'p self, py: Python<'p>, path: String) -> PyResult<&'p PyAny> {
+    ///
+    /// Only `size` bytes starting from `offset` will be read if given.
+    #[pyo3(signature = (path, *, offset=None, size=None))]
+    pub fn read<'p>(
+        &'p self,
+        py: Python<'p>,
+        path: String,
+        offset: Option<u64>,
+        size: Option<u64>,
+    ) -> PyResult<&'p PyAny> {
         let this = self.0.clone();
         future_into_py(py, async move {
-            let res: Vec<u8> = this.read(&path).await.map_err(format_pyerr)?;
+            let res: Vec<u8> = match (offset, size) {
+                (None, None) => this.read(&path).await,
+                (offset, size) => this.range_read(&path, build_range(offset, size)).await,
+            }
+            .map_err(format_pyerr)?;
             let pybytes: PyObject = Python::with_gil(|py| PyBytes::new(py, &res).into());
             Ok(pybytes)
         })
@@ -85,14 +104,41 @@ impl AsyncOperator {
     }
 
     /// Write bytes into given path.
-    pub fn write<'p>(&'p self, py: Python<'p>, path: String, bs: &PyBytes) -> PyResult<&'p PyAny> {
+    #[pyo3(signature = (path, bs, *, content_type=None, content_disposition=None, cache_control=None))]
+    pub fn write<'p>(
+        &'p self,
+        py: Python<'p>,
+        path: String,
+        bs: &PyBytes,
+        content_type: Option<&str>,
+        content_disposition: Option<&str>,
+        cache_control: Option<&str>,
+    ) -> PyResult<&'p PyAny> {
         let this = self.0.clone();
         let bs = bs.as_bytes().to_vec();
+        let op = build_op_write(content_type, content_disposition, cache_control);
         future_into_py(py, async move {
-            this.write(&path, bs).await.map_err(format_pyerr)
+            this.write_with(&path, op, bs).await.map_err(format_pyerr)
         })
     }
 
+    /// Open a file-like writer for the given path, the content is stored
+    /// after the writer is closed.
+    #[pyo3(signature = (path, *, content_type=None, content_disposition=None, cache_control=None))]
+    pub fn open_writer(
+        &self,
+        path: String,
+        content_type: Option<&str>,
+        content_disposition: Option<&str>,
+        cache_control: Option<&str>,
+    ) -> PyResult<AsyncWriter> {
+        Ok(AsyncWriter::new(WriterState::Init {
+            operator: self.0.clone(),
+            path,
+            op: build_op_write(content_type, content_disposition, cache_control),
+        }))
+    }
+
     /// Get current path's metadata **without cache** directly.
     pub fn stat<'p>(&'p self, py: Python<'p>, path: String) -> PyResult<&'p PyAny> {
         let this = self.0.clone();
@@ -103,6 +149,40 @@ impl AsyncOperator {
         })
     }
 
+    /// Copy source to target.
+    pub fn copy<'p>(
+        &'p self,
+        py: Python<'p>,
+        source: String,
+        target: String,
+    ) -> PyResult<&'p PyAny> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            this.copy(&source, &target).await.map_err(format_pyerr)
+        })
+    }
+
+    /// Rename filename.
+    pub fn rename<'p>(
+        &'p self,
+        py: Python<'p>,
+        source: String,
+        target: String,
+    ) -> PyResult<&'p PyAny> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            this.rename(&source, &target).await.map_err(format_pyerr)
+        })
+    }
+
+    /// Remove the path and all nested dirs and files recursively.
+    pub fn remove_all<'p>(&'p self, py: Python<'p>, path: String) -> PyResult<&'p PyAny> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            this.remove_all(&path).await.map_err(format_pyerr)
+        })
+    }
+
     /// Create a dir at given path.
     ///
     /// # Notes
@@ -155,6 +235,63 @@ impl AsyncOperator {
         })
     }
 
+    /// Presign an operation for stat(head), `expire` is in seconds.
+    pub fn presign_stat<'p>(
+        &'p self,
+        py: Python<'p>,
+        path: String,
+        expire: u64,
+    ) -> PyResult<&'p PyAny> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            let res = this
+                .presign_stat(&path, Duration::from_secs(expire))
+                .await
+                .map_err(format_pyerr)
+                .map(PresignedRequest)?;
+
+            Ok(res)
+        })
+    }
+
+    /// Presign an operation for read, `expire` is in seconds.
+    pub fn presign_read<'p>(
+        &'p self,
+        py: Python<'p>,
+        path: String,
+        expire: u64,
+    ) -> PyResult<&'p PyAny> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            let res = this
+                .presign_read(&path, Duration::from_secs(expire))
+                .await
+                .map_err(format_pyerr)
+                .map(PresignedRequest)?;
+
+            Ok(res)
+        })
+    }
+
+    /// Presign an operation for write, `expire` is in seconds.
+    pub fn presign_write<'p>(
+        &'p self,
+        py: Python<'p>,
+        path: String,
+        expire: u64,
+    ) -> PyResult<&'p PyAny> {
+        let this = self.0.clone();
+        future_into_py(py, async move {
+            let res = this
+                .presign_write(&path, Duration::from_secs(expire))
+                .await
+                .map_err(format_pyerr)
+                .map(PresignedRequest)?;
+
+            Ok(res)
+        })
+    }
+
     fn __repr__(&self) -> String {
         let info = self.0.info();
         let name = info.name();
@@ -221,22 +358,26 @@ impl AsyncReader {
 
 #[pymethods]
 impl AsyncReader {
-    /// Read and return size bytes, or if size is not given, until EOF.
-    pub fn read<'p>(&'p self, py: Python<'p>, size: Option<usize>) -> PyResult<&'p PyAny> {
+    /// Read and return at most `size` bytes, or if size is not given, until EOF.
+    ///
+    /// Returns an empty bytes at EOF.
+    #[pyo3(signature = (size=None,))]
+    pub fn read<'p>(&'p self, py: Python<'p>, size: Option<i64>) -> PyResult<&'p PyAny> {
         let reader = self.0.clone();
         future_into_py(py, async move {
             let mut state = reader.lock().await;
             let reader = state.reader().await?;
             let buffer = match size {
-                Some(size) => {
-                    let mut buffer = vec![0; size];
+                Some(size) if size >= 0 => {
+                    let mut buffer = Vec::with_capacity(size as usize);
                     reader
-                        .read_exact(&mut buffer)
+                        .take(size as u64)
+                        .read_to_end(&mut buffer)
                         .await
                         .map_err(|err| PyIOError::new_err(err.to_string()))?;
                     buffer
                 }
-                None => {
+                _ => {
                     let mut buffer = Vec::new();
                     reader
                         .read_to_end(&mut buffer)
@@ -324,6 +465,133 @@ impl AsyncReader {
     }
 }
 
+enum WriterState {
+    Init {
+        operator: od::Operator,
+        path: String,
+        op: OpWrite,
+    },
+    Open(od::Writer, oio::AppendBuffer),
+    Closed,
+}
+
+impl WriterState {
+    async fn writer(&mut self) -> PyResult<(&mut od::Writer, &mut oio::AppendBuffer)> {
+        if let WriterState::Init { operator, path, op } = self {
+            let writer = operator
+                .writer_with(path, op.clone())
+                .await
+                .map_err(format_pyerr)?;
+            *self = WriterState::Open(writer, oio::AppendBuffer::new());
+        }
+        match self {
+            WriterState::Open(writer, buf) => Ok((writer, buf)),
+            WriterState::Closed => Err(PyValueError::new_err("I/O operation on closed file.")),
+            WriterState::Init { .. } => unreachable!("writer must be opened"),
+        }
+    }
+
+    /// Buffer `bs` and append them in chunks of 8 MiB.
+    async fn append(&mut self, bs: Vec<u8>) -> PyResult<()> {
+        let (writer, buf) = self.writer().await?;
+        if let Some(chunk) = buf.push(bs.into()) {
+            writer.append(chunk).await.map_err(format_pyerr)?;
+        }
+        Ok(())
+    }
+
+    async fn close(&mut self) -> PyResult<()> {
+        if let WriterState::Closed = self {
+            return Ok(());
+        }
+        // Open the writer if needed, so that closing behaves the same as
+        // the blocking writer which is opened eagerly.
+        let (writer, buf) = self.writer().await?;
+        if let Some(chunk) = buf.finish() {
+            writer.append(chunk).await.map_err(format_pyerr)?;
+        }
+        writer.close().await.map_err(format_pyerr)?;
+        *self = WriterState::Closed;
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> PyResult<()> {
+        let state = std::mem::replace(self, WriterState::Closed);
+        if let WriterState::Open(mut writer, _) = state {
+            match writer.abort().await {
+                // Dropping the writer without closing discards the content
+                // if the service can't abort.
+                Err(err) if err.kind() == od::ErrorKind::Unsupported => {}
+                res => res.map_err(format_pyerr)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A file-like async writer, the content is stored after the writer is
+/// closed. Written bytes are buffered and appended in chunks of 8 MiB.
+/// Can be used as an async context manager, the content will be discarded
+/// if the context exits with an exception.
+#[pyclass(module = "opendal")]
+pub struct AsyncWriter(Arc<Mutex<WriterState>>);
+
+impl AsyncWriter {
+    fn new(writer: WriterState) -> Self {
+        Self(Arc::new(Mutex::new(writer)))
+    }
+}
+
+#[pymethods]
+impl AsyncWriter {
+    /// Write bytes into this writer, returns the number of bytes written.
+    pub fn write<'p>(&'p self, py: Python<'p>, bs: &PyBytes) -> PyResult<&'p PyAny> {
+        let writer = self.0.clone();
+        let bs = bs.as_bytes().to_vec();
+        future_into_py(py, async move {
+            let mut state = writer.lock().await;
+            let size = bs.len();
+            state.append(bs).await?;
+            Ok(size)
+        })
+    }
+
+    /// Close this writer and make sure all content has been stored.
+    ///
+    /// Closing a closed writer has no effect.
+    pub fn close<'p>(&'p self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let writer = self.0.clone();
+        future_into_py(py, async move {
+            let mut state = writer.lock().await;
+            state.close().await
+        })
+    }
+
+    fn __aenter__<'a>(slf: PyRef<'a, Self>, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let slf = slf.into_py(py);
+        future_into_py(py, async move { Ok(slf) })
+    }
+
+    fn __aexit__<'a>(
+        &self,
+        py: Python<'a>,
+        exc_type: &'a PyAny,
+        _exc_value: &'a PyAny,
+        _traceback: &'a PyAny,
+    ) -> PyResult<&'a PyAny> {
+        let writer = self.0.clone();
+        let failed = !exc_type.is_none();
+        future_into_py(py, async move {
+            let mut state = writer.lock().await;
+            if failed {
+                state.abort().await
+            } else {
+                state.close().await
+            }
+        })
+    }
+}
+
 #[pyclass(module = "opendal")]
 struct AsyncLister(Arc<Mutex<od::Lister>>);
 
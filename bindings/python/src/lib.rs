@@ -19,12 +19,19 @@
 #![allow(clippy::redundant_closure)]
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::io::Read;
 use std::io::Seek;
 use std::io::SeekFrom;
+use std::ops::Bound;
 use std::str::FromStr;
+use std::time::Duration;
 
 use ::opendal as od;
+use chrono::DateTime;
+use chrono::Utc;
+use od::ops::OpWrite;
+use od::raw::oio;
 use pyo3::create_exception;
 use pyo3::exceptions::PyException;
 use pyo3::exceptions::PyFileNotFoundError;
@@ -62,61 +69,53 @@ fn build_operator(
     map: HashMap<String, String>,
     layers: Vec<layers::Layer>,
 ) -> PyResult<od::Operator> {
-    use od::services::*;
-
-    let op = match scheme {
-        od::Scheme::Azblob => od::Operator::from_map::<Azblob>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Azdfs => od::Operator::from_map::<Azdfs>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Fs => od::Operator::from_map::<Fs>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Gcs => od::Operator::from_map::<Gcs>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Ghac => od::Operator::from_map::<Ghac>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Http => od::Operator::from_map::<Http>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Ipmfs => od::Operator::from_map::<Ipmfs>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Memory => od::Operator::from_map::<Memory>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Obs => od::Operator::from_map::<Obs>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Oss => od::Operator::from_map::<Oss>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::S3 => od::Operator::from_map::<S3>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Webdav => od::Operator::from_map::<Webdav>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        od::Scheme::Webhdfs => od::Operator::from_map::<Webhdfs>(map)
-            .map_err(format_pyerr)?
-            .finish(),
-        _ => Err(PyNotImplementedError::new_err(format!(
-            "unsupported scheme `{scheme}`"
-        )))?,
-    };
+    let op = od::Operator::via_map(scheme, map).map_err(format_pyerr)?;
 
     add_layers(op, layers)
 }
 
+fn build_op_write(
+    content_type: Option<&str>,
+    content_disposition: Option<&str>,
+    cache_control: Option<&str>,
+) -> OpWrite {
+    let mut op = OpWrite::new();
+    if let Some(v) = content_type {
+        op = op.with_content_type(v);
+    }
+    if let Some(v) = content_disposition {
+        op = op.with_content_disposition(v);
+    }
+    if let Some(v) = cache_control {
+        op = op.with_cache_control(v);
+    }
+    op
+}
+
+/// Build the range to read from `offset` and `size`, reading until EOF if
+/// `size` is not given.
+fn build_range(offset: Option<u64>, size: Option<u64>) -> (Bound<u64>, Bound<u64>) {
+    let offset = offset.unwrap_or_default();
+    let end = match size {
+        Some(size) => Bound::Excluded(offset.saturating_add(size)),
+        None => Bound::Unbounded,
+    };
+    (Bound::Included(offset), end)
+}
+
+/// Run `fut` to completion on the runtime shared with the async APIs.
+fn block_on<F: Future>(fut: F) -> F::Output {
+    pyo3_asyncio::tokio::get_runtime().block_on(fut)
+}
+
 /// `Operator` is the entry for all public blocking APIs
 ///
 /// Create a new blocking `Operator` with the given `scheme` and options(`**kwargs`).
 #[pyclass(module = "opendal")]
-struct Operator(od::BlockingOperator);
+struct Operator {
+    core: od::Operator,
+    blocking: od::BlockingOperator,
+}
 
 #[pymethods]
 impl Operator {
@@ -135,33 +134,86 @@ impl Operator {
             })
             .unwrap_or_default();
 
-        Ok(Operator(build_operator(scheme, map, layers)?.blocking()))
+        let core = build_operator(scheme, map, layers)?;
+        let blocking = core.blocking();
+        Ok(Operator { core, blocking })
     }
 
     /// Read the whole path into bytes.
-    pub fn read<'p>(&'p self, py: Python<'p>, path: &str) -> PyResult<&'p PyAny> {
-        self.0
-            .read(path)
-            .map_err(format_pyerr)
+    ///
+    /// Only `size` bytes starting from `offset` will be read if given.
+    #[pyo3(signature = (path, *, offset=None, size=None))]
+    pub fn read<'p>(
+        &'p self,
+        py: Python<'p>,
+        path: &str,
+        offset: Option<u64>,
+        size: Option<u64>,
+    ) -> PyResult<&'p PyAny> {
+        let res = match (offset, size) {
+            (None, None) => self.blocking.read(path),
+            (offset, size) => self.blocking.range_read(path, build_range(offset, size)),
+        };
+        res.map_err(format_pyerr)
             .map(|res| PyBytes::new(py, &res).into())
     }
 
     /// Open a file-like reader for the given path.
     pub fn open_reader(&self, path: &str) -> PyResult<Reader> {
-        self.0
+        self.blocking
             .reader(path)
             .map(|reader| Reader(Some(reader)))
             .map_err(format_pyerr)
     }
 
     /// Write bytes into given path.
-    pub fn write(&self, path: &str, bs: Vec<u8>) -> PyResult<()> {
-        self.0.write(path, bs).map_err(format_pyerr)
+    #[pyo3(signature = (path, bs, *, content_type=None, content_disposition=None, cache_control=None))]
+    pub fn write(
+        &self,
+        path: &str,
+        bs: Vec<u8>,
+        content_type: Option<&str>,
+        content_disposition: Option<&str>,
+        cache_control: Option<&str>,
+    ) -> PyResult<()> {
+        let op = build_op_write(content_type, content_disposition, cache_control);
+        self.blocking.write_with(path, op, bs).map_err(format_pyerr)
+    }
+
+    /// Open a file-like writer for the given path, the content is stored
+    /// after the writer is closed.
+    #[pyo3(signature = (path, *, content_type=None, content_disposition=None, cache_control=None))]
+    pub fn open_writer(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+        content_disposition: Option<&str>,
+        cache_control: Option<&str>,
+    ) -> PyResult<Writer> {
+        let op = build_op_write(content_type, content_disposition, cache_control);
+        block_on(self.core.writer_with(path, op))
+            .map(|writer| Writer(Some((writer, oio::AppendBuffer::new()))))
+            .map_err(format_pyerr)
     }
 
     /// Get current path's metadata **without cache** directly.
     pub fn stat(&self, path: &str) -> PyResult<Metadata> {
-        self.0.stat(path).map_err(format_pyerr).map(Metadata)
+        self.blocking.stat(path).map_err(format_pyerr).map(Metadata)
+    }
+
+    /// Copy source to target.
+    pub fn copy(&self, source: &str, target: &str) -> PyResult<()> {
+        self.blocking.copy(source, target).map_err(format_pyerr)
+    }
+
+    /// Rename filename.
+    pub fn rename(&self, source: &str, target: &str) -> PyResult<()> {
+        self.blocking.rename(source, target).map_err(format_pyerr)
+    }
+
+    /// Remove the path and all nested dirs and files recursively.
+    pub fn remove_all(&self, path: &str) -> PyResult<()> {
+        self.blocking.remove_all(path).map_err(format_pyerr)
     }
 
     /// Create a dir at given path.
@@ -177,7 +229,7 @@ impl Operator {
     /// - Create on existing dir will succeed.
     /// - Create dir is always recursive, works like `mkdir -p`
     pub fn create_dir(&self, path: &str) -> PyResult<()> {
-        self.0.create_dir(path).map_err(format_pyerr)
+        self.blocking.create_dir(path).map_err(format_pyerr)
     }
 
     /// Delete given path.
@@ -186,21 +238,46 @@ impl Operator {
     ///
     /// - Delete not existing error won't return errors.
     pub fn delete(&self, path: &str) -> PyResult<()> {
-        self.0.delete(path).map_err(format_pyerr)
+        self.blocking.delete(path).map_err(format_pyerr)
     }
 
     /// List current dir path.
     pub fn list(&self, path: &str) -> PyResult<BlockingLister> {
-        Ok(BlockingLister(self.0.list(path).map_err(format_pyerr)?))
+        Ok(BlockingLister(
+            self.blocking.list(path).map_err(format_pyerr)?,
+        ))
     }
 
     /// List dir in flat way.
     pub fn scan(&self, path: &str) -> PyResult<BlockingLister> {
-        Ok(BlockingLister(self.0.scan(path).map_err(format_pyerr)?))
+        Ok(BlockingLister(
+            self.blocking.scan(path).map_err(format_pyerr)?,
+        ))
+    }
+
+    /// Presign an operation for stat(head), `expire` is in seconds.
+    pub fn presign_stat(&self, path: &str, expire: u64) -> PyResult<PresignedRequest> {
+        block_on(self.core.presign_stat(path, Duration::from_secs(expire)))
+            .map_err(format_pyerr)
+            .map(PresignedRequest)
+    }
+
+    /// Presign an operation for read, `expire` is in seconds.
+    pub fn presign_read(&self, path: &str, expire: u64) -> PyResult<PresignedRequest> {
+        block_on(self.core.presign_read(path, Duration::from_secs(expire)))
+            .map_err(format_pyerr)
+            .map(PresignedRequest)
+    }
+
+    /// Presign an operation for write, `expire` is in seconds.
+    pub fn presign_write(&self, path: &str, expire: u64) -> PyResult<PresignedRequest> {
+        block_on(self.core.presign_write(path, Duration::from_secs(expire)))
+            .map_err(format_pyerr)
+            .map(PresignedRequest)
     }
 
     fn __repr__(&self) -> String {
-        let info = self.0.info();
+        let info = self.core.info();
         let name = info.name();
         if name.is_empty() {
             format!("Operator(\"{}\", root=\"{}\")", info.scheme(), info.root())
@@ -231,19 +308,21 @@ impl Reader {
 
 #[pymethods]
 impl Reader {
-    /// Read and return size bytes, or if size is not given, until EOF.
+    /// Read and return at most `size` bytes, or if size is not given, until EOF.
+    ///
+    /// Returns an empty bytes at EOF.
     #[pyo3(signature = (size=None,))]
-    pub fn read<'p>(&'p mut self, py: Python<'p>, size: Option<usize>) -> PyResult<&'p PyAny> {
+    pub fn read<'p>(&'p mut self, py: Python<'p>, size: Option<i64>) -> PyResult<&'p PyAny> {
         let reader = self.as_mut()?;
         let buffer = match size {
-            Some(size) => {
-                let mut buffer = vec![0; size];
-                reader
-                    .read_exact(&mut buffer)
+            Some(size) if size >= 0 => {
+                let mut buffer = Vec::with_capacity(size as usize);
+                Read::take(reader, size as u64)
+                    .read_to_end(&mut buffer)
                     .map_err(|err| PyIOError::new_err(err.to_string()))?;
                 buffer
             }
-            None => {
+            _ => {
                 let mut buffer = Vec::new();
                 reader
                     .read_to_end(&mut buffer)
@@ -274,7 +353,10 @@ impl Reader {
     #[pyo3(signature = (pos, whence = 0))]
     pub fn seek(&mut self, pos: i64, whence: u8) -> PyResult<u64> {
         let whence = match whence {
-            0 => SeekFrom::Start(pos as u64),
+            0 => SeekFrom::Start(
+                u64::try_from(pos)
+                    .map_err(|_| PyValueError::new_err(format!("negative seek position {pos}")))?,
+            ),
             1 => SeekFrom::Current(pos),
             2 => SeekFrom::End(pos),
             _ => return Err(PyValueError::new_err("invalid whence")),
@@ -293,12 +375,129 @@ impl Reader {
             .map_err(|err| PyIOError::new_err(err.to_string()))
     }
 
+    /// Return `True` since `Reader` is readable.
+    pub fn readable(&self) -> bool {
+        true
+    }
+
+    /// Return `True` since `Reader` is seekable.
+    pub fn seekable(&self) -> bool {
+        true
+    }
+
+    /// Return `False` since `Reader` is not writable.
+    pub fn writable(&self) -> bool {
+        false
+    }
+
+    /// Close this reader, further reading will raise `ValueError`.
+    pub fn close(&mut self) {
+        drop(self.0.take());
+    }
+
+    /// Return `True` if this reader has been closed.
+    #[getter]
+    pub fn closed(&self) -> bool {
+        self.0.is_none()
+    }
+
     pub fn __enter__(slf: Py<Self>) -> Py<Self> {
         slf
     }
 
     pub fn __exit__(&mut self, _exc_type: PyObject, _exc_value: PyObject, _traceback: PyObject) {
-        drop(self.0.take());
+        self.close();
+    }
+}
+
+/// A file-like blocking writer, the content is stored after the writer is
+/// closed. Written bytes are buffered and appended in chunks of 8 MiB.
+/// Can be used as a context manager, the writer will be aborted if the
+/// context exits with an exception.
+#[pyclass(module = "opendal")]
+struct Writer(Option<(od::Writer, oio::AppendBuffer)>);
+
+#[pymethods]
+impl Writer {
+    /// Write bytes into this writer, returns the number of bytes written.
+    pub fn write(&mut self, bs: &[u8]) -> PyResult<usize> {
+        let (writer, buf) = self
+            .0
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("I/O operation on closed file."))?;
+        if let Some(chunk) = buf.push(bs.to_vec().into()) {
+            block_on(writer.append(chunk)).map_err(format_pyerr)?;
+        }
+        Ok(bs.len())
+    }
+
+    /// Return `False` since `Writer` is not readable.
+    pub fn readable(&self) -> bool {
+        false
+    }
+
+    /// Return `False` since `Writer` is not seekable.
+    pub fn seekable(&self) -> bool {
+        false
+    }
+
+    /// Return `True` since `Writer` is writable.
+    pub fn writable(&self) -> bool {
+        true
+    }
+
+    /// Close this writer and make sure all content has been stored.
+    ///
+    /// Closing a closed writer has no effect.
+    pub fn close(&mut self) -> PyResult<()> {
+        let (mut writer, mut buf) = match self.0.take() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        block_on(async {
+            if let Some(chunk) = buf.finish() {
+                writer.append(chunk).await?;
+            }
+            writer.close().await
+        })
+        .map_err(format_pyerr)
+    }
+
+    /// Abort this writer and discard all written content.
+    ///
+    /// Aborting a closed writer has no effect.
+    pub fn abort(&mut self) -> PyResult<()> {
+        match self.0.take() {
+            Some((mut writer, _)) => match block_on(writer.abort()) {
+                // Dropping the writer without closing discards the content
+                // if the service can't abort.
+                Err(err) if err.kind() == od::ErrorKind::Unsupported => Ok(()),
+                res => res.map_err(format_pyerr),
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Return `True` if this writer has been closed or aborted.
+    #[getter]
+    pub fn closed(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    pub fn __exit__(
+        &mut self,
+        exc_type: &PyAny,
+        _exc_value: PyObject,
+        _traceback: PyObject,
+    ) -> PyResult<()> {
+        if !exc_type.is_none() {
+            return self.abort();
+        }
+        self.close()
     }
 }
 
@@ -376,11 +575,29 @@ impl Metadata {
         self.0.etag()
     }
 
+    /// Last modified time of this entry.
+    #[getter]
+    pub fn last_modified(&self) -> Option<DateTime<Utc>> {
+        self.0.last_modified()
+    }
+
     /// mode represent this entry's mode.
     #[getter]
     pub fn mode(&self) -> EntryMode {
         EntryMode(self.0.mode())
     }
+
+    fn __repr__(&self) -> String {
+        let last_modified = match self.0.last_modified() {
+            Some(t) => format!("\"{}\"", t.to_rfc3339()),
+            None => "None".to_string(),
+        };
+        format!(
+            "Metadata(mode={}, content_length={}, last_modified={last_modified})",
+            EntryMode(self.0.mode()).__repr__(),
+            self.0.content_length(),
+        )
+    }
 }
 
 #[pyclass(module = "opendal")]
@@ -407,6 +624,39 @@ impl EntryMode {
     }
 }
 
+/// A presigned request which can be sent without credentials.
+#[pyclass(module = "opendal")]
+struct PresignedRequest(od::raw::PresignedRequest);
+
+#[pymethods]
+impl PresignedRequest {
+    /// Return the URL of this request.
+    #[getter]
+    pub fn url(&self) -> String {
+        self.0.uri().to_string()
+    }
+
+    /// Return the HTTP method of this request.
+    #[getter]
+    pub fn method(&self) -> &str {
+        self.0.method().as_str()
+    }
+
+    /// Return the HTTP headers of this request.
+    #[getter]
+    pub fn headers(&self) -> PyResult<HashMap<&str, &str>> {
+        let mut headers = HashMap::new();
+        for (k, v) in self.0.header().iter() {
+            let k = k.as_str();
+            let v = v
+                .to_str()
+                .map_err(|err| Error::new_err(format!("header {k} is not valid: {err}")))?;
+            headers.insert(k, v);
+        }
+        Ok(headers)
+    }
+}
+
 fn format_pyerr(err: od::Error) -> PyErr {
     use od::ErrorKind::*;
     match err.kind() {
@@ -450,11 +700,14 @@ fn format_pyerr(err: od::Error) -> PyErr {
 fn _opendal(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<Operator>()?;
     m.add_class::<Reader>()?;
+    m.add_class::<Writer>()?;
     m.add_class::<AsyncOperator>()?;
     m.add_class::<AsyncReader>()?;
+    m.add_class::<AsyncWriter>()?;
     m.add_class::<Entry>()?;
     m.add_class::<EntryMode>()?;
     m.add_class::<Metadata>()?;
+    m.add_class::<PresignedRequest>()?;
     m.add("Error", py.get_type::<Error>())?;
 
     let layers = layers::create_submodule(py)?;
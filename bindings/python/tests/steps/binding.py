@@ -88,3 +88,65 @@ async def step_impl(context, filename, size):
 async def step_impl(context, filename, content):
     bs = await context.op.read(filename)
     assert bs == content.encode()
+
+
+@when('Blocking open writer path "{filename}" with content "{content}"')
+def step_impl(context, filename, content):
+    with context.op.open_writer(filename) as w:
+        w.write(content.encode())
+
+
+@when('Blocking open writer path "{filename}" and raise an exception')
+def step_impl(context, filename):
+    try:
+        with context.op.open_writer(filename) as w:
+            w.write(b"discarded")
+            raise RuntimeError("abort")
+    except RuntimeError:
+        pass
+
+
+@then('The blocking file "{filename}" should not exist')
+def step_impl(context, filename):
+    try:
+        context.op.stat(filename)
+    except FileNotFoundError:
+        return
+    raise AssertionError(f"{filename} should not exist")
+
+
+@then('The blocking file "{filename}" in range {start:d} to {end:d} must have content "{content}"')
+def step_impl(context, filename, start, end, content):
+    bs = context.op.read(filename, offset=start, size=end - start)
+    assert bs == content.encode()
+
+
+@then('The blocking reader of "{filename}" must read content "{content}" in chunks of {size:d}')
+def step_impl(context, filename, content, size):
+    with context.op.open_reader(filename) as r:
+        chunks = iter(lambda: r.read(size), b"")
+        assert b"".join(chunks) == content.encode()
+
+
+@when('Async open writer path "{filename}" with content "{content}"')
+@async_run_until_complete
+async def step_impl(context, filename, content):
+    async with context.op.open_writer(filename) as w:
+        await w.write(content.encode())
+
+
+@then('The async file "{filename}" in range {start:d} to {end:d} must have content "{content}"')
+@async_run_until_complete
+async def step_impl(context, filename, start, end, content):
+    bs = await context.op.read(filename, offset=start, size=end - start)
+    assert bs == content.encode()
+
+
+@then('The async reader of "{filename}" must read content "{content}" in chunks of {size:d}')
+@async_run_until_complete
+async def step_impl(context, filename, content, size):
+    async with context.op.open_reader(filename) as r:
+        bs = b""
+        while chunk := await r.read(size):
+            bs += chunk
+        assert bs == content.encode()
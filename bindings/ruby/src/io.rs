@@ -0,0 +1,259 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cell::RefCell;
+use std::io::SeekFrom;
+
+use magnus::block::block_given;
+use magnus::block::yield_value;
+use magnus::class;
+use magnus::error::Result;
+use magnus::exception;
+use magnus::method;
+use magnus::prelude::*;
+use magnus::scan_args::scan_args;
+use magnus::typed_data::Obj;
+use magnus::Error;
+use magnus::RModule;
+use magnus::RString;
+use magnus::Value;
+use od::raw::oio;
+use od::raw::oio::BlockingRead;
+use opendal as od;
+
+use crate::format_magnus_error;
+use crate::RUNTIME;
+
+/// The chunk size used by `each_chunk` if not specified.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+fn closed_stream_error() -> Error {
+    Error::new(exception::io_error(), "closed stream")
+}
+
+/// Read until `limit` bytes have been read or reaching EOF, read until
+/// EOF if `limit` is `None`.
+fn read_up_to(r: &mut od::BlockingReader, limit: Option<usize>) -> od::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut chunk = vec![0; limit.unwrap_or(DEFAULT_CHUNK_SIZE).min(DEFAULT_CHUNK_SIZE)];
+    loop {
+        let size = match limit {
+            Some(limit) if buf.len() >= limit => break,
+            Some(limit) => chunk.len().min(limit - buf.len()),
+            None => chunk.len(),
+        };
+        let n = r.read(&mut chunk[..size])?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    Ok(buf)
+}
+
+/// Reader is an IO-like object to read content of a path.
+#[magnus::wrap(class = "OpenDAL::Reader", free_immediately, size)]
+pub struct Reader(RefCell<Option<od::BlockingReader>>);
+
+impl Reader {
+    pub fn new(reader: od::BlockingReader) -> Self {
+        Reader(RefCell::new(Some(reader)))
+    }
+
+    fn with_reader<T>(
+        &self,
+        f: impl FnOnce(&mut od::BlockingReader) -> od::Result<T>,
+    ) -> Result<T> {
+        let mut reader = self.0.borrow_mut();
+        let reader = reader.as_mut().ok_or_else(closed_stream_error)?;
+        f(reader).map_err(format_magnus_error)
+    }
+
+    /// Read at most `length` bytes, or the remaining content if `length` is
+    /// `nil`.
+    ///
+    /// Like `IO#read`, returns `nil` at EOF if `length` is given, returns
+    /// an empty string at EOF otherwise.
+    pub fn read(&self, args: &[Value]) -> Result<Option<RString>> {
+        let args = scan_args::<(), (Option<Option<usize>>,), (), (), (), ()>(args)?;
+        let (length,) = args.optional;
+        match length.flatten() {
+            None => {
+                let buf = self.with_reader(|r| read_up_to(r, None))?;
+                Ok(Some(RString::from_slice(&buf)))
+            }
+            Some(0) => Ok(Some(RString::new(""))),
+            Some(length) => {
+                let buf = self.with_reader(|r| read_up_to(r, Some(length)))?;
+                if buf.is_empty() {
+                    Ok(None)
+                } else {
+                    Ok(Some(RString::from_slice(&buf)))
+                }
+            }
+        }
+    }
+
+    /// Seek to `offset` relative to `whence`, which is one of `IO::SEEK_SET`,
+    /// `IO::SEEK_CUR` and `IO::SEEK_END`. Returns `0` like `IO#seek`.
+    pub fn seek(&self, args: &[Value]) -> Result<i32> {
+        let args = scan_args::<(i64,), (Option<i32>,), (), (), (), ()>(args)?;
+        let (offset,) = args.required;
+        let (whence,) = args.optional;
+        let pos = match whence.unwrap_or(0) {
+            0 => {
+                let offset = u64::try_from(offset).map_err(|_| {
+                    Error::new(
+                        exception::arg_error(),
+                        format!("negative offset {offset} with IO::SEEK_SET"),
+                    )
+                })?;
+                SeekFrom::Start(offset)
+            }
+            1 => SeekFrom::Current(offset),
+            2 => SeekFrom::End(offset),
+            whence => {
+                return Err(Error::new(
+                    exception::arg_error(),
+                    format!("invalid whence {whence}"),
+                ))
+            }
+        };
+        self.with_reader(|r| r.seek(pos))?;
+        Ok(0)
+    }
+
+    /// Current position of this reader.
+    pub fn pos(&self) -> Result<u64> {
+        self.with_reader(|r| r.seek(SeekFrom::Current(0)))
+    }
+
+    /// Yield content in chunks of at most `size` bytes until EOF, returns an
+    /// enumerator if no block is given.
+    pub fn each_chunk(rb_self: Obj<Self>, args: &[Value]) -> Result<Value> {
+        if !block_given() {
+            return Ok(*rb_self.enumeratorize("each_chunk", args));
+        }
+
+        let parsed = scan_args::<(), (Option<Option<usize>>,), (), (), (), ()>(args)?;
+        let (size,) = parsed.optional;
+        let size = size.flatten().unwrap_or(DEFAULT_CHUNK_SIZE);
+        if size == 0 {
+            return Err(Error::new(
+                exception::arg_error(),
+                "chunk size must be positive",
+            ));
+        }
+
+        loop {
+            // Release the borrow before yielding, the block could touch
+            // this reader again.
+            let buf = rb_self.get().with_reader(|r| read_up_to(r, Some(size)))?;
+            if buf.is_empty() {
+                break;
+            }
+            let _: Value = yield_value(RString::from_slice(&buf))?;
+        }
+        Ok(*rb_self)
+    }
+
+    /// Close this reader, further reading will raise `IOError`.
+    pub fn close(&self) {
+        self.0.borrow_mut().take();
+    }
+
+    /// Returns `true` if this reader has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.0.borrow().is_none()
+    }
+}
+
+/// Writer buffers written content and appends it to the path in chunks of
+/// 8 MiB, the content is stored on close.
+#[magnus::wrap(class = "OpenDAL::Writer", free_immediately, size)]
+pub struct Writer(RefCell<Option<(od::Writer, oio::AppendBuffer)>>);
+
+impl Writer {
+    pub fn new(writer: od::Writer) -> Self {
+        Writer(RefCell::new(Some((writer, oio::AppendBuffer::new()))))
+    }
+
+    /// Append string into this writer, returns the number of bytes written.
+    pub fn write(&self, bs: RString) -> Result<usize> {
+        let mut writer = self.0.borrow_mut();
+        let (writer, buf) = writer.as_mut().ok_or_else(closed_stream_error)?;
+        let size = bs.len();
+        if let Some(chunk) = buf.push(bs.to_bytes()) {
+            RUNTIME
+                .block_on(writer.append(chunk))
+                .map_err(format_magnus_error)?;
+        }
+        Ok(size)
+    }
+
+    /// Close this writer and make sure all content has been stored, closing
+    /// a closed writer does nothing.
+    pub fn close(&self) -> Result<()> {
+        let writer = self.0.borrow_mut().take();
+        let (mut writer, mut buf) = match writer {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        RUNTIME
+            .block_on(async {
+                if let Some(chunk) = buf.finish() {
+                    writer.append(chunk).await?;
+                }
+                writer.close().await
+            })
+            .map_err(format_magnus_error)
+    }
+
+    /// Abort this writer and discard all written content, aborting a closed
+    /// writer does nothing.
+    pub fn abort(&self) -> Result<()> {
+        let writer = self.0.borrow_mut().take();
+        match writer {
+            Some((mut writer, _)) => RUNTIME
+                .block_on(writer.abort())
+                .map_err(format_magnus_error),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `true` if this writer has been closed or aborted.
+    pub fn is_closed(&self) -> bool {
+        self.0.borrow().is_none()
+    }
+}
+
+pub fn init(namespace: RModule) -> Result<()> {
+    let reader_class = namespace.define_class("Reader", class::object())?;
+    reader_class.define_method("read", method!(Reader::read, -1))?;
+    reader_class.define_method("seek", method!(Reader::seek, -1))?;
+    reader_class.define_method("pos", method!(Reader::pos, 0))?;
+    reader_class.define_method("each_chunk", method!(Reader::each_chunk, -1))?;
+    reader_class.define_method("close", method!(Reader::close, 0))?;
+    reader_class.define_method("closed?", method!(Reader::is_closed, 0))?;
+
+    let writer_class = namespace.define_class("Writer", class::object())?;
+    writer_class.define_method("write", method!(Writer::write, 1))?;
+    writer_class.define_method("close", method!(Writer::close, 0))?;
+    writer_class.define_method("abort", method!(Writer::abort, 0))?;
+    writer_class.define_method("closed?", method!(Writer::is_closed, 0))?;
+    Ok(())
+}
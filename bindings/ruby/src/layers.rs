@@ -0,0 +1,133 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::Duration;
+
+use magnus::class;
+use magnus::error::Result;
+use magnus::exception;
+use magnus::function;
+use magnus::prelude::*;
+use magnus::scan_args::get_kwargs;
+use magnus::scan_args::scan_args;
+use magnus::Error;
+use magnus::RHash;
+use magnus::RModule;
+use magnus::Value;
+use opendal as od;
+
+/// Convert seconds into [`Duration`], raising `ArgumentError` for
+/// negative or non-finite values.
+fn to_duration(name: &str, secs: f64) -> Result<Duration> {
+    if !secs.is_finite() || secs < 0.0 {
+        return Err(Error::new(
+            exception::arg_error(),
+            format!("{name} must be a non-negative number, got {secs}"),
+        ));
+    }
+    Ok(Duration::from_secs_f64(secs))
+}
+
+#[magnus::wrap(class = "OpenDAL::Layers::RetryLayer", free_immediately, size)]
+#[derive(Clone)]
+pub struct RetryLayer(pub od::layers::RetryLayer);
+
+impl RetryLayer {
+    /// Create a retry layer, accepts keyword arguments `max_times`, `factor`,
+    /// `jitter`, `max_delay` and `min_delay`. Delays are in seconds.
+    fn new(args: &[Value]) -> Result<Self> {
+        let args = scan_args::<(), (), (), (), RHash, ()>(args)?;
+        let kwargs = get_kwargs::<
+            _,
+            (),
+            (
+                Option<usize>,
+                Option<f32>,
+                Option<bool>,
+                Option<f64>,
+                Option<f64>,
+            ),
+            (),
+        >(
+            args.keywords,
+            &[],
+            &["max_times", "factor", "jitter", "max_delay", "min_delay"],
+        )?;
+        let (max_times, factor, jitter, max_delay, min_delay) = kwargs.optional;
+
+        let mut retry = od::layers::RetryLayer::default();
+        if let Some(max_times) = max_times {
+            retry = retry.with_max_times(max_times);
+        }
+        if let Some(factor) = factor {
+            retry = retry.with_factor(factor);
+        }
+        if jitter.unwrap_or_default() {
+            retry = retry.with_jitter();
+        }
+        if let Some(max_delay) = max_delay {
+            retry = retry.with_max_delay(to_duration("max_delay", max_delay)?);
+        }
+        if let Some(min_delay) = min_delay {
+            retry = retry.with_min_delay(to_duration("min_delay", min_delay)?);
+        }
+        Ok(Self(retry))
+    }
+}
+
+#[magnus::wrap(class = "OpenDAL::Layers::LoggingLayer", free_immediately, size)]
+#[derive(Clone)]
+pub struct LoggingLayer(pub od::layers::LoggingLayer);
+
+impl LoggingLayer {
+    /// Create a logging layer, logs are written to stderr and filtered by
+    /// the `RUST_LOG` env.
+    fn new() -> Self {
+        // Ignore the error if logger has been initiated.
+        let _ = env_logger::try_init();
+        Self(od::layers::LoggingLayer::default())
+    }
+}
+
+#[magnus::wrap(
+    class = "OpenDAL::Layers::ConcurrentLimitLayer",
+    free_immediately,
+    size
+)]
+#[derive(Clone)]
+pub struct ConcurrentLimitLayer(pub od::layers::ConcurrentLimitLayer);
+
+impl ConcurrentLimitLayer {
+    fn new(permits: usize) -> Self {
+        Self(od::layers::ConcurrentLimitLayer::new(permits))
+    }
+}
+
+pub fn init(namespace: RModule) -> Result<()> {
+    let layers = namespace.define_module("Layers")?;
+
+    let retry_class = layers.define_class("RetryLayer", class::object())?;
+    retry_class.define_singleton_method("new", function!(RetryLayer::new, -1))?;
+
+    let logging_class = layers.define_class("LoggingLayer", class::object())?;
+    logging_class.define_singleton_method("new", function!(LoggingLayer::new, 0))?;
+
+    let concurrent_limit_class = layers.define_class("ConcurrentLimitLayer", class::object())?;
+    concurrent_limit_class
+        .define_singleton_method("new", function!(ConcurrentLimitLayer::new, 1))?;
+    Ok(())
+}
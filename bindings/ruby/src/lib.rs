@@ -17,7 +17,10 @@
 
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::time::Duration;
 
+use magnus::block::block_given;
+use magnus::block::yield_value;
 use magnus::class;
 use magnus::define_module;
 use magnus::error::Result;
@@ -25,69 +28,54 @@ use magnus::exception;
 use magnus::function;
 use magnus::method;
 use magnus::prelude::*;
+use magnus::typed_data::Obj;
 use magnus::Error;
+use magnus::ExceptionClass;
+use magnus::RModule;
 use magnus::RString;
+use magnus::Value;
+use once_cell::sync::Lazy;
 use opendal as od;
+use tokio::runtime::Builder;
+use tokio::runtime::Runtime;
 
-fn build_operator(scheme: od::Scheme, map: HashMap<String, String>) -> Result<od::Operator> {
-    use od::services::*;
-
-    let op = match scheme {
-        od::Scheme::Azblob => od::Operator::from_map::<Azblob>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Azdfs => od::Operator::from_map::<Azdfs>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Fs => od::Operator::from_map::<Fs>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Gcs => od::Operator::from_map::<Gcs>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Ghac => od::Operator::from_map::<Ghac>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Http => od::Operator::from_map::<Http>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Ipmfs => od::Operator::from_map::<Ipmfs>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Memory => od::Operator::from_map::<Memory>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Obs => od::Operator::from_map::<Obs>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Oss => od::Operator::from_map::<Oss>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::S3 => od::Operator::from_map::<S3>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Webdav => od::Operator::from_map::<Webdav>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        od::Scheme::Webhdfs => od::Operator::from_map::<Webhdfs>(map)
-            .map_err(format_magnus_error)?
-            .finish(),
-        _ => {
-            return Err(format_magnus_error(od::Error::new(
-                od::ErrorKind::Unexpected,
-                "not supported scheme",
-            )))
-        }
-    };
+mod io;
+mod layers;
+mod lister;
+mod task;
+
+use io::Reader;
+use io::Writer;
+use lister::Lister;
+use task::Output;
+use task::Task;
 
-    Ok(op)
+/// The runtime shared by all async operations of ruby binding.
+static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    Builder::new_multi_thread()
+        .thread_name("opendal-ruby")
+        .enable_all()
+        .build()
+        .expect("build tokio runtime must succeed")
+});
+
+fn build_operator(scheme: od::Scheme, map: HashMap<String, String>) -> Result<od::Operator> {
+    od::Operator::via_map(scheme, map).map_err(format_magnus_error)
 }
 
 #[magnus::wrap(class = "OpenDAL::Operator", free_immediately, size)]
 #[derive(Clone, Debug)]
-pub struct Operator(od::BlockingOperator);
+pub struct Operator {
+    core: od::Operator,
+    blocking: od::BlockingOperator,
+}
 
 impl Operator {
+    fn from_core(core: od::Operator) -> Self {
+        let blocking = core.blocking();
+        Operator { core, blocking }
+    }
+
     pub fn new(scheme: String, options: Option<HashMap<String, String>>) -> Result<Self> {
         let scheme = od::Scheme::from_str(&scheme)
             .map_err(|err| {
@@ -95,29 +83,166 @@ impl Operator {
             })
             .map_err(format_magnus_error)?;
         let options = options.unwrap_or_default();
-        Ok(Operator(build_operator(scheme, options)?.blocking()))
+        Ok(Operator::from_core(build_operator(scheme, options)?))
+    }
+
+    /// Returns a new operator with given layer applied.
+    pub fn layer(&self, layer: Value) -> Result<Self> {
+        let op = self.core.clone();
+        let op = if let Ok(layer) = layer.try_convert::<&layers::RetryLayer>() {
+            op.layer(layer.0.clone())
+        } else if let Ok(layer) = layer.try_convert::<&layers::LoggingLayer>() {
+            op.layer(layer.0.clone())
+        } else if let Ok(layer) = layer.try_convert::<&layers::ConcurrentLimitLayer>() {
+            op.layer(layer.0.clone())
+        } else {
+            return Err(Error::new(
+                exception::type_error(),
+                format!("{} is not a supported layer", layer.inspect()),
+            ));
+        };
+        Ok(Operator::from_core(op))
     }
 
     /// Read the whole path into string.
     pub fn read(&self, path: String) -> Result<RString> {
-        let bytes = self.0.read(&path).map_err(format_magnus_error)?;
+        let bytes = self.blocking.read(&path).map_err(format_magnus_error)?;
         Ok(RString::from_slice(&bytes))
     }
 
     /// Write string into given path.
     pub fn write(&self, path: String, bs: RString) -> Result<()> {
-        self.0
+        self.blocking
             .write(&path, bs.to_bytes())
             .map_err(format_magnus_error)
     }
 
     /// Get current path's metadata **without cache** directly.
     pub fn stat(&self, path: String) -> Result<Metadata> {
-        self.0
+        self.blocking
             .stat(&path)
             .map_err(format_magnus_error)
             .map(Metadata)
     }
+
+    /// Create a dir at given path, the path must end with `/`.
+    pub fn create_dir(&self, path: String) -> Result<()> {
+        self.blocking.create_dir(&path).map_err(format_magnus_error)
+    }
+
+    /// Delete given path, deleting a not existing path is not an error.
+    pub fn delete(&self, path: String) -> Result<()> {
+        self.blocking.delete(&path).map_err(format_magnus_error)
+    }
+
+    /// List entries directly under given dir.
+    pub fn list(&self, path: String) -> Result<Lister> {
+        self.blocking
+            .list(&path)
+            .map_err(format_magnus_error)
+            .map(Lister::new)
+    }
+
+    /// List entries under given dir recursively.
+    pub fn scan(&self, path: String) -> Result<Lister> {
+        self.blocking
+            .scan(&path)
+            .map_err(format_magnus_error)
+            .map(Lister::new)
+    }
+
+    /// Open a reader on given path.
+    pub fn reader(&self, path: String) -> Result<Reader> {
+        self.blocking
+            .reader(&path)
+            .map_err(format_magnus_error)
+            .map(Reader::new)
+    }
+
+    /// Open a writer on given path, the content is stored after the writer
+    /// is closed.
+    ///
+    /// If a block is given, the writer is yielded to the block and closed
+    /// after the block returns, then the block's result is returned. The
+    /// writer is aborted and the content is discarded if the block raises.
+    pub fn writer(&self, path: String) -> Result<Value> {
+        let writer = RUNTIME
+            .block_on(self.core.writer(&path))
+            .map_err(format_magnus_error)?;
+        let writer = Obj::wrap(Writer::new(writer));
+        if !block_given() {
+            return Ok(*writer);
+        }
+
+        match yield_value::<_, Value>(writer) {
+            Ok(result) => {
+                writer.get().close()?;
+                Ok(result)
+            }
+            Err(err) => {
+                // The error raised by block is more useful than the abort one.
+                let _ = writer.get().abort();
+                Err(err)
+            }
+        }
+    }
+
+    /// Read the whole path in background, returns a [`Task`] whose value
+    /// is the content.
+    pub fn read_async(&self, path: String) -> Task {
+        let op = self.core.clone();
+        Task::spawn(async move { op.read(&path).await.map(Output::Bytes) })
+    }
+
+    /// Write string into given path in background, returns a [`Task`].
+    pub fn write_async(&self, path: String, bs: RString) -> Task {
+        let op = self.core.clone();
+        let bs = bs.to_bytes();
+        Task::spawn(async move { op.write(&path, bs).await.map(|_| Output::Nil) })
+    }
+
+    /// Stat given path in background, returns a [`Task`] whose value is
+    /// the metadata.
+    pub fn stat_async(&self, path: String) -> Task {
+        let op = self.core.clone();
+        Task::spawn(async move { op.stat(&path).await.map(Output::Metadata) })
+    }
+
+    /// Create a dir at given path in background, returns a [`Task`].
+    pub fn create_dir_async(&self, path: String) -> Task {
+        let op = self.core.clone();
+        Task::spawn(async move { op.create_dir(&path).await.map(|_| Output::Nil) })
+    }
+
+    /// Delete given path in background, returns a [`Task`].
+    pub fn delete_async(&self, path: String) -> Task {
+        let op = self.core.clone();
+        Task::spawn(async move { op.delete(&path).await.map(|_| Output::Nil) })
+    }
+
+    /// Get a presigned request for read, `expire` is in seconds.
+    pub fn presign_read(&self, path: String, expire: u64) -> Result<PresignedRequest> {
+        RUNTIME
+            .block_on(self.core.presign_read(&path, Duration::from_secs(expire)))
+            .map_err(format_magnus_error)
+            .map(PresignedRequest)
+    }
+
+    /// Get a presigned request for write, `expire` is in seconds.
+    pub fn presign_write(&self, path: String, expire: u64) -> Result<PresignedRequest> {
+        RUNTIME
+            .block_on(self.core.presign_write(&path, Duration::from_secs(expire)))
+            .map_err(format_magnus_error)
+            .map(PresignedRequest)
+    }
+
+    /// Get a presigned request for stat, `expire` is in seconds.
+    pub fn presign_stat(&self, path: String, expire: u64) -> Result<PresignedRequest> {
+        RUNTIME
+            .block_on(self.core.presign_stat(&path, Duration::from_secs(expire)))
+            .map_err(format_magnus_error)
+            .map(PresignedRequest)
+    }
 }
 
 #[magnus::wrap(class = "OpenDAL::Metadata", free_immediately, size)]
@@ -149,6 +274,11 @@ impl Metadata {
         self.0.etag()
     }
 
+    /// Last modified time of this entry in seconds since unix epoch.
+    pub fn last_modified(&self) -> Option<i64> {
+        self.0.last_modified().map(|t| t.timestamp())
+    }
+
     /// Returns `True` if this is a file.
     pub fn is_file(&self) -> bool {
         self.0.is_file()
@@ -160,18 +290,109 @@ impl Metadata {
     }
 }
 
+#[magnus::wrap(class = "OpenDAL::PresignedRequest", free_immediately, size)]
+pub struct PresignedRequest(od::raw::PresignedRequest);
+
+impl PresignedRequest {
+    /// HTTP method of this request.
+    pub fn method(&self) -> String {
+        self.0.method().to_string()
+    }
+
+    /// URI of this request.
+    pub fn uri(&self) -> String {
+        self.0.uri().to_string()
+    }
+
+    /// HTTP headers of this request.
+    pub fn headers(&self) -> Result<HashMap<String, String>> {
+        let mut headers = HashMap::new();
+        for (k, v) in self.0.header().iter() {
+            let v = v.to_str().map_err(|err| {
+                Error::new(
+                    exception::runtime_error(),
+                    format!("header {k} is not visible ascii: {err}"),
+                )
+            })?;
+            headers.insert(k.to_string(), v.to_string());
+        }
+        Ok(headers)
+    }
+}
+
+/// The name of the ruby exception class mapped from [`od::ErrorKind`].
+fn error_class_name(kind: od::ErrorKind) -> &'static str {
+    match kind {
+        od::ErrorKind::Unsupported => "UnsupportedError",
+        od::ErrorKind::ConfigInvalid => "ConfigInvalidError",
+        od::ErrorKind::NotFound => "NotFoundError",
+        od::ErrorKind::PermissionDenied => "PermissionDeniedError",
+        od::ErrorKind::IsADirectory => "IsADirectoryError",
+        od::ErrorKind::NotADirectory => "NotADirectoryError",
+        od::ErrorKind::AlreadyExists => "AlreadyExistsError",
+        od::ErrorKind::RateLimited => "RateLimitedError",
+        od::ErrorKind::IsSameFile => "IsSameFileError",
+        od::ErrorKind::PreconditionFailed => "PreconditionFailedError",
+        _ => "UnexpectedError",
+    }
+}
+
 fn format_magnus_error(err: od::Error) -> Error {
-    Error::new(exception::runtime_error(), err.to_string())
+    new_magnus_error(err.kind(), err.to_string())
+}
+
+/// Create the ruby exception of given kind with `msg`.
+fn new_magnus_error(kind: od::ErrorKind, msg: String) -> Error {
+    let class = class::object()
+        .const_get::<_, RModule>("OpenDAL")
+        .and_then(|m| m.const_get::<_, ExceptionClass>(error_class_name(kind)));
+    match class {
+        Ok(class) => Error::new(class, msg),
+        Err(_) => Error::new(exception::runtime_error(), msg),
+    }
 }
 
 #[magnus::init]
 fn init() -> Result<()> {
     let namespace = define_module("OpenDAL")?;
+
+    let error_class = namespace.define_error("Error", exception::standard_error())?;
+    for kind in [
+        od::ErrorKind::Unexpected,
+        od::ErrorKind::Unsupported,
+        od::ErrorKind::ConfigInvalid,
+        od::ErrorKind::NotFound,
+        od::ErrorKind::PermissionDenied,
+        od::ErrorKind::IsADirectory,
+        od::ErrorKind::NotADirectory,
+        od::ErrorKind::AlreadyExists,
+        od::ErrorKind::RateLimited,
+        od::ErrorKind::IsSameFile,
+        od::ErrorKind::PreconditionFailed,
+    ] {
+        namespace.define_error(error_class_name(kind), error_class)?;
+    }
+
     let operator_class = namespace.define_class("Operator", class::object())?;
     operator_class.define_singleton_method("new", function!(Operator::new, 2))?;
+    operator_class.define_method("layer", method!(Operator::layer, 1))?;
     operator_class.define_method("read", method!(Operator::read, 1))?;
     operator_class.define_method("write", method!(Operator::write, 2))?;
     operator_class.define_method("stat", method!(Operator::stat, 1))?;
+    operator_class.define_method("create_dir", method!(Operator::create_dir, 1))?;
+    operator_class.define_method("delete", method!(Operator::delete, 1))?;
+    operator_class.define_method("list", method!(Operator::list, 1))?;
+    operator_class.define_method("scan", method!(Operator::scan, 1))?;
+    operator_class.define_method("reader", method!(Operator::reader, 1))?;
+    operator_class.define_method("writer", method!(Operator::writer, 1))?;
+    operator_class.define_method("read_async", method!(Operator::read_async, 1))?;
+    operator_class.define_method("write_async", method!(Operator::write_async, 2))?;
+    operator_class.define_method("stat_async", method!(Operator::stat_async, 1))?;
+    operator_class.define_method("create_dir_async", method!(Operator::create_dir_async, 1))?;
+    operator_class.define_method("delete_async", method!(Operator::delete_async, 1))?;
+    operator_class.define_method("presign_read", method!(Operator::presign_read, 2))?;
+    operator_class.define_method("presign_write", method!(Operator::presign_write, 2))?;
+    operator_class.define_method("presign_stat", method!(Operator::presign_stat, 2))?;
 
     let metadata_class = namespace.define_class("Metadata", class::object())?;
     metadata_class.define_method(
@@ -182,7 +403,18 @@ fn init() -> Result<()> {
     metadata_class.define_method("content_md5", method!(Metadata::content_md5, 0))?;
     metadata_class.define_method("content_type", method!(Metadata::content_type, 0))?;
     metadata_class.define_method("etag", method!(Metadata::etag, 0))?;
+    metadata_class.define_method("last_modified", method!(Metadata::last_modified, 0))?;
     metadata_class.define_method("is_file", method!(Metadata::is_file, 0))?;
     metadata_class.define_method("is_dir", method!(Metadata::is_dir, 0))?;
+
+    let presigned_request_class = namespace.define_class("PresignedRequest", class::object())?;
+    presigned_request_class.define_method("method", method!(PresignedRequest::method, 0))?;
+    presigned_request_class.define_method("uri", method!(PresignedRequest::uri, 0))?;
+    presigned_request_class.define_method("headers", method!(PresignedRequest::headers, 0))?;
+
+    lister::init(namespace)?;
+    io::init(namespace)?;
+    layers::init(namespace)?;
+    task::init(namespace)?;
     Ok(())
 }
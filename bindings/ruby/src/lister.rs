@@ -0,0 +1,97 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cell::RefCell;
+
+use magnus::block::block_given;
+use magnus::block::yield_value;
+use magnus::class;
+use magnus::error::Result;
+use magnus::method;
+use magnus::module;
+use magnus::prelude::*;
+use magnus::typed_data::Obj;
+use magnus::RModule;
+use magnus::Value;
+use opendal as od;
+
+use crate::format_magnus_error;
+
+/// Lister yields entries of a dir, it includes `Enumerable` so the entries
+/// can be consumed with `each`, `map`, `to_a` and so on.
+///
+/// A lister can only be iterated once.
+#[magnus::wrap(class = "OpenDAL::Lister", free_immediately, size)]
+pub struct Lister(RefCell<od::BlockingLister>);
+
+/// `BlockingLister` is not `Send` only because `BlockingPage` doesn't require
+/// it, and ruby only touches the lister while holding the GVL.
+unsafe impl Send for Lister {}
+
+impl Lister {
+    pub fn new(lister: od::BlockingLister) -> Self {
+        Lister(RefCell::new(lister))
+    }
+
+    /// Yield every entry to the given block, returns an enumerator if no
+    /// block is given.
+    pub fn each(rb_self: Obj<Self>) -> Result<Value> {
+        if !block_given() {
+            return Ok(*rb_self.enumeratorize("each", ()));
+        }
+
+        loop {
+            // Release the borrow before yielding, the block could touch
+            // this lister again.
+            let next = rb_self.get().0.borrow_mut().next();
+            match next {
+                Some(entry) => {
+                    let entry = entry.map_err(format_magnus_error)?;
+                    let _: Value = yield_value(Entry(entry))?;
+                }
+                None => break,
+            }
+        }
+        Ok(*rb_self)
+    }
+}
+
+#[magnus::wrap(class = "OpenDAL::Entry", free_immediately, size)]
+pub struct Entry(od::Entry);
+
+impl Entry {
+    /// Path of this entry.
+    pub fn path(&self) -> &str {
+        self.0.path()
+    }
+
+    /// Name of this entry.
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+}
+
+pub fn init(namespace: RModule) -> Result<()> {
+    let lister_class = namespace.define_class("Lister", class::object())?;
+    lister_class.include_module(module::enumerable())?;
+    lister_class.define_method("each", method!(Lister::each, 0))?;
+
+    let entry_class = namespace.define_class("Entry", class::object())?;
+    entry_class.define_method("path", method!(Entry::path, 0))?;
+    entry_class.define_method("name", method!(Entry::name, 0))?;
+    Ok(())
+}
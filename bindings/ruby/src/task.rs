@@ -0,0 +1,101 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cell::RefCell;
+use std::future::Future;
+
+use magnus::class;
+use magnus::error::Result;
+use magnus::method;
+use magnus::prelude::*;
+use magnus::IntoValue;
+use magnus::RModule;
+use magnus::RString;
+use magnus::Value;
+use opendal as od;
+use tokio::task::JoinHandle;
+
+use crate::new_magnus_error;
+use crate::Metadata;
+use crate::RUNTIME;
+
+/// Output of the operations that could be spawned as a [`Task`].
+#[derive(Clone)]
+pub enum Output {
+    Nil,
+    Bytes(Vec<u8>),
+    Metadata(od::Metadata),
+}
+
+enum State {
+    Running(JoinHandle<od::Result<Output>>),
+    // `od::Error` is not `Clone`, so only its kind and message are kept to
+    // raise the same error every time.
+    Done(std::result::Result<Output, (od::ErrorKind, String)>),
+}
+
+/// Task is the handle of an operation running on the background runtime,
+/// which is returned by the `*_async` methods of operator.
+#[magnus::wrap(class = "OpenDAL::Task", free_immediately, size)]
+pub struct Task(RefCell<State>);
+
+impl Task {
+    pub fn spawn<F>(fut: F) -> Self
+    where
+        F: Future<Output = od::Result<Output>> + Send + 'static,
+    {
+        Task(RefCell::new(State::Running(RUNTIME.spawn(fut))))
+    }
+
+    /// Wait for the operation to finish and return its result like
+    /// `Thread#value`, the error of the operation is raised.
+    pub fn value(&self) -> Result<Value> {
+        let mut state = self.0.borrow_mut();
+        if let State::Running(handle) = &mut *state {
+            let res = match RUNTIME.block_on(handle) {
+                Ok(res) => res,
+                Err(err) => {
+                    Err(od::Error::new(od::ErrorKind::Unexpected, "task failed").set_source(err))
+                }
+            };
+            *state = State::Done(res.map_err(|err| (err.kind(), err.to_string())));
+        }
+
+        match &*state {
+            State::Done(Ok(Output::Nil)) => Ok(().into_value()),
+            State::Done(Ok(Output::Bytes(bs))) => Ok(RString::from_slice(bs).into_value()),
+            State::Done(Ok(Output::Metadata(meta))) => Ok(Metadata(meta.clone()).into_value()),
+            State::Done(Err((kind, msg))) => Err(new_magnus_error(*kind, msg.clone())),
+            State::Running(_) => unreachable!("task must be done after waiting"),
+        }
+    }
+
+    /// Returns `true` if the operation has finished.
+    pub fn is_done(&self) -> bool {
+        match &*self.0.borrow() {
+            State::Running(handle) => handle.is_finished(),
+            State::Done(_) => true,
+        }
+    }
+}
+
+pub fn init(namespace: RModule) -> Result<()> {
+    let task_class = namespace.define_class("Task", class::object())?;
+    task_class.define_method("value", method!(Task::value, 0))?;
+    task_class.define_method("done?", method!(Task::is_done, 0))?;
+    Ok(())
+}
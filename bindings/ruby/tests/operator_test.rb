@@ -0,0 +1,135 @@
+# Licensed to the Apache Software Foundation (ASF) under one
+# or more contributor license agreements.  See the NOTICE file
+# distributed with this work for additional information
+# regarding copyright ownership.  The ASF licenses this file
+# to you under the Apache License, Version 2.0 (the
+# "License"); you may not use this file except in compliance
+# with the License.  You may obtain a copy of the License at
+#
+#   http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing,
+# software distributed under the License is distributed on an
+# "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+# KIND, either express or implied.  See the License for the
+# specific language governing permissions and limitations
+# under the License.
+
+# frozen_string_literal: true
+
+require "fileutils"
+require "tmpdir"
+require "minitest/autorun"
+require_relative "../lib/opendal"
+
+class OperatorTest < Minitest::Test
+  def setup
+    @op = OpenDAL::Operator.new("memory", nil)
+    @op.write("/dir/file", "Hello, World!")
+  end
+
+  def test_create_dir_and_delete
+    @op.create_dir("/empty/")
+    assert @op.stat("/empty/").is_dir
+
+    @op.delete("/dir/file")
+    assert_raises(OpenDAL::NotFoundError) { @op.stat("/dir/file") }
+  end
+
+  def test_error_classes
+    error = assert_raises(OpenDAL::NotFoundError) { @op.read("/not_exist") }
+    assert_kind_of OpenDAL::Error, error
+    assert_kind_of StandardError, error
+  end
+
+  def test_list_and_scan
+    @op.write("/dir/sub/file", "nested")
+
+    assert_equal ["dir/file", "dir/sub/"], @op.list("/dir/").map(&:path).sort
+    assert_includes @op.scan("/dir/").map(&:path), "dir/sub/file"
+    assert_kind_of Enumerator, @op.list("/dir/").each
+  end
+
+  def test_reader
+    reader = @op.reader("/dir/file")
+    assert_equal "Hello", reader.read(5)
+    assert_equal 5, reader.pos
+
+    reader.seek(-6, IO::SEEK_END)
+    assert_equal "World!", reader.read
+    assert_nil reader.read(1)
+    assert_equal "", reader.read
+
+    reader.seek(0)
+    assert_equal ["Hello", ", Wor", "ld!"], reader.each_chunk(5).to_a
+
+    reader.close
+    assert reader.closed?
+    assert_raises(IOError) { reader.read }
+  end
+
+  def test_writer
+    writer = @op.writer("/dir/written")
+    assert_equal 6, writer.write("Hello,")
+    writer.write(" World!")
+    writer.close
+    assert_equal "Hello, World!", @op.read("/dir/written")
+  end
+
+  def test_writer_block
+    result = @op.writer("/dir/block") do |writer|
+      writer.write("in block")
+      :done
+    end
+    assert_equal :done, result
+    assert_equal "in block", @op.read("/dir/block")
+
+    assert_raises(RuntimeError) do
+      @op.writer("/dir/aborted") do |writer|
+        writer.write("discarded")
+        raise "abort"
+      end
+    end
+    assert_raises(OpenDAL::NotFoundError) { @op.stat("/dir/aborted") }
+
+    writer = @op.writer("/dir/manual")
+    writer.write("discarded")
+    writer.abort
+    assert writer.closed?
+    assert_raises(OpenDAL::NotFoundError) { @op.stat("/dir/manual") }
+  end
+
+  def test_async
+    task = @op.write_async("/dir/async", "async content")
+    assert_nil task.value
+    assert task.done?
+
+    assert_equal "async content", @op.read_async("/dir/async").value
+    assert_equal 13, @op.stat_async("/dir/async").value.content_length
+
+    @op.delete_async("/dir/async").value
+    task = @op.read_async("/dir/async")
+    2.times { assert_raises(OpenDAL::NotFoundError) { task.value } }
+  end
+
+  def test_layer
+    op = @op
+      .layer(OpenDAL::Layers::RetryLayer.new(max_times: 3, jitter: true, min_delay: 0.1))
+      .layer(OpenDAL::Layers::ConcurrentLimitLayer.new(4))
+      .layer(OpenDAL::Layers::LoggingLayer.new)
+    assert_equal "Hello, World!", op.read("/dir/file")
+
+    assert_raises(TypeError) { @op.layer("retry") }
+  end
+
+  def test_fs
+    root = File.join(Dir.tmpdir, "opendal-ruby-test")
+    op = OpenDAL::Operator.new("fs", {"root" => root})
+    op.write("file", "fs content")
+    assert_equal "fs content", op.read("file")
+    assert_equal 10, op.stat("file").content_length
+    refute_nil op.stat("file").last_modified
+  ensure
+    FileUtils.rm_rf(root)
+  end
+end
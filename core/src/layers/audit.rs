@@ -0,0 +1,1197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::mem;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use bytes::BytesMut;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use log::warn;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Record structured audit logs for every operation into another [`Operator`].
+///
+/// # Records
+///
+/// Every audited operation will produce a record in [JSON Lines](https://jsonlines.org/)
+/// format like:
+///
+/// ```json
+/// {"timestamp":"2023-04-01T08:00:00.000Z","operation":"write","path":"path/to/file","size":4096,"result":"ok","principal":"alice"}
+/// ```
+///
+/// - `timestamp`: RFC 3339 time in UTC when the operation finished.
+/// - `operation`: name of [`Operation`].
+/// - `path`: path of the operation.
+/// - `target`: destination path of `copy` and `rename`.
+/// - `size`: bytes read or written if known.
+/// - `result`: `ok`, `error`, `aborted` or `dropped` (for writers dropped
+///   without `close` or `abort`).
+/// - `error_kind`: [`ErrorKind`] of the failed operation.
+/// - `principal`: caller supplied principal, see [`AuditLayer::with_principal`].
+///
+/// Writes are recorded after `close` (or `abort`) so that `size` contains
+/// all bytes written by this writer.
+///
+/// # Flush
+///
+/// Records are buffered in memory and flushed as a new object (rolling
+/// object) under [`AuditLayer::with_prefix`] in the target operator when:
+///
+/// - buffered records reach [`AuditLayer::with_batch_size`], or
+/// - the last flush is older than [`AuditLayer::with_flush_interval`].
+///
+/// Flush happens on the operation that triggers it. If the layer is applied
+/// inside a tokio runtime, a background task will also flush records every
+/// flush interval, and flush the rest after the layer and all operators
+/// built from it have been dropped. Please call [`AuditLayer::flush`]
+/// before exit to make sure all records have been persisted.
+///
+/// # Backpressure
+///
+/// If flush failed, records will be kept and retried by the next flush
+/// with the same rolling object, so records keep in order.
+/// Once buffered records reach [`AuditLayer::with_max_buffered`], audited
+/// operations will wait for a successful flush before calling the
+/// underlying service, and return the flush error if it failed. So no
+/// operation will be performed without being audited.
+///
+/// Blocking operations can only be flushed if the target operator
+/// supports blocking.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::AuditLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let target = Operator::new(services::Memory::default())?.finish();
+/// let audit = AuditLayer::new(target)
+///     .with_principal("alice")
+///     .with_prefix("audit/");
+///
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(audit.clone())
+///     .finish();
+/// op.write("test", "Hello, World!").await?;
+///
+/// // Make sure all records have been persisted.
+/// audit.flush().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AuditLayer {
+    core: AuditCore,
+}
+
+impl AuditLayer {
+    /// Create a new audit layer which will flush records into `target`.
+    ///
+    /// By default, only operations that mutate data are audited: `create_dir`,
+    /// `write`, `copy`, `rename`, `delete`, `batch` and their blocking
+    /// versions.
+    pub fn new(target: Operator) -> Self {
+        Self {
+            core: AuditCore {
+                target,
+                principal: None,
+                prefix: "audit/".to_string(),
+                operations: Arc::new(HashSet::from([
+                    Operation::CreateDir,
+                    Operation::Write,
+                    Operation::Copy,
+                    Operation::Rename,
+                    Operation::Delete,
+                    Operation::Batch,
+                    Operation::BlockingCreateDir,
+                    Operation::BlockingWrite,
+                    Operation::BlockingCopy,
+                    Operation::BlockingMove,
+                    Operation::BlockingDelete,
+                ])),
+                batch_size: 1024,
+                max_buffered: 64 * 1024,
+                flush_interval: Duration::from_secs(60),
+                state: Arc::new(AuditState::default()),
+            },
+        }
+    }
+
+    /// Set the principal that will be recorded in every record.
+    pub fn with_principal(mut self, principal: &str) -> Self {
+        self.core.principal = Some(principal.to_string());
+        self
+    }
+
+    /// Set the prefix of rolling objects in target operator.
+    ///
+    /// Default to `audit/`.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.core.prefix = prefix.to_string();
+        self
+    }
+
+    /// Set the operations that need to be audited.
+    pub fn with_operations(mut self, operations: impl IntoIterator<Item = Operation>) -> Self {
+        self.core.operations = Arc::new(operations.into_iter().collect());
+        self
+    }
+
+    /// Set the number of records that will trigger a flush.
+    ///
+    /// Default to `1024`.
+    ///
+    /// # Panics
+    ///
+    /// Input batch_size must be larger than 0.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be larger than 0");
+
+        self.core.batch_size = batch_size;
+        self
+    }
+
+    /// Set the max number of records that can be buffered before
+    /// operations are blocked by flush.
+    ///
+    /// Default to `65536`.
+    ///
+    /// # Panics
+    ///
+    /// Input max_buffered must be larger than 0.
+    pub fn with_max_buffered(mut self, max_buffered: usize) -> Self {
+        assert!(max_buffered > 0, "max_buffered must be larger than 0");
+
+        self.core.max_buffered = max_buffered;
+        self
+    }
+
+    /// Set the interval after which buffered records will be flushed by
+    /// the next audited operation.
+    ///
+    /// Default to `60s`.
+    pub fn with_flush_interval(mut self, interval: Duration) -> Self {
+        self.core.flush_interval = interval;
+        self
+    }
+
+    /// Flush all buffered records into target operator.
+    ///
+    /// Records are shared between this layer and all operators built from
+    /// it, so please keep a clone of this layer to flush before exit.
+    pub async fn flush(&self) -> Result<()> {
+        self.core.flush().await
+    }
+
+    /// Flush all buffered records into target operator in blocking way.
+    pub fn blocking_flush(&self) -> Result<()> {
+        self.core.blocking_flush()
+    }
+}
+
+impl<A: Accessor> Layer<A> for AuditLayer {
+    type LayeredAccessor = AuditAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        self.core.spawn_flusher();
+
+        AuditAccessor {
+            inner,
+            core: self.core.clone(),
+        }
+    }
+}
+
+/// AuditRecord is a line in the rolling objects.
+#[derive(Debug, Serialize)]
+struct AuditRecord {
+    timestamp: String,
+    operation: &'static str,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    result: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    principal: Option<String>,
+}
+
+/// Outcome of an audited operation.
+#[derive(Clone, Copy)]
+enum Outcome<'a> {
+    Ok,
+    /// Writer has been aborted successfully.
+    Aborted,
+    /// Writer has been dropped without close or abort.
+    Dropped,
+    Err(&'a Error),
+}
+
+impl<'a> Outcome<'a> {
+    fn of<T>(res: &'a Result<T>) -> Self {
+        match res {
+            Ok(_) => Outcome::Ok,
+            Err(e) => Outcome::Err(e),
+        }
+    }
+
+    fn result(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Aborted => "aborted",
+            Outcome::Dropped => "dropped",
+            Outcome::Err(_) => "error",
+        }
+    }
+
+    fn error_kind(&self) -> Option<&'static str> {
+        match self {
+            Outcome::Err(e) => Some(e.kind().into_static()),
+            _ => None,
+        }
+    }
+}
+
+/// RollingObject is a batch of records that has been assigned a path.
+struct RollingObject {
+    path: String,
+    records: usize,
+    content: Bytes,
+}
+
+#[derive(Default)]
+struct AuditState {
+    records: Mutex<Vec<AuditRecord>>,
+    /// Rolling objects failed to flush. They will be retried with the same
+    /// path so that records keep in order across rolling objects.
+    pending: Mutex<Vec<RollingObject>>,
+    last_flush: Mutex<Option<Instant>>,
+    seq: AtomicU64,
+    /// Whether the background flusher has been spawned.
+    flusher: AtomicBool,
+}
+
+#[derive(Clone)]
+struct AuditCore {
+    target: Operator,
+    principal: Option<String>,
+    prefix: String,
+    operations: Arc<HashSet<Operation>>,
+    batch_size: usize,
+    max_buffered: usize,
+    flush_interval: Duration,
+
+    state: Arc<AuditState>,
+}
+
+impl AuditCore {
+    fn is_audited(&self, op: Operation) -> bool {
+        self.operations.contains(&op)
+    }
+
+    fn is_full(&self) -> bool {
+        let pending: usize = self.state.pending.lock().iter().map(|v| v.records).sum();
+        self.state.records.lock().len() + pending >= self.max_buffered
+    }
+
+    /// Spawn a task to flush records every flush interval if we are inside
+    /// a tokio runtime.
+    ///
+    /// The task holds a clone of the core, and exits after it's the only
+    /// one left, which means the layer and all operators built from it
+    /// have been dropped.
+    fn spawn_flusher(&self) {
+        let handle = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle,
+            Err(_) => return,
+        };
+        if self.state.flusher.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let core = self.clone();
+        handle.spawn(async move {
+            loop {
+                tokio::time::sleep(core.flush_interval).await;
+
+                let is_last = Arc::strong_count(&core.state) == 1;
+                if let Err(e) = core.flush().await {
+                    warn!(target: "opendal::layers::audit", "flush audit records failed: {e:?}");
+                }
+                if is_last {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn should_flush(&self) -> bool {
+        if self.state.records.lock().len() >= self.batch_size {
+            return true;
+        }
+
+        let mut last_flush = self.state.last_flush.lock();
+        match *last_flush {
+            Some(v) => v.elapsed() >= self.flush_interval,
+            None => {
+                // Start the interval from the first record.
+                *last_flush = Some(Instant::now());
+                false
+            }
+        }
+    }
+
+    fn push(
+        &self,
+        op: Operation,
+        path: &str,
+        target: Option<&str>,
+        size: Option<u64>,
+        outcome: Outcome<'_>,
+    ) {
+        let record = AuditRecord {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            operation: op.into_static(),
+            path: path.to_string(),
+            target: target.map(|v| v.to_string()),
+            size,
+            result: outcome.result(),
+            error_kind: outcome.error_kind(),
+            principal: self.principal.clone(),
+        };
+
+        self.state.records.lock().push(record);
+    }
+
+    /// Make sure there is room for new records before performing an
+    /// audited operation.
+    async fn ready(&self, op: Operation) -> Result<()> {
+        if self.is_audited(op) && self.is_full() {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    fn blocking_ready(&self, op: Operation) -> Result<()> {
+        if self.is_audited(op) && self.is_full() {
+            self.blocking_flush()?;
+        }
+        Ok(())
+    }
+
+    async fn record(
+        &self,
+        op: Operation,
+        path: &str,
+        target: Option<&str>,
+        size: Option<u64>,
+        outcome: Outcome<'_>,
+    ) {
+        if !self.is_audited(op) {
+            return;
+        }
+
+        self.push(op, path, target, size, outcome);
+        if self.should_flush() {
+            if let Err(e) = self.flush().await {
+                warn!(target: "opendal::layers::audit", "flush audit records failed: {e:?}");
+            }
+        }
+    }
+
+    fn blocking_record(
+        &self,
+        op: Operation,
+        path: &str,
+        target: Option<&str>,
+        size: Option<u64>,
+        outcome: Outcome<'_>,
+    ) {
+        if !self.is_audited(op) {
+            return;
+        }
+
+        self.push(op, path, target, size, outcome);
+        if self.target.info().can_blocking() && self.should_flush() {
+            if let Err(e) = self.blocking_flush() {
+                warn!(target: "opendal::layers::audit", "flush audit records failed: {e:?}");
+            }
+        }
+    }
+
+    /// Take all pending rolling objects and build a new one from buffered
+    /// records.
+    fn take(&self) -> Result<Vec<RollingObject>> {
+        let records = mem::take(&mut *self.state.records.lock());
+        *self.state.last_flush.lock() = Some(Instant::now());
+
+        let mut objects = mem::take(&mut *self.state.pending.lock());
+        if records.is_empty() {
+            return Ok(objects);
+        }
+
+        let mut buf = BytesMut::new();
+        for record in &records {
+            let line = match serde_json::to_vec(record) {
+                Ok(v) => v,
+                Err(e) => {
+                    self.restore(records, objects);
+                    return Err(
+                        Error::new(ErrorKind::Unexpected, "serialize audit record failed")
+                            .set_source(e),
+                    );
+                }
+            };
+            buf.extend_from_slice(&line);
+            buf.extend_from_slice(b"\n");
+        }
+
+        let path = format!(
+            "{}{}-{:08}-{}.jsonl",
+            self.prefix,
+            Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+            self.state.seq.fetch_add(1, Ordering::Relaxed),
+            uuid::Uuid::new_v4()
+        );
+        objects.push(RollingObject {
+            path,
+            records: records.len(),
+            content: buf.freeze(),
+        });
+        Ok(objects)
+    }
+
+    /// Put records back to the front of buffer and objects back to pending
+    /// so that they will be flushed in order next time.
+    fn restore(&self, mut records: Vec<AuditRecord>, objects: Vec<RollingObject>) {
+        if !records.is_empty() {
+            let mut buffered = self.state.records.lock();
+            records.append(&mut buffered);
+            *buffered = records;
+        }
+
+        if !objects.is_empty() {
+            let mut pending = self.state.pending.lock();
+            pending.extend(objects);
+            pending.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let mut failed = Vec::new();
+        let mut res = Ok(());
+        for object in self.take()? {
+            // Keep the following objects if one failed so that they will be
+            // retried in order.
+            if res.is_err() {
+                failed.push(object);
+                continue;
+            }
+            if let Err(e) = self
+                .target
+                .write(&object.path, object.content.clone())
+                .await
+            {
+                failed.push(object);
+                res = Err(e);
+            }
+        }
+        self.restore(Vec::new(), failed);
+        res
+    }
+
+    fn blocking_flush(&self) -> Result<()> {
+        let op = self.target.blocking();
+
+        let mut failed = Vec::new();
+        let mut res = Ok(());
+        for object in self.take()? {
+            if res.is_err() {
+                failed.push(object);
+                continue;
+            }
+            if let Err(e) = op.write(&object.path, object.content.clone()) {
+                failed.push(object);
+                res = Err(e);
+            }
+        }
+        self.restore(Vec::new(), failed);
+        res
+    }
+}
+
+pub struct AuditAccessor<A: Accessor> {
+    inner: A,
+    core: AuditCore,
+}
+
+impl<A: Accessor> Debug for AuditAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for AuditAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = AuditWriter<A::Writer>;
+    type BlockingWriter = AuditWriter<A::BlockingWriter>;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.core.ready(Operation::CreateDir).await?;
+
+        let res = self.inner.create_dir(path, args).await;
+        self.core
+            .record(Operation::CreateDir, path, None, None, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.core.ready(Operation::Read).await?;
+
+        let res = self.inner.read(path, args).await;
+        let size = res
+            .as_ref()
+            .ok()
+            .and_then(|(rp, _)| rp.metadata().content_length_raw());
+        self.core
+            .record(Operation::Read, path, None, size, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.core.ready(Operation::Write).await?;
+
+        match self.inner.write(path, args).await {
+            Ok((rp, w)) => Ok((
+                rp,
+                AuditWriter::new(w, self.core.clone(), Operation::Write, path),
+            )),
+            Err(e) => {
+                self.core
+                    .record(Operation::Write, path, None, None, Outcome::Err(&e))
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.core.ready(Operation::Copy).await?;
+
+        let res = self.inner.copy(from, to, args).await;
+        self.core
+            .record(Operation::Copy, from, Some(to), None, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.core.ready(Operation::Rename).await?;
+
+        let res = self.inner.rename(from, to, args).await;
+        self.core
+            .record(Operation::Rename, from, Some(to), None, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.core.ready(Operation::Stat).await?;
+
+        let res = self.inner.stat(path, args).await;
+        self.core
+            .record(Operation::Stat, path, None, None, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.core.ready(Operation::Delete).await?;
+
+        let res = self.inner.delete(path, args).await;
+        self.core
+            .record(Operation::Delete, path, None, None, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.core.ready(Operation::List).await?;
+
+        let res = self.inner.list(path, args).await;
+        self.core
+            .record(Operation::List, path, None, None, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.core.ready(Operation::Scan).await?;
+
+        let res = self.inner.scan(path, args).await;
+        self.core
+            .record(Operation::Scan, path, None, None, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.core.ready(Operation::Batch).await?;
+
+        let paths: Vec<String> = args
+            .operation()
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect();
+        let res = self.inner.batch(args).await;
+        match &res {
+            Ok(rp) => {
+                for (path, result) in rp.results() {
+                    self.core
+                        .record(Operation::Batch, path, None, None, Outcome::of(result))
+                        .await;
+                }
+            }
+            Err(e) => {
+                for path in &paths {
+                    self.core
+                        .record(Operation::Batch, path, None, None, Outcome::Err(e))
+                        .await;
+                }
+            }
+        }
+        res
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.core.ready(Operation::Presign).await?;
+
+        let res = self.inner.presign(path, args).await;
+        self.core
+            .record(Operation::Presign, path, None, None, Outcome::of(&res))
+            .await;
+        res
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.core.blocking_ready(Operation::BlockingCreateDir)?;
+
+        let res = self.inner.blocking_create_dir(path, args);
+        self.core.blocking_record(
+            Operation::BlockingCreateDir,
+            path,
+            None,
+            None,
+            Outcome::of(&res),
+        );
+        res
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.core.blocking_ready(Operation::BlockingRead)?;
+
+        let res = self.inner.blocking_read(path, args);
+        let size = res
+            .as_ref()
+            .ok()
+            .and_then(|(rp, _)| rp.metadata().content_length_raw());
+        self.core
+            .blocking_record(Operation::BlockingRead, path, None, size, Outcome::of(&res));
+        res
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.core.blocking_ready(Operation::BlockingWrite)?;
+
+        match self.inner.blocking_write(path, args) {
+            Ok((rp, w)) => Ok((
+                rp,
+                AuditWriter::new(w, self.core.clone(), Operation::BlockingWrite, path),
+            )),
+            Err(e) => {
+                self.core.blocking_record(
+                    Operation::BlockingWrite,
+                    path,
+                    None,
+                    None,
+                    Outcome::Err(&e),
+                );
+                Err(e)
+            }
+        }
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.core.blocking_ready(Operation::BlockingCopy)?;
+
+        let res = self.inner.blocking_copy(from, to, args);
+        self.core.blocking_record(
+            Operation::BlockingCopy,
+            from,
+            Some(to),
+            None,
+            Outcome::of(&res),
+        );
+        res
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.core.blocking_ready(Operation::BlockingMove)?;
+
+        let res = self.inner.blocking_rename(from, to, args);
+        self.core.blocking_record(
+            Operation::BlockingMove,
+            from,
+            Some(to),
+            None,
+            Outcome::of(&res),
+        );
+        res
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.core.blocking_ready(Operation::BlockingStat)?;
+
+        let res = self.inner.blocking_stat(path, args);
+        self.core
+            .blocking_record(Operation::BlockingStat, path, None, None, Outcome::of(&res));
+        res
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.core.blocking_ready(Operation::BlockingDelete)?;
+
+        let res = self.inner.blocking_delete(path, args);
+        self.core.blocking_record(
+            Operation::BlockingDelete,
+            path,
+            None,
+            None,
+            Outcome::of(&res),
+        );
+        res
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.core.blocking_ready(Operation::BlockingList)?;
+
+        let res = self.inner.blocking_list(path, args);
+        self.core
+            .blocking_record(Operation::BlockingList, path, None, None, Outcome::of(&res));
+        res
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.core.blocking_ready(Operation::BlockingScan)?;
+
+        let res = self.inner.blocking_scan(path, args);
+        self.core
+            .blocking_record(Operation::BlockingScan, path, None, None, Outcome::of(&res));
+        res
+    }
+}
+
+/// AuditWriter will record the write once after it has been failed,
+/// closed, aborted or dropped.
+pub struct AuditWriter<W> {
+    inner: W,
+    core: AuditCore,
+    op: Operation,
+    path: String,
+    written: u64,
+    /// Whether the write has been recorded by a failed write, close or
+    /// abort. Following calls won't record it again.
+    finished: bool,
+}
+
+impl<W> AuditWriter<W> {
+    fn new(inner: W, core: AuditCore, op: Operation, path: &str) -> Self {
+        Self {
+            inner,
+            core,
+            op,
+            path: path.to_string(),
+            written: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<W> Drop for AuditWriter<W> {
+    /// Record the writer dropped without close or abort. The record will be
+    /// flushed by following operations or the background flusher.
+    fn drop(&mut self) {
+        if !self.finished && self.core.is_audited(self.op) {
+            self.core.push(
+                self.op,
+                &self.path,
+                None,
+                Some(self.written),
+                Outcome::Dropped,
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for AuditWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+        match self.inner.write(bs).await {
+            Ok(()) => {
+                self.written += size;
+                Ok(())
+            }
+            Err(e) => {
+                if !self.finished {
+                    self.finished = true;
+                    self.core
+                        .record(
+                            self.op,
+                            &self.path,
+                            None,
+                            Some(self.written),
+                            Outcome::Err(&e),
+                        )
+                        .await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+        match self.inner.append(bs).await {
+            Ok(()) => {
+                self.written += size;
+                Ok(())
+            }
+            Err(e) => {
+                if !self.finished {
+                    self.finished = true;
+                    self.core
+                        .record(
+                            self.op,
+                            &self.path,
+                            None,
+                            Some(self.written),
+                            Outcome::Err(&e),
+                        )
+                        .await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        let res = self.inner.abort().await;
+        if !self.finished {
+            self.finished = true;
+            let outcome = match &res {
+                Ok(()) => Outcome::Aborted,
+                Err(e) => Outcome::Err(e),
+            };
+            self.core
+                .record(self.op, &self.path, None, Some(self.written), outcome)
+                .await;
+        }
+        res
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        if !self.finished {
+            self.finished = true;
+            self.core
+                .record(
+                    self.op,
+                    &self.path,
+                    None,
+                    Some(self.written),
+                    Outcome::of(&res),
+                )
+                .await;
+        }
+        res
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for AuditWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+        match self.inner.write(bs) {
+            Ok(()) => {
+                self.written += size;
+                Ok(())
+            }
+            Err(e) => {
+                if !self.finished {
+                    self.finished = true;
+                    self.core.blocking_record(
+                        self.op,
+                        &self.path,
+                        None,
+                        Some(self.written),
+                        Outcome::Err(&e),
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn append(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+        match self.inner.append(bs) {
+            Ok(()) => {
+                self.written += size;
+                Ok(())
+            }
+            Err(e) => {
+                if !self.finished {
+                    self.finished = true;
+                    self.core.blocking_record(
+                        self.op,
+                        &self.path,
+                        None,
+                        Some(self.written),
+                        Outcome::Err(&e),
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        if !self.finished {
+            self.finished = true;
+            self.core.blocking_record(
+                self.op,
+                &self.path,
+                None,
+                Some(self.written),
+                Outcome::of(&res),
+            );
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::services::Memory;
+
+    async fn records(target: &Operator) -> Vec<Value> {
+        let mut paths: Vec<String> = target
+            .scan("audit/")
+            .await
+            .unwrap()
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        paths.sort();
+
+        let mut records = Vec::new();
+        for path in paths {
+            let bs = target.read(&path).await.unwrap();
+            for line in bs.split(|b| *b == b'\n').filter(|v| !v.is_empty()) {
+                records.push(serde_json::from_slice(line).unwrap());
+            }
+        }
+        records
+    }
+
+    #[tokio::test]
+    async fn test_audit_write() {
+        let target = Operator::new(Memory::default()).unwrap().finish();
+        let audit = AuditLayer::new(target.clone()).with_principal("alice");
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(audit.clone())
+            .finish();
+
+        op.write("test", "Hello, World!").await.unwrap();
+        op.read("test").await.unwrap();
+        op.delete("test").await.unwrap();
+
+        assert!(
+            records(&target).await.is_empty(),
+            "records must be buffered"
+        );
+        audit.flush().await.unwrap();
+
+        let records = records(&target).await;
+        assert_eq!(records.len(), 2, "read is not audited by default");
+        assert_eq!(records[0]["operation"], "write");
+        assert_eq!(records[0]["path"], "test");
+        assert_eq!(records[0]["size"], 13);
+        assert_eq!(records[0]["result"], "ok");
+        assert_eq!(records[0]["principal"], "alice");
+        assert_eq!(records[1]["operation"], "delete");
+    }
+
+    #[tokio::test]
+    async fn test_audit_batch_size() {
+        let target = Operator::new(Memory::default()).unwrap().finish();
+        let audit = AuditLayer::new(target.clone())
+            .with_operations([Operation::Read])
+            .with_batch_size(2);
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(audit.clone())
+            .finish();
+
+        op.write("test", "Hello, World!").await.unwrap();
+        op.read("test").await.unwrap();
+        let err = op.read("not_exist").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let records = records(&target).await;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["operation"], "read");
+        assert_eq!(records[0]["result"], "ok");
+        assert_eq!(records[1]["result"], "error");
+        assert_eq!(records[1]["error_kind"], "NotFound");
+    }
+
+    #[tokio::test]
+    async fn test_audit_background_flush() {
+        let target = Operator::new(Memory::default()).unwrap().finish();
+        let audit = AuditLayer::new(target.clone()).with_flush_interval(Duration::from_millis(10));
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(audit)
+            .finish();
+
+        op.write("test", "Hello, World!").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let records = records(&target).await;
+        assert_eq!(records.len(), 1, "records must be flushed in background");
+        assert_eq!(records[0]["operation"], "write");
+    }
+
+    #[tokio::test]
+    async fn test_audit_dropped_writer() {
+        let target = Operator::new(Memory::default()).unwrap().finish();
+        let audit = AuditLayer::new(target.clone());
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(audit.clone())
+            .finish();
+
+        let mut w = op.writer("test").await.unwrap();
+        w.append(Bytes::from("Hello, World!")).await.unwrap();
+        drop(w);
+        audit.flush().await.unwrap();
+
+        let records = records(&target).await;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["result"], "dropped");
+        assert_eq!(records[0]["size"], 13);
+    }
+
+    /// FailedWriter fails all writes and appends.
+    struct FailedWriter;
+
+    #[async_trait]
+    impl oio::Write for FailedWriter {
+        async fn write(&mut self, _: Bytes) -> Result<()> {
+            Err(Error::new(ErrorKind::Unexpected, "write failed"))
+        }
+
+        async fn append(&mut self, _: Bytes) -> Result<()> {
+            Err(Error::new(ErrorKind::Unexpected, "append failed"))
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_failed_writer() {
+        let target = Operator::new(Memory::default()).unwrap().finish();
+        let audit = AuditLayer::new(target.clone());
+
+        let mut w = AuditWriter::new(FailedWriter, audit.core.clone(), Operation::Write, "test");
+        oio::Write::append(&mut w, Bytes::from("Hello"))
+            .await
+            .unwrap_err();
+        oio::Write::append(&mut w, Bytes::from("World"))
+            .await
+            .unwrap_err();
+        oio::Write::abort(&mut w).await.unwrap();
+        drop(w);
+        audit.flush().await.unwrap();
+
+        let records = records(&target).await;
+        assert_eq!(records.len(), 1, "failed write must be recorded once");
+        assert_eq!(records[0]["result"], "error");
+        assert_eq!(records[0]["error_kind"], "Unexpected");
+    }
+}
@@ -15,13 +15,19 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fmt::Debug;
 use std::io;
+use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
+use std::thread;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use futures::ready;
 use futures::FutureExt;
+use parking_lot::Mutex;
 use rand::prelude::*;
 use rand::rngs::StdRng;
 
@@ -41,10 +47,24 @@ use crate::*;
 /// For example: If we specify an error rate of 0.5, there is a 50% chance
 /// of an EOF error for every read operation.
 ///
-/// # Note
+/// # Profiles
 ///
-/// For now, ChaosLayer only injects read operations. More operations may
-/// be added in the future.
+/// Faults are described by [`ChaosProfile`]. ChaosLayer holds one profile
+/// for each kind of IO it injects into:
+///
+/// - `read`: applied to `oio::Read` and `oio::BlockingRead`.
+/// - `write`: applied to `oio::Write` and `oio::BlockingWrite`.
+/// - `list`: applied to `oio::Page` and `oio::BlockingPage` returned by
+///   `list` and `scan`.
+///
+/// `ChaosLayer::new(error_ratio)` only sets the error ratio of the read
+/// profile, other profiles are disabled by default.
+///
+/// # Determinism
+///
+/// ChaosLayer uses entropy as the random seed by default. Use
+/// [`ChaosLayer::with_seed`] to make the injected faults reproducible for
+/// the same sequence of operations.
 ///
 /// # Examples
 ///
@@ -60,34 +80,205 @@ use crate::*;
 ///     .layer(ChaosLayer::new(0.1))
 ///     .finish();
 /// ```
+///
+/// Inject faults into writes and lists with a fixed seed:
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::ChaosLayer;
+/// use opendal::layers::ChaosProfile;
+/// use opendal::services;
+/// use opendal::ErrorKind;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         ChaosLayer::new(0.1)
+///             .with_seed(42)
+///             .with_write_profile(
+///                 ChaosProfile::new(0.2)
+///                     .with_error_kind(ErrorKind::RateLimited)
+///                     .with_latency(0.5, Duration::from_millis(10)),
+///             )
+///             .with_list_profile(ChaosProfile::new(0.0).with_partial_ratio(0.5)),
+///     )
+///     .finish();
+/// ```
 #[derive(Debug, Clone)]
 pub struct ChaosLayer {
-    error_ratio: f64,
+    seed: Option<u64>,
+
+    read: ChaosProfile,
+    write: ChaosProfile,
+    list: ChaosProfile,
 }
 
 impl ChaosLayer {
-    /// Create a new chaos layer with specified error ratio.
+    /// Create a new chaos layer with specified error ratio for read
+    /// operations.
     ///
     /// # Panics
     ///
     /// Input error_ratio must in [0.0..=1.0]
     pub fn new(error_ratio: f64) -> Self {
-        assert!(
-            (0.0..=1.0).contains(&error_ratio),
-            "error_ratio must between 0.0 and 1.0"
-        );
-        Self { error_ratio }
+        Self {
+            seed: None,
+            read: ChaosProfile::new(error_ratio),
+            write: ChaosProfile::default(),
+            list: ChaosProfile::default(),
+        }
+    }
+
+    /// Set the random seed so that injected faults are reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the profile applied to read operations.
+    pub fn with_read_profile(mut self, profile: ChaosProfile) -> Self {
+        self.read = profile;
+        self
+    }
+
+    /// Set the profile applied to write operations.
+    pub fn with_write_profile(mut self, profile: ChaosProfile) -> Self {
+        self.write = profile;
+        self
+    }
+
+    /// Set the profile applied to list and scan operations.
+    pub fn with_list_profile(mut self, profile: ChaosProfile) -> Self {
+        self.list = profile;
+        self
     }
 }
 
+/// ChaosProfile describes which faults will be injected into an operation.
+///
+/// All ratios must in [0.0..=1.0] and are evaluated once for every IO
+/// call (for example, every `read` or `Page::next`), no matter how many
+/// times the call is polled.
+#[derive(Debug, Clone)]
+pub struct ChaosProfile {
+    error_kind: ErrorKind,
+    error_ratio: f64,
+    latency_ratio: f64,
+    latency: Duration,
+    partial_ratio: f64,
+    truncate_ratio: f64,
+}
+
+impl Default for ChaosProfile {
+    fn default() -> Self {
+        Self {
+            error_kind: ErrorKind::Unexpected,
+            error_ratio: 0.0,
+            latency_ratio: 0.0,
+            latency: Duration::ZERO,
+            partial_ratio: 0.0,
+            truncate_ratio: 0.0,
+        }
+    }
+}
+
+impl ChaosProfile {
+    /// Create a new profile with specified error ratio.
+    ///
+    /// # Panics
+    ///
+    /// Input error_ratio must in [0.0..=1.0]
+    pub fn new(error_ratio: f64) -> Self {
+        assert_ratio("error_ratio", error_ratio);
+
+        Self {
+            error_ratio,
+            ..Default::default()
+        }
+    }
+
+    /// Set the kind of injected errors, `ErrorKind::Unexpected` by default.
+    ///
+    /// Injected errors are always temporary so that they can be retried
+    /// by [`RetryLayer`][crate::layers::RetryLayer].
+    pub fn with_error_kind(mut self, kind: ErrorKind) -> Self {
+        self.error_kind = kind;
+        self
+    }
+
+    /// Delay the operation by `latency` at specified ratio.
+    ///
+    /// # Panics
+    ///
+    /// Input ratio must in [0.0..=1.0]
+    pub fn with_latency(mut self, ratio: f64, latency: Duration) -> Self {
+        assert_ratio("latency_ratio", ratio);
+
+        self.latency_ratio = ratio;
+        self.latency = latency;
+        self
+    }
+
+    /// Return partial results at specified ratio.
+    ///
+    /// - For read, a short read will be returned.
+    /// - For list, the page will be split and the rest entries will be
+    ///   returned by following calls.
+    /// - For write, this setting is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Input ratio must in [0.0..=1.0]
+    pub fn with_partial_ratio(mut self, ratio: f64) -> Self {
+        assert_ratio("partial_ratio", ratio);
+
+        self.partial_ratio = ratio;
+        self
+    }
+
+    /// End the stream early at specified ratio.
+    ///
+    /// - For read, an early EOF will be returned and all following reads
+    ///   will return EOF until next seek.
+    /// - For list, the listing will end without returning the rest entries.
+    /// - For write, this setting is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Input ratio must in [0.0..=1.0]
+    pub fn with_truncate_ratio(mut self, ratio: f64) -> Self {
+        assert_ratio("truncate_ratio", ratio);
+
+        self.truncate_ratio = ratio;
+        self
+    }
+}
+
+fn assert_ratio(name: &str, ratio: f64) {
+    assert!(
+        (0.0..=1.0).contains(&ratio),
+        "{name} must between 0.0 and 1.0"
+    );
+}
+
 impl<A: Accessor> Layer<A> for ChaosLayer {
     type LayeredAccessor = ChaosAccessor<A>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
         ChaosAccessor {
             inner,
-            rng: StdRng::from_entropy(),
-            error_ratio: self.error_ratio,
+            rng: Mutex::new(rng),
+            read: self.read.clone(),
+            write: self.write.clone(),
+            list: self.list.clone(),
         }
     }
 }
@@ -95,9 +286,25 @@ impl<A: Accessor> Layer<A> for ChaosLayer {
 #[derive(Debug)]
 pub struct ChaosAccessor<A> {
     inner: A,
-    rng: StdRng,
+    rng: Mutex<StdRng>,
 
-    error_ratio: f64,
+    read: ChaosProfile,
+    write: ChaosProfile,
+    list: ChaosProfile,
+}
+
+impl<A> ChaosAccessor<A> {
+    /// Derive a new chaos from the accessor's rng.
+    ///
+    /// Every IO object owns its rng so that they will not contend with
+    /// each other, while the whole sequence is still decided by the seed.
+    fn chaos(&self, profile: &ChaosProfile) -> Chaos {
+        let rng = StdRng::from_rng(&mut *self.rng.lock()).expect("StdRng must be seeded");
+        Chaos {
+            rng,
+            profile: profile.clone(),
+        }
+    }
 }
 
 #[async_trait]
@@ -105,132 +312,649 @@ impl<A: Accessor> LayeredAccessor for ChaosAccessor<A> {
     type Inner = A;
     type Reader = ChaosReader<A::Reader>;
     type BlockingReader = ChaosReader<A::BlockingReader>;
-    type Writer = A::Writer;
-    type BlockingWriter = A::BlockingWriter;
-    type Pager = A::Pager;
-    type BlockingPager = A::BlockingPager;
+    type Writer = ChaosWriter<A::Writer>;
+    type BlockingWriter = ChaosWriter<A::BlockingWriter>;
+    type Pager = ChaosPager<A::Pager>;
+    type BlockingPager = ChaosPager<A::BlockingPager>;
 
     fn inner(&self) -> &Self::Inner {
         &self.inner
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let chaos = self.chaos(&self.read);
         self.inner
             .read(path, args)
-            .map(|v| v.map(|(rp, r)| (rp, ChaosReader::new(r, self.rng.clone(), self.error_ratio))))
+            .map(|v| v.map(|(rp, r)| (rp, ChaosReader::new(r, chaos))))
             .await
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let chaos = self.chaos(&self.read);
         self.inner
             .blocking_read(path, args)
-            .map(|(rp, r)| (rp, ChaosReader::new(r, self.rng.clone(), self.error_ratio)))
+            .map(|(rp, r)| (rp, ChaosReader::new(r, chaos)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        self.inner.write(path, args).await
+        let chaos = self.chaos(&self.write);
+        self.inner
+            .write(path, args)
+            .map(|v| v.map(|(rp, w)| (rp, ChaosWriter::new(w, chaos))))
+            .await
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        self.inner.blocking_write(path, args)
+        let chaos = self.chaos(&self.write);
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, ChaosWriter::new(w, chaos)))
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
-        self.inner.list(path, args).await
+        let chaos = self.chaos(&self.list);
+        self.inner
+            .list(path, args)
+            .map(|v| v.map(|(rp, p)| (rp, ChaosPager::new(p, chaos))))
+            .await
     }
 
     async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
-        self.inner.scan(path, args).await
+        let chaos = self.chaos(&self.list);
+        self.inner
+            .scan(path, args)
+            .map(|v| v.map(|(rp, p)| (rp, ChaosPager::new(p, chaos))))
+            .await
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
-        self.inner.blocking_list(path, args)
+        let chaos = self.chaos(&self.list);
+        self.inner
+            .blocking_list(path, args)
+            .map(|(rp, p)| (rp, ChaosPager::new(p, chaos)))
     }
 
     fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
-        self.inner.blocking_scan(path, args)
+        let chaos = self.chaos(&self.list);
+        self.inner
+            .blocking_scan(path, args)
+            .map(|(rp, p)| (rp, ChaosPager::new(p, chaos)))
+    }
+}
+
+/// Chaos holds the rng and profile of an IO object.
+struct Chaos {
+    rng: StdRng,
+    profile: ChaosProfile,
+}
+
+impl Chaos {
+    /// If I feel lucky, we can return the correct response. Otherwise,
+    /// we need to generate an error.
+    fn i_feel_lucky(&mut self) -> bool {
+        !self.rng.gen_bool(self.profile.error_ratio)
+    }
+
+    fn latency(&mut self) -> Option<Duration> {
+        if self.profile.latency.is_zero() || !self.rng.gen_bool(self.profile.latency_ratio) {
+            return None;
+        }
+        Some(self.profile.latency)
+    }
+
+    fn partial(&mut self) -> bool {
+        self.rng.gen_bool(self.profile.partial_ratio)
+    }
+
+    fn truncate(&mut self) -> bool {
+        self.rng.gen_bool(self.profile.truncate_ratio)
+    }
+
+    /// Pick a size in `1..len` for partial results, returns `None` if
+    /// `len` can't be split.
+    fn partial_size(&mut self, len: usize) -> Option<usize> {
+        if len <= 1 || !self.partial() {
+            return None;
+        }
+        Some(self.rng.gen_range(1..len))
+    }
+
+    /// Decide all faults of a call that reads into a buffer of `len`.
+    fn roll(&mut self, len: usize) -> Roll {
+        Roll {
+            latency: self.latency(),
+            error: !self.i_feel_lucky(),
+            truncate: self.truncate(),
+            partial_size: self.partial_size(len),
+        }
+    }
+
+    fn blocking_delay(&mut self) {
+        if let Some(dur) = self.latency() {
+            thread::sleep(dur)
+        }
     }
+
+    async fn delay(&mut self) {
+        if let Some(dur) = self.latency() {
+            tokio::time::sleep(dur).await
+        }
+    }
+
+    fn error(&self) -> Error {
+        Error::new(self.profile.error_kind, "I am your chaos!")
+            .with_operation("chaos")
+            .set_temporary()
+    }
+}
+
+/// Roll is the faults decided for a call.
+#[derive(Debug, Clone, Copy)]
+struct Roll {
+    latency: Option<Duration>,
+    error: bool,
+    truncate: bool,
+    partial_size: Option<usize>,
 }
 
 /// ChaosReader will inject error into read operations.
 pub struct ChaosReader<R> {
     inner: R,
-    rng: StdRng,
+    chaos: Chaos,
 
-    error_ratio: f64,
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    /// The faults of current call, rolled when the call starts and reset
+    /// after it returns ready.
+    roll: Option<Roll>,
+    /// Early EOF has been injected, reset after seek.
+    eof: bool,
+    /// The rest bytes of a partial `poll_next`.
+    rest: Option<Bytes>,
 }
 
 impl<R> ChaosReader<R> {
-    fn new(inner: R, rng: StdRng, error_ratio: f64) -> Self {
+    fn new(inner: R, chaos: Chaos) -> Self {
         Self {
             inner,
-            rng,
-            error_ratio,
+            chaos,
+            sleep: None,
+            roll: None,
+            eof: false,
+            rest: None,
         }
     }
 
-    /// If I feel lucky, we can return the correct response. Otherwise,
-    /// we need to generate an error.
-    fn i_feel_lucky(&mut self) -> bool {
-        let point = self.rng.gen_range(0..=100);
-        point >= (self.error_ratio * 100.0) as i32
+    /// Roll the faults of current call if not rolled yet, and wait for
+    /// the injected latency.
+    fn poll_roll(&mut self, cx: &mut Context<'_>, len: usize) -> Poll<Roll> {
+        let roll = match self.roll {
+            Some(roll) => roll,
+            None => {
+                let roll = self.chaos.roll(len);
+                self.roll = Some(roll);
+                self.sleep = roll.latency.map(|dur| Box::pin(tokio::time::sleep(dur)));
+                roll
+            }
+        };
+
+        if let Some(sleep) = self.sleep.as_mut() {
+            ready!(sleep.poll_unpin(cx));
+            self.sleep = None;
+        }
+        Poll::Ready(roll)
     }
 
-    fn unexpected_eof() -> Error {
-        Error::new(ErrorKind::Unexpected, "I am your chaos!")
-            .with_operation("chaos")
-            .set_temporary()
+    fn take_rest(&mut self) -> Option<Bytes> {
+        let mut bs = self.rest.take()?;
+        if let Some(size) = self.chaos.partial_size(bs.len()) {
+            self.rest = Some(bs.split_off(size));
+        }
+        Some(bs)
+    }
+
+    fn split_next(&mut self, mut bs: Bytes) -> Bytes {
+        if let Some(size) = self.chaos.partial_size(bs.len()) {
+            self.rest = Some(bs.split_off(size));
+        }
+        bs
     }
 }
 
 impl<R: oio::Read> oio::Read for ChaosReader<R> {
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
-        if self.i_feel_lucky() {
-            self.inner.poll_read(cx, buf)
-        } else {
-            Poll::Ready(Err(Self::unexpected_eof()))
+        let roll = ready!(self.poll_roll(cx, buf.len()));
+
+        if self.eof {
+            self.roll = None;
+            return Poll::Ready(Ok(0));
+        }
+        if roll.error {
+            self.roll = None;
+            return Poll::Ready(Err(self.chaos.error()));
         }
+        if roll.truncate {
+            self.roll = None;
+            self.eof = true;
+            return Poll::Ready(Ok(0));
+        }
+
+        let size = roll
+            .partial_size
+            .map(|size| size.min(buf.len()))
+            .unwrap_or(buf.len());
+        let res = ready!(self.inner.poll_read(cx, &mut buf[..size]));
+        self.roll = None;
+        Poll::Ready(res)
     }
 
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
-        if self.i_feel_lucky() {
-            self.inner.poll_seek(cx, pos)
-        } else {
-            Poll::Ready(Err(Self::unexpected_eof()))
+        let roll = ready!(self.poll_roll(cx, 0));
+
+        if roll.error {
+            self.roll = None;
+            return Poll::Ready(Err(self.chaos.error()));
+        }
+
+        let res = ready!(self.inner.poll_seek(cx, pos));
+        self.roll = None;
+        if res.is_ok() {
+            self.eof = false;
+            self.rest = None;
         }
+        Poll::Ready(res)
     }
 
     fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
-        if self.i_feel_lucky() {
-            self.inner.poll_next(cx)
-        } else {
-            Poll::Ready(Some(Err(Self::unexpected_eof())))
+        let roll = ready!(self.poll_roll(cx, 0));
+
+        if self.eof {
+            self.roll = None;
+            return Poll::Ready(None);
+        }
+        if roll.error {
+            self.roll = None;
+            return Poll::Ready(Some(Err(self.chaos.error())));
+        }
+        if roll.truncate {
+            self.roll = None;
+            self.eof = true;
+            return Poll::Ready(None);
+        }
+        if let Some(bs) = self.take_rest() {
+            self.roll = None;
+            return Poll::Ready(Some(Ok(bs)));
         }
+
+        let res = ready!(self.inner.poll_next(cx));
+        self.roll = None;
+        Poll::Ready(res.map(|v| v.map(|bs| self.split_next(bs))))
     }
 }
 
 impl<R: oio::BlockingRead> oio::BlockingRead for ChaosReader<R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        if self.i_feel_lucky() {
-            self.inner.read(buf)
-        } else {
-            Err(Self::unexpected_eof())
+        self.chaos.blocking_delay();
+
+        if self.eof {
+            return Ok(0);
+        }
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        if self.chaos.truncate() {
+            self.eof = true;
+            return Ok(0);
         }
+
+        let size = self.chaos.partial_size(buf.len()).unwrap_or(buf.len());
+        self.inner.read(&mut buf[..size])
     }
 
     fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
-        if self.i_feel_lucky() {
-            self.inner.seek(pos)
-        } else {
-            Err(Self::unexpected_eof())
+        self.chaos.blocking_delay();
+
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
         }
+
+        let n = self.inner.seek(pos)?;
+        self.eof = false;
+        self.rest = None;
+        Ok(n)
     }
 
     fn next(&mut self) -> Option<Result<Bytes>> {
-        if self.i_feel_lucky() {
-            self.inner.next()
-        } else {
-            Some(Err(Self::unexpected_eof()))
+        self.chaos.blocking_delay();
+
+        if self.eof {
+            return None;
+        }
+        if !self.chaos.i_feel_lucky() {
+            return Some(Err(self.chaos.error()));
+        }
+        if self.chaos.truncate() {
+            self.eof = true;
+            return None;
+        }
+        if let Some(bs) = self.take_rest() {
+            return Some(Ok(bs));
+        }
+
+        self.inner.next().map(|v| v.map(|bs| self.split_next(bs)))
+    }
+}
+
+/// ChaosWriter will inject error into write operations.
+pub struct ChaosWriter<W> {
+    inner: W,
+    chaos: Chaos,
+}
+
+impl<W> ChaosWriter<W> {
+    fn new(inner: W, chaos: Chaos) -> Self {
+        Self { inner, chaos }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for ChaosWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.chaos.delay().await;
+
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        self.inner.write(bs).await
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.chaos.delay().await;
+
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        self.inner.append(bs).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.chaos.delay().await;
+
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        self.inner.abort().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.chaos.delay().await;
+
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        self.inner.close().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for ChaosWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.chaos.blocking_delay();
+
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        self.inner.write(bs)
+    }
+
+    fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.chaos.blocking_delay();
+
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        self.inner.append(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.chaos.blocking_delay();
+
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        self.inner.close()
+    }
+}
+
+/// ChaosPager will inject error into list and scan operations.
+pub struct ChaosPager<P> {
+    inner: P,
+    chaos: Chaos,
+
+    /// The rest entries of a partial page.
+    rest: Vec<oio::Entry>,
+    /// Listing has been truncated, all following calls return `None`.
+    done: bool,
+}
+
+impl<P> ChaosPager<P> {
+    fn new(inner: P, chaos: Chaos) -> Self {
+        Self {
+            inner,
+            chaos,
+            rest: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Decide the page that should be returned to the caller.
+    ///
+    /// Returns `None` if the listing is truncated by chaos.
+    fn page(&mut self, mut entries: Vec<oio::Entry>) -> Option<Vec<oio::Entry>> {
+        if self.chaos.truncate() {
+            self.done = true;
+            return None;
+        }
+        if let Some(size) = self.chaos.partial_size(entries.len()) {
+            self.rest = entries.split_off(size);
+        }
+        Some(entries)
+    }
+
+    fn take_rest(&mut self) -> Option<Vec<oio::Entry>> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        Some(std::mem::take(&mut self.rest))
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for ChaosPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        self.chaos.delay().await;
+
+        if self.done {
+            return Ok(None);
+        }
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        if let Some(entries) = self.take_rest() {
+            return Ok(self.page(entries));
+        }
+
+        match self.inner.next().await? {
+            Some(entries) => Ok(self.page(entries)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for ChaosPager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        self.chaos.blocking_delay();
+
+        if self.done {
+            return Ok(None);
+        }
+        if !self.chaos.i_feel_lucky() {
+            return Err(self.chaos.error());
+        }
+        if let Some(entries) = self.take_rest() {
+            return Ok(self.page(entries));
+        }
+
+        match self.inner.next()? {
+            Some(entries) => Ok(self.page(entries)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::AsyncReadExt;
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_chaos_write() {
+        let op =
+            Operator::new(Memory::default())
+                .unwrap()
+                .layer(ChaosLayer::new(0.0).with_write_profile(
+                    ChaosProfile::new(1.0).with_error_kind(ErrorKind::RateLimited),
+                ))
+                .finish();
+
+        let err = op
+            .write("test", "Hello, World!")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert!(err.is_temporary());
+    }
+
+    #[tokio::test]
+    async fn test_chaos_read_truncate() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("test", vec![1; 4096]).await.unwrap();
+
+        let op = op.layer(
+            ChaosLayer::new(0.0)
+                .with_seed(42)
+                .with_read_profile(ChaosProfile::new(0.0).with_truncate_ratio(1.0)),
+        );
+
+        let mut r = op.reader("test").await.unwrap();
+        let mut content = Vec::new();
+        let size = r.read_to_end(&mut content).await.unwrap();
+        assert_eq!(size, 0);
+    }
+
+    #[tokio::test]
+    async fn test_chaos_read_partial() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("test", vec![1; 4096]).await.unwrap();
+
+        let op = op.layer(
+            ChaosLayer::new(0.0)
+                .with_seed(42)
+                .with_read_profile(ChaosProfile::new(0.0).with_partial_ratio(1.0)),
+        );
+
+        let mut r = op.reader("test").await.unwrap();
+        let mut content = Vec::new();
+        let size = r.read_to_end(&mut content).await.unwrap();
+        assert_eq!(size, 4096);
+        assert_eq!(content, vec![1; 4096]);
+    }
+
+    #[tokio::test]
+    async fn test_chaos_list_partial() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        for i in 0..10 {
+            op.write(&format!("dir/{i}"), "Hello, World!")
+                .await
+                .unwrap();
         }
+
+        let op = op.layer(
+            ChaosLayer::new(0.0)
+                .with_seed(42)
+                .with_list_profile(ChaosProfile::new(0.0).with_partial_ratio(1.0)),
+        );
+
+        let lister = op.scan("dir/").await.unwrap();
+        let mut actual: Vec<String> = lister
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        actual.sort();
+
+        let expected: Vec<String> = (0..10).map(|i| format!("dir/{i}")).collect();
+        assert_eq!(actual, expected);
+    }
+
+    /// A reader that returns pending several times before every read.
+    struct PendingReader {
+        pending: usize,
+    }
+
+    impl oio::Read for PendingReader {
+        fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+            if self.pending > 0 {
+                self.pending -= 1;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.pending = 16;
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, _: io::SeekFrom) -> Poll<Result<u64>> {
+            Poll::Ready(Ok(0))
+        }
+
+        fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            Poll::Ready(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chaos_roll_once_per_call() {
+        let chaos = Chaos {
+            rng: StdRng::seed_from_u64(42),
+            profile: ChaosProfile::new(0.5),
+        };
+        let mut r = ChaosReader::new(PendingReader { pending: 16 }, chaos);
+
+        let mut buf = [0; 16];
+        let mut succeeded = 0;
+        for _ in 0..32 {
+            if oio::ReadExt::read(&mut r, &mut buf).await.is_ok() {
+                succeeded += 1;
+            }
+        }
+        // Rolling on every poll would make nearly all reads fail.
+        assert!(succeeded > 8, "only {succeeded} of 32 reads succeeded");
+    }
+
+    #[test]
+    fn test_chaos_seed() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.blocking().write("test", "Hello, World!").unwrap();
+
+        let results = |seed| {
+            let op = op
+                .clone()
+                .layer(ChaosLayer::new(0.5).with_seed(seed))
+                .blocking();
+            (0..32).map(|_| op.read("test").is_ok()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(results(42), results(42));
     }
 }
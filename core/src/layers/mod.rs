@@ -17,6 +17,9 @@
 
 //! `Layer` is the mechanism to intercept operations.
 
+mod audit;
+pub use self::audit::AuditLayer;
+
 mod concurrent_limit;
 pub use concurrent_limit::ConcurrentLimitLayer;
 
@@ -30,6 +33,8 @@ pub use logging::LoggingLayer;
 mod chaos;
 #[cfg(feature = "layers-chaos")]
 pub use chaos::ChaosLayer;
+#[cfg(feature = "layers-chaos")]
+pub use chaos::ChaosProfile;
 
 #[cfg(feature = "layers-metrics")]
 mod metrics;
@@ -41,6 +46,15 @@ mod prometheus;
 #[cfg(feature = "layers-prometheus")]
 pub use self::prometheus::PrometheusLayer;
 
+mod permission;
+pub use self::permission::PermissionLayer;
+
+mod replication;
+pub use self::replication::Divergence;
+pub use self::replication::ReadFallback;
+pub use self::replication::ReplicationLayer;
+pub use self::replication::ReplicationMode;
+
 mod retry;
 pub use self::retry::RetryLayer;
 
@@ -0,0 +1,660 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use flagset::FlagSet;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Restrict operations by operation kind and path.
+///
+/// # Rules
+///
+/// PermissionLayer holds a list of allow and deny rules, every rule
+/// contains a set of [`Operation`] and a path pattern:
+///
+/// - An operation is denied if any deny rule matches it.
+/// - Otherwise, an operation is allowed if any allow rule matches it.
+/// - Otherwise, the default effect is used, which is allow for
+///   [`PermissionLayer::new`] and deny for [`PermissionLayer::deny_all`].
+///
+/// Denied operations return [`ErrorKind::PermissionDenied`] without
+/// reaching the underlying service.
+///
+/// Blocking operations are matched by their async version, for example,
+/// denying [`Operation::Write`] also denies [`Operation::BlockingWrite`].
+///
+/// `copy` and `rename` must be allowed on both source and destination.
+/// `batch` must be allowed for both [`Operation::Batch`] and the batched
+/// operation of every path. `presign` must be allowed for both
+/// [`Operation::Presign`] and the presigned operation.
+///
+/// # Patterns
+///
+/// Patterns are matched against the path relative to the operator's root.
+/// `.` and `..` in the path are resolved before matching, and paths that
+/// escape the root via `..` are always denied:
+///
+/// - Pattern without wildcards is a prefix: `public/` matches `public/a`
+///   and `public/b/c`, empty pattern matches all paths.
+/// - `*` matches any characters except `/`.
+/// - `**` matches any characters including `/`.
+/// - `?` matches any single character except `/`.
+///
+/// Patterns are compiled once, and matching takes linear time of the path
+/// length no matter how many wildcards the pattern has.
+///
+/// # Capabilities
+///
+/// Capabilities in [`OperatorInfo`] will be removed if their operations
+/// are denied on all paths. For example, [`PermissionLayer::read_only`]
+/// removes `Write`, `Copy`, `Rename` and `Batch`.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::PermissionLayer;
+/// use opendal::raw::Operation;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// // Read only operator.
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(PermissionLayer::read_only())
+///     .finish();
+///
+/// // Only allow reading and writing under `plugins/foo/`.
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         PermissionLayer::deny_all()
+///             .allow([Operation::Read, Operation::Stat], "plugins/foo/")
+///             .allow([Operation::Write], "plugins/foo/*.json"),
+///     )
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermissionLayer {
+    default_allow: bool,
+    allows: Vec<Rule>,
+    denies: Vec<Rule>,
+}
+
+impl PermissionLayer {
+    /// Create a new permission layer that allows all operations by default.
+    pub fn new() -> Self {
+        Self {
+            default_allow: true,
+            allows: vec![],
+            denies: vec![],
+        }
+    }
+
+    /// Create a new permission layer that denies all operations by default.
+    pub fn deny_all() -> Self {
+        Self {
+            default_allow: false,
+            ..Self::new()
+        }
+    }
+
+    /// Create a new permission layer that denies all operations that
+    /// mutate data.
+    pub fn read_only() -> Self {
+        Self::new().deny(
+            [
+                Operation::CreateDir,
+                Operation::Write,
+                Operation::Copy,
+                Operation::Rename,
+                Operation::Delete,
+                Operation::Batch,
+            ],
+            "",
+        )
+    }
+
+    /// Allow operations on paths that match given pattern.
+    pub fn allow(mut self, ops: impl IntoIterator<Item = Operation>, pattern: &str) -> Self {
+        self.allows.push(Rule::new(ops, pattern));
+        self
+    }
+
+    /// Deny operations on paths that match given pattern.
+    pub fn deny(mut self, ops: impl IntoIterator<Item = Operation>, pattern: &str) -> Self {
+        self.denies.push(Rule::new(ops, pattern));
+        self
+    }
+
+    fn is_allowed(&self, op: Operation, path: &str) -> bool {
+        let op = normalize_operation(op);
+        let path = match normalize_path_components(path) {
+            Some(path) => path,
+            None => return false,
+        };
+        let path = path.as_str();
+
+        if self.denies.iter().any(|r| r.matches(op, path)) {
+            return false;
+        }
+        if self.allows.iter().any(|r| r.matches(op, path)) {
+            return true;
+        }
+        self.default_allow
+    }
+
+    /// Check if given operation is denied on all paths.
+    fn is_denied_everywhere(&self, op: Operation) -> bool {
+        if self
+            .denies
+            .iter()
+            .any(|r| r.contains(op) && r.matches_all())
+        {
+            return true;
+        }
+        !self.default_allow && !self.allows.iter().any(|r| r.contains(op))
+    }
+}
+
+impl Default for PermissionLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Accessor> Layer<A> for PermissionLayer {
+    type LayeredAccessor = PermissionAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        PermissionAccessor {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Map blocking operations to their async version.
+fn normalize_operation(op: Operation) -> Operation {
+    match op {
+        Operation::BlockingCreateDir => Operation::CreateDir,
+        Operation::BlockingRead => Operation::Read,
+        Operation::BlockingWrite => Operation::Write,
+        Operation::BlockingCopy => Operation::Copy,
+        Operation::BlockingMove => Operation::Rename,
+        Operation::BlockingStat => Operation::Stat,
+        Operation::BlockingDelete => Operation::Delete,
+        Operation::BlockingList => Operation::List,
+        Operation::BlockingScan => Operation::Scan,
+        v => v,
+    }
+}
+
+/// Resolve `.` and `..` in the path, returns `None` if the path escapes
+/// the root.
+///
+/// The trailing `/` of dir is kept.
+fn normalize_path_components(path: &str) -> Option<String> {
+    let mut components: Vec<&str> = Vec::new();
+    let mut is_dir = false;
+    for component in path.split('/') {
+        is_dir = matches!(component, "" | "." | "..");
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            v => components.push(v),
+        }
+    }
+
+    let mut path = components.join("/");
+    if !path.is_empty() && is_dir {
+        path.push('/');
+    }
+    Some(path)
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    ops: Vec<Operation>,
+    pattern: String,
+    /// Compiled glob of the pattern, `None` if the pattern is a prefix.
+    glob: Option<Glob>,
+}
+
+impl Rule {
+    fn new(ops: impl IntoIterator<Item = Operation>, pattern: &str) -> Self {
+        let pattern = pattern.trim_start_matches('/').to_string();
+        let glob = if pattern.contains(['*', '?']) {
+            Some(Glob::new(&pattern))
+        } else {
+            None
+        };
+
+        Self {
+            ops: ops.into_iter().map(normalize_operation).collect(),
+            pattern,
+            glob,
+        }
+    }
+
+    fn contains(&self, op: Operation) -> bool {
+        self.ops.contains(&op)
+    }
+
+    fn matches_all(&self) -> bool {
+        self.pattern.is_empty() || self.pattern == "**"
+    }
+
+    fn matches(&self, op: Operation, path: &str) -> bool {
+        if !self.contains(op) {
+            return false;
+        }
+
+        match &self.glob {
+            Some(glob) => glob.matches(path),
+            None => path.starts_with(&self.pattern),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Byte(u8),
+    /// `?`
+    Any,
+    /// `*`
+    Star,
+    /// `**`
+    DoubleStar,
+}
+
+/// Glob pattern which supports `*`, `**` and `?`.
+///
+/// Glob is matched by simulating all positions of the pattern at the same
+/// time instead of backtracking, so the time is bounded by the product of
+/// pattern and path length.
+#[derive(Debug, Clone)]
+struct Glob {
+    tokens: Vec<Token>,
+}
+
+impl Glob {
+    fn new(pattern: &str) -> Self {
+        let mut tokens = Vec::with_capacity(pattern.len());
+        let mut bs = pattern.as_bytes().iter().peekable();
+        while let Some(b) = bs.next() {
+            let token = match b {
+                b'*' if bs.peek() == Some(&&b'*') => {
+                    bs.next();
+                    Token::DoubleStar
+                }
+                b'*' => Token::Star,
+                b'?' => Token::Any,
+                b => Token::Byte(*b),
+            };
+            // Consecutive `**` are the same as one.
+            if token == Token::DoubleStar && tokens.last() == Some(&Token::DoubleStar) {
+                continue;
+            }
+            tokens.push(token);
+        }
+        Self { tokens }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        let n = self.tokens.len();
+        let mut current = vec![false; n + 1];
+        let mut next = vec![false; n + 1];
+        current[0] = true;
+        self.expand(&mut current);
+
+        for c in path.bytes() {
+            next.iter_mut().for_each(|v| *v = false);
+            for (i, token) in self.tokens.iter().enumerate() {
+                if !current[i] {
+                    continue;
+                }
+                match token {
+                    Token::Byte(b) if *b == c => next[i + 1] = true,
+                    Token::Any if c != b'/' => next[i + 1] = true,
+                    Token::Star if c != b'/' => next[i] = true,
+                    Token::DoubleStar => next[i] = true,
+                    _ => {}
+                }
+            }
+            self.expand(&mut next);
+            if !next.contains(&true) {
+                return false;
+            }
+            std::mem::swap(&mut current, &mut next);
+        }
+        current[n]
+    }
+
+    /// Wildcards could match empty, so the following positions are active
+    /// too.
+    fn expand(&self, states: &mut [bool]) {
+        for (i, token) in self.tokens.iter().enumerate() {
+            if states[i] && matches!(token, Token::Star | Token::DoubleStar) {
+                states[i + 1] = true;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PermissionAccessor<A: Accessor> {
+    inner: A,
+    layer: PermissionLayer,
+}
+
+impl<A: Accessor> PermissionAccessor<A> {
+    fn check(&self, op: Operation, path: &str) -> Result<()> {
+        if self.layer.is_allowed(op, path) {
+            return Ok(());
+        }
+
+        Err(Error::new(
+            ErrorKind::PermissionDenied,
+            "operation is denied by permission layer",
+        )
+        .with_operation(op)
+        .with_context("service", self.inner.info().scheme())
+        .with_context("path", path))
+    }
+
+    fn check_batch(&self, args: &OpBatch) -> Result<()> {
+        for (path, op) in args.operation() {
+            self.check(Operation::Batch, path)?;
+            self.check(op.operation(), path)?;
+        }
+        Ok(())
+    }
+
+    fn check_presign(&self, path: &str, args: &OpPresign) -> Result<()> {
+        self.check(Operation::Presign, path)?;
+
+        match args.operation() {
+            PresignOperation::Stat(_) => self.check(Operation::Stat, path),
+            PresignOperation::Read(_) => self.check(Operation::Read, path),
+            PresignOperation::Write(_) => self.check(Operation::Write, path),
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for PermissionAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut info = self.inner.info();
+
+        let mut denied: FlagSet<AccessorCapability> = FlagSet::default();
+        for (op, cap) in [
+            (Operation::Read, AccessorCapability::Read),
+            (Operation::Write, AccessorCapability::Write),
+            (Operation::Copy, AccessorCapability::Copy),
+            (Operation::Rename, AccessorCapability::Rename),
+            (Operation::List, AccessorCapability::List),
+            (Operation::Scan, AccessorCapability::Scan),
+            (Operation::Presign, AccessorCapability::Presign),
+            (Operation::Batch, AccessorCapability::Batch),
+            // Batch only supports delete so far.
+            (Operation::Delete, AccessorCapability::Batch),
+        ] {
+            if self.layer.is_denied_everywhere(op) {
+                denied |= cap;
+            }
+        }
+
+        let capabilities = info.capabilities() - denied;
+        info.set_capabilities(capabilities);
+        info
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.check(Operation::CreateDir, path)?;
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.check(Operation::Read, path)?;
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check(Operation::Write, path)?;
+        self.inner.write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check(Operation::Copy, from)?;
+        self.check(Operation::Copy, to)?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.check(Operation::Rename, from)?;
+        self.check(Operation::Rename, to)?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.check(Operation::Stat, path)?;
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check(Operation::Delete, path)?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.check(Operation::List, path)?;
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.check(Operation::Scan, path)?;
+        self.inner.scan(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.check_batch(&args)?;
+        self.inner.batch(args).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.check_presign(path, &args)?;
+        self.inner.presign(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.check(Operation::BlockingCreateDir, path)?;
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.check(Operation::BlockingRead, path)?;
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check(Operation::BlockingWrite, path)?;
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check(Operation::BlockingCopy, from)?;
+        self.check(Operation::BlockingCopy, to)?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.check(Operation::BlockingMove, from)?;
+        self.check(Operation::BlockingMove, to)?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.check(Operation::BlockingStat, path)?;
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check(Operation::BlockingDelete, path)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.check(Operation::BlockingList, path)?;
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.check(Operation::BlockingScan, path)?;
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_glob_match() {
+        let cases = vec![
+            ("*.json", "a.json", true),
+            ("*.json", "dir/a.json", false),
+            ("**.json", "dir/a.json", true),
+            ("dir/**", "dir/a/b/c", true),
+            ("dir/*/c", "dir/a/c", true),
+            ("dir/*/c", "dir/a/b/c", false),
+            ("a?c", "abc", true),
+            ("a?c", "a/c", false),
+            ("**/c", "a/b/c", true),
+        ];
+
+        for (pattern, path, expected) in cases {
+            assert_eq!(
+                Glob::new(pattern).matches(path),
+                expected,
+                "{pattern} {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_glob_match_many_double_stars() {
+        let pattern = "**a**a**a**a**a**a**a**a**a**a**b";
+        let path = "a".repeat(4096);
+        assert!(!Glob::new(pattern).matches(&path));
+    }
+
+    #[test]
+    fn test_normalize_path_components() {
+        let cases = vec![
+            ("plugins/foo/data", Some("plugins/foo/data")),
+            ("/plugins//foo/./data", Some("plugins/foo/data")),
+            ("plugins/foo/../bar/", Some("plugins/bar/")),
+            ("plugins/foo/../../secret", Some("secret")),
+            ("plugins/../../secret", None),
+            ("..", None),
+            ("/", Some("")),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(
+                normalize_path_components(path).as_deref(),
+                expected,
+                "{path}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_only() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("test", "Hello, World!").await.unwrap();
+
+        let op = op.layer(PermissionLayer::read_only());
+        let info = op.info();
+        assert!(info.can_read());
+        assert!(!info.can_write());
+        assert!(!info.can_copy());
+        assert!(!info.can_rename());
+        assert!(!info.can_batch());
+
+        assert_eq!(op.read("test").await.unwrap(), b"Hello, World!");
+        let err = op.write("test", "Hello").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.delete("test").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.blocking().write("test", "Hello").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_path_scoped() {
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(
+                PermissionLayer::deny_all()
+                    .allow([Operation::Read, Operation::Write], "plugins/foo/")
+                    .deny([Operation::Write], "plugins/foo/*.lock"),
+            )
+            .finish();
+        assert!(op.info().can_write());
+        assert!(!op.info().can_list());
+
+        op.write("plugins/foo/data", "Hello, World!").await.unwrap();
+        assert_eq!(op.read("plugins/foo/data").await.unwrap(), b"Hello, World!");
+
+        let err = op.write("plugins/foo/x.lock", "").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.write("plugins/bar/data", "").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.stat("plugins/foo/data").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        // Paths escape the scope via `..` must be denied.
+        let err = op.read("plugins/foo/../../secret").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.read("plugins/foo/../bar/data").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.read("../plugins/foo/data").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+}
@@ -0,0 +1,1121 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use log::warn;
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use tokio::sync::Notify;
+
+use crate::ops::*;
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// Replicate writes into secondary operators for disaster recovery.
+///
+/// # Replication
+///
+/// `write`, `delete`, `create_dir`, `copy` and `rename` (and `batch`
+/// deletes) will be performed on the primary (the operator this layer is
+/// applied to) first, and replicated to all secondaries only if the
+/// primary succeeded. Failures on secondaries never fail the operation,
+/// they are reported via [`ReplicationLayer::with_divergence_handler`]
+/// instead.
+///
+/// - [`ReplicationMode::Sync`]: operations will be replicated before they
+///   returned. Writers will forward every `append` to all secondaries, so
+///   objects will never be buffered as a whole.
+/// - [`ReplicationMode::Async`]: operations will be pushed into a bounded
+///   queue and replicated by a background task spawned on current tokio
+///   runtime. Operations will wait if the queue is full. Written objects
+///   will be streamed from primary to secondaries after the writer closed.
+///   Use [`ReplicationLayer::flush`] to wait for queued replications
+///   before exiting. Operations will be replicated synchronously if there
+///   is no tokio runtime. The background task will be spawned again if its
+///   runtime has been shutdown, and replications dropped with it will be
+///   reported as divergence.
+///
+/// Blocking operations are always replicated synchronously.
+///
+/// # Read Fallback
+///
+/// `read` and `stat` will fallback to secondaries in order if the primary
+/// failed, see [`ReadFallback`].
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ReplicationLayer;
+/// use opendal::layers::ReplicationMode;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let secondary = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .finish();
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         ReplicationLayer::new(vec![secondary])
+///             .with_mode(ReplicationMode::Async { queue_size: 1024 })
+///             .with_divergence_handler(|d| eprintln!("replication diverged: {d:?}")),
+///     )
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct ReplicationLayer {
+    secondaries: Vec<Operator>,
+    mode: ReplicationMode,
+    read_fallback: ReadFallback,
+    handler: DivergenceHandler,
+    pending: Arc<Pending>,
+}
+
+impl ReplicationLayer {
+    /// Create a new replication layer with given secondaries.
+    pub fn new(secondaries: Vec<Operator>) -> Self {
+        Self {
+            secondaries,
+            mode: ReplicationMode::Sync,
+            read_fallback: ReadFallback::NotFound,
+            handler: Arc::new(|d: Divergence| {
+                warn!(
+                    target: "opendal::layers::replication",
+                    "operation={} path={} secondary={} -> replication diverged: {:?}",
+                    d.operation, d.path, d.secondary, d.error
+                )
+            }),
+            pending: Arc::default(),
+        }
+    }
+
+    /// Set the replication mode, default to [`ReplicationMode::Sync`].
+    ///
+    /// # Panics
+    ///
+    /// `queue_size` of [`ReplicationMode::Async`] must be larger than 0.
+    pub fn with_mode(mut self, mode: ReplicationMode) -> Self {
+        if let ReplicationMode::Async { queue_size } = mode {
+            assert!(queue_size > 0, "queue_size must be larger than 0");
+        }
+
+        self.mode = mode;
+        self
+    }
+
+    /// Set the read fallback policy, default to [`ReadFallback::NotFound`].
+    pub fn with_read_fallback(mut self, fallback: ReadFallback) -> Self {
+        self.read_fallback = fallback;
+        self
+    }
+
+    /// Set the handler that will be called while replication to a
+    /// secondary failed.
+    ///
+    /// By default, divergence will be logged as warning.
+    pub fn with_divergence_handler(
+        mut self,
+        handler: impl Fn(Divergence) + Send + Sync + 'static,
+    ) -> Self {
+        self.handler = Arc::new(handler);
+        self
+    }
+
+    /// Wait until all replications queued by operators built with this
+    /// layer have been performed.
+    ///
+    /// This is a no-op in [`ReplicationMode::Sync`].
+    pub async fn flush(&self) {
+        loop {
+            let notified = self.pending.notify.notified();
+            if self.pending.count.load(Ordering::Acquire) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl<A: Accessor> Layer<A> for ReplicationLayer {
+    type LayeredAccessor = ReplicationAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let inner = Arc::new(inner);
+        let replicator = Arc::new(Replicator {
+            primary: inner.clone(),
+            secondaries: self
+                .secondaries
+                .iter()
+                .map(|op| op.clone().into_inner())
+                .collect(),
+            handler: self.handler.clone(),
+            pending: self.pending.clone(),
+        });
+        let queue = match self.mode {
+            ReplicationMode::Sync => None,
+            ReplicationMode::Async { queue_size } => Some(Queue {
+                size: queue_size,
+                tx: Mutex::new(None),
+            }),
+        };
+
+        ReplicationAccessor {
+            inner,
+            replicator,
+            queue,
+            read_fallback: self.read_fallback,
+        }
+    }
+}
+
+/// ReplicationMode decides when operations will be replicated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicationMode {
+    /// Replicate operations before they returned.
+    Sync,
+    /// Replicate operations in background with a bounded queue.
+    Async {
+        /// Max number of pending operations.
+        queue_size: usize,
+    },
+}
+
+/// ReadFallback decides when reads will fallback to secondaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadFallback {
+    /// Never read from secondaries.
+    Disabled,
+    /// Read from secondaries if primary returns [`ErrorKind::NotFound`].
+    NotFound,
+    /// Read from secondaries if primary returns any error.
+    AnyError,
+}
+
+impl ReadFallback {
+    fn should_fallback(&self, err: &Error) -> bool {
+        match self {
+            ReadFallback::Disabled => false,
+            ReadFallback::NotFound => err.kind() == ErrorKind::NotFound,
+            ReadFallback::AnyError => true,
+        }
+    }
+}
+
+/// Divergence means a secondary failed to replicate an operation that has
+/// been performed on primary.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Divergence {
+    /// The operation that failed to replicate.
+    pub operation: Operation,
+    /// The path of the operation, for `copy` and `rename`, it's the
+    /// destination path.
+    pub path: String,
+    /// The index of the secondary in [`ReplicationLayer::new`].
+    pub secondary: usize,
+    /// The error returned by the secondary.
+    pub error: Error,
+}
+
+type DivergenceHandler = Arc<dyn Fn(Divergence) + Send + Sync>;
+
+/// Task is a replication that will be performed by background task.
+enum Task {
+    CreateDir(String),
+    Write(String),
+    Copy(String, String),
+    Rename(String, String),
+    Delete(String),
+}
+
+impl Task {
+    /// Return the operation and the path to report divergence.
+    fn operation(&self) -> (Operation, &str) {
+        match self {
+            Task::CreateDir(path) => (Operation::CreateDir, path),
+            Task::Write(path) => (Operation::Write, path),
+            Task::Copy(_, to) => (Operation::Copy, to),
+            Task::Rename(_, to) => (Operation::Rename, to),
+            Task::Delete(path) => (Operation::Delete, path),
+        }
+    }
+}
+
+/// Pending counts replication tasks that have been queued but not performed.
+#[derive(Default)]
+struct Pending {
+    count: AtomicUsize,
+    notify: Notify,
+}
+
+/// Queued is a task in queue, which is counted as pending until dropped.
+///
+/// If the task is dropped before performed, for example the background
+/// task is dropped with its runtime, it will be reported as divergence of
+/// all secondaries so that [`ReplicationLayer::flush`] will not wait for
+/// it forever.
+struct Queued {
+    task: Option<Task>,
+    pending: Arc<Pending>,
+    handler: DivergenceHandler,
+    secondaries: usize,
+}
+
+impl Queued {
+    fn task(&self) -> &Task {
+        self.task
+            .as_ref()
+            .expect("task must be valid before finished")
+    }
+
+    /// Mark the task as performed.
+    fn finish(mut self) {
+        self.task = None;
+    }
+}
+
+impl Drop for Queued {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            let (operation, path) = task.operation();
+            for secondary in 0..self.secondaries {
+                (self.handler)(Divergence {
+                    operation,
+                    path: path.to_string(),
+                    secondary,
+                    error: Error::new(ErrorKind::Unexpected, "replication task has been stopped"),
+                })
+            }
+        }
+
+        if self.pending.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.pending.notify.notify_waiters();
+        }
+    }
+}
+
+struct Queue {
+    size: usize,
+    /// Sender of the background task, which will be spawned on current
+    /// runtime while sending the first task, and spawned again after the
+    /// runtime it's running on has been shutdown.
+    tx: Mutex<Option<mpsc::Sender<Queued>>>,
+}
+
+struct Replicator<A: Accessor> {
+    primary: Arc<A>,
+    secondaries: Vec<FusedAccessor>,
+    handler: DivergenceHandler,
+    pending: Arc<Pending>,
+}
+
+impl<A: Accessor> Replicator<A> {
+    fn diverge(&self, operation: Operation, path: &str, secondary: usize, error: Error) {
+        (self.handler)(Divergence {
+            operation,
+            path: path.to_string(),
+            secondary,
+            error,
+        })
+    }
+
+    /// Push the task into queue, it will be counted as pending until
+    /// performed or dropped by the background task.
+    ///
+    /// The task will be replicated synchronously if the background task
+    /// has been stopped, since the primary has succeeded already.
+    async fn send(&self, tx: &mpsc::Sender<Queued>, task: Task) {
+        self.pending.count.fetch_add(1, Ordering::AcqRel);
+        let queued = Queued {
+            task: Some(task),
+            pending: self.pending.clone(),
+            handler: self.handler.clone(),
+            secondaries: self.secondaries.len(),
+        };
+
+        if let Err(mpsc::error::SendError(queued)) = tx.send(queued).await {
+            self.replicate(queued.task()).await;
+            queued.finish();
+        }
+    }
+
+    async fn replicate(&self, task: &Task) {
+        for (idx, sec) in self.secondaries.iter().enumerate() {
+            let (op, path, res) = match task {
+                Task::CreateDir(path) => (
+                    Operation::CreateDir,
+                    path,
+                    sec.create_dir(path, OpCreate::new()).await.map(|_| ()),
+                ),
+                Task::Write(path) => (Operation::Write, path, self.stream(sec, path).await),
+                Task::Copy(from, to) => {
+                    let res = match sec.copy(from, to, OpCopy::new()).await {
+                        Err(e) if e.kind() == ErrorKind::Unsupported => self.stream(sec, to).await,
+                        res => res.map(|_| ()),
+                    };
+                    (Operation::Copy, to, res)
+                }
+                Task::Rename(from, to) => {
+                    let res = match sec.rename(from, to, OpRename::new()).await {
+                        Err(e) if e.kind() == ErrorKind::Unsupported => {
+                            match self.stream(sec, to).await {
+                                Ok(()) => sec.delete(from, OpDelete::new()).await.map(|_| ()),
+                                Err(e) => Err(e),
+                            }
+                        }
+                        res => res.map(|_| ()),
+                    };
+                    (Operation::Rename, to, res)
+                }
+                Task::Delete(path) => (
+                    Operation::Delete,
+                    path,
+                    sec.delete(path, OpDelete::new()).await.map(|_| ()),
+                ),
+            };
+
+            if let Err(e) = res {
+                self.diverge(op, path, idx, e);
+            }
+        }
+    }
+
+    fn blocking_replicate(&self, task: &Task) {
+        for (idx, sec) in self.secondaries.iter().enumerate() {
+            let (op, path, res) = match task {
+                Task::CreateDir(path) => (
+                    Operation::BlockingCreateDir,
+                    path,
+                    sec.blocking_create_dir(path, OpCreate::new()).map(|_| ()),
+                ),
+                Task::Write(path) => (
+                    Operation::BlockingWrite,
+                    path,
+                    self.blocking_stream(sec, path),
+                ),
+                Task::Copy(from, to) => {
+                    let res = match sec.blocking_copy(from, to, OpCopy::new()) {
+                        Err(e) if e.kind() == ErrorKind::Unsupported => {
+                            self.blocking_stream(sec, to)
+                        }
+                        res => res.map(|_| ()),
+                    };
+                    (Operation::BlockingCopy, to, res)
+                }
+                Task::Rename(from, to) => {
+                    let res = match sec.blocking_rename(from, to, OpRename::new()) {
+                        Err(e) if e.kind() == ErrorKind::Unsupported => {
+                            match self.blocking_stream(sec, to) {
+                                Ok(()) => sec.blocking_delete(from, OpDelete::new()).map(|_| ()),
+                                Err(e) => Err(e),
+                            }
+                        }
+                        res => res.map(|_| ()),
+                    };
+                    (Operation::BlockingMove, to, res)
+                }
+                Task::Delete(path) => (
+                    Operation::BlockingDelete,
+                    path,
+                    sec.blocking_delete(path, OpDelete::new()).map(|_| ()),
+                ),
+            };
+
+            if let Err(e) = res {
+                self.diverge(op, path, idx, e);
+            }
+        }
+    }
+
+    /// Stream the object at path from primary to secondary.
+    async fn stream(&self, sec: &FusedAccessor, path: &str) -> Result<()> {
+        let (_, mut r) = self.primary.read(path, OpRead::new()).await?;
+        let (_, mut w) = sec.write(path, OpWrite::new().with_append()).await?;
+
+        let res = async {
+            let mut buf = oio::AppendBuffer::new();
+            while let Some(bs) = r.next().await {
+                if let Some(bs) = buf.push(bs?) {
+                    w.append(bs).await?;
+                }
+            }
+            if let Some(bs) = buf.finish() {
+                w.append(bs).await?;
+            }
+            w.close().await
+        }
+        .await;
+
+        if res.is_err() {
+            // The partial object will be left on secondary if not aborted.
+            let _ = w.abort().await;
+        }
+        res
+    }
+
+    fn blocking_stream(&self, sec: &FusedAccessor, path: &str) -> Result<()> {
+        let (_, mut r) = self.primary.blocking_read(path, OpRead::new())?;
+        let (_, mut w) = sec.blocking_write(path, OpWrite::new().with_append())?;
+
+        let mut buf = oio::AppendBuffer::new();
+        while let Some(bs) = oio::BlockingRead::next(&mut r) {
+            if let Some(bs) = buf.push(bs?) {
+                w.append(bs)?;
+            }
+        }
+        if let Some(bs) = buf.finish() {
+            w.append(bs)?;
+        }
+        w.close()
+    }
+}
+
+pub struct ReplicationAccessor<A: Accessor> {
+    inner: Arc<A>,
+    replicator: Arc<Replicator<A>>,
+    queue: Option<Queue>,
+    read_fallback: ReadFallback,
+}
+
+impl<A: Accessor> Debug for ReplicationAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplicationAccessor")
+            .field("inner", &self.inner)
+            .field("secondaries", &self.replicator.secondaries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> ReplicationAccessor<A> {
+    /// Replicate the task or push it into queue in async mode.
+    async fn replicate(&self, task: Task) {
+        match self.queue() {
+            Some(tx) => self.replicator.send(&tx, task).await,
+            None => self.replicator.replicate(&task).await,
+        }
+    }
+
+    /// Return the sender of queue in async mode.
+    ///
+    /// The background task will be spawned on current runtime if not
+    /// started or stopped with its runtime. Returns `None` if there is no
+    /// runtime to spawn on, so that operations will be replicated
+    /// synchronously instead of panic.
+    fn queue(&self) -> Option<mpsc::Sender<Queued>> {
+        let queue = self.queue.as_ref()?;
+
+        let mut tx = queue.tx.lock();
+        if let Some(tx) = tx.as_ref().filter(|tx| !tx.is_closed()) {
+            return Some(tx.clone());
+        }
+
+        let handle = tokio::runtime::Handle::try_current().ok()?;
+        let (sender, mut rx) = mpsc::channel::<Queued>(queue.size);
+        let replicator = self.replicator.clone();
+        handle.spawn(async move {
+            while let Some(queued) = rx.recv().await {
+                replicator.replicate(queued.task()).await;
+                queued.finish();
+            }
+        });
+
+        *tx = Some(sender.clone());
+        Some(sender)
+    }
+
+    async fn fallback_read(
+        &self,
+        path: &str,
+        args: OpRead,
+        err: Error,
+    ) -> Result<(RpRead, ReplicationReader<A::Reader, oio::Reader>)> {
+        for sec in &self.replicator.secondaries {
+            if let Ok((rp, r)) = sec.read(path, args.clone()).await {
+                return Ok((rp, ReplicationReader::Secondary(r)));
+            }
+        }
+        Err(err)
+    }
+
+    fn blocking_fallback_read(
+        &self,
+        path: &str,
+        args: OpRead,
+        err: Error,
+    ) -> Result<(
+        RpRead,
+        ReplicationReader<A::BlockingReader, oio::BlockingReader>,
+    )> {
+        for sec in &self.replicator.secondaries {
+            if let Ok((rp, r)) = sec.blocking_read(path, args.clone()) {
+                return Ok((rp, ReplicationReader::Secondary(r)));
+            }
+        }
+        Err(err)
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ReplicationAccessor<A> {
+    type Inner = A;
+    type Reader = ReplicationReader<A::Reader, oio::Reader>;
+    type BlockingReader = ReplicationReader<A::BlockingReader, oio::BlockingReader>;
+    type Writer = ReplicationWriter<A, A::Writer, oio::Writer>;
+    type BlockingWriter = ReplicationWriter<A, A::BlockingWriter, oio::BlockingWriter>;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        let rp = self.inner.create_dir(path, args).await?;
+        self.replicate(Task::CreateDir(path.to_string())).await;
+        Ok(rp)
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        match self.inner.read(path, args.clone()).await {
+            Ok((rp, r)) => Ok((rp, ReplicationReader::Primary(r))),
+            Err(e) if self.read_fallback.should_fallback(&e) => {
+                self.fallback_read(path, args, e).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let (rp, w) = self.inner.write(path, args.clone()).await?;
+
+        let mut secondaries = Vec::new();
+        let queue = self.queue();
+        if queue.is_none() {
+            for (idx, sec) in self.replicator.secondaries.iter().enumerate() {
+                match sec.write(path, args.clone()).await {
+                    Ok((_, w)) => secondaries.push((idx, w)),
+                    Err(e) => self.replicator.diverge(Operation::Write, path, idx, e),
+                }
+            }
+        }
+
+        Ok((
+            rp,
+            ReplicationWriter {
+                inner: w,
+                replicator: self.replicator.clone(),
+                secondaries,
+                queue,
+                path: path.to_string(),
+            },
+        ))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.copy(from, to, args).await?;
+        self.replicate(Task::Copy(from.to_string(), to.to_string()))
+            .await;
+        Ok(rp)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.rename(from, to, args).await?;
+        self.replicate(Task::Rename(from.to_string(), to.to_string()))
+            .await;
+        Ok(rp)
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.inner.stat(path, args.clone()).await {
+            Err(e) if self.read_fallback.should_fallback(&e) => {
+                for sec in &self.replicator.secondaries {
+                    if let Ok(rp) = sec.stat(path, args.clone()).await {
+                        return Ok(rp);
+                    }
+                }
+                Err(e)
+            }
+            res => res,
+        }
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rp = self.inner.delete(path, args).await?;
+        self.replicate(Task::Delete(path.to_string())).await;
+        Ok(rp)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let rp = self.inner.batch(args).await?;
+        for (path, res) in rp.results() {
+            if let Ok(BatchedReply::Delete(_)) = res {
+                self.replicate(Task::Delete(path.to_string())).await;
+            }
+        }
+        Ok(rp)
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        let rp = self.inner.blocking_create_dir(path, args)?;
+        self.replicator
+            .blocking_replicate(&Task::CreateDir(path.to_string()));
+        Ok(rp)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        match self.inner.blocking_read(path, args.clone()) {
+            Ok((rp, r)) => Ok((rp, ReplicationReader::Primary(r))),
+            Err(e) if self.read_fallback.should_fallback(&e) => {
+                self.blocking_fallback_read(path, args, e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let (rp, w) = self.inner.blocking_write(path, args.clone())?;
+
+        let mut secondaries = Vec::new();
+        for (idx, sec) in self.replicator.secondaries.iter().enumerate() {
+            match sec.blocking_write(path, args.clone()) {
+                Ok((_, w)) => secondaries.push((idx, w)),
+                Err(e) => self
+                    .replicator
+                    .diverge(Operation::BlockingWrite, path, idx, e),
+            }
+        }
+
+        Ok((
+            rp,
+            ReplicationWriter {
+                inner: w,
+                replicator: self.replicator.clone(),
+                secondaries,
+                queue: None,
+                path: path.to_string(),
+            },
+        ))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.blocking_copy(from, to, args)?;
+        self.replicator
+            .blocking_replicate(&Task::Copy(from.to_string(), to.to_string()));
+        Ok(rp)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.blocking_rename(from, to, args)?;
+        self.replicator
+            .blocking_replicate(&Task::Rename(from.to_string(), to.to_string()));
+        Ok(rp)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.inner.blocking_stat(path, args.clone()) {
+            Err(e) if self.read_fallback.should_fallback(&e) => {
+                for sec in &self.replicator.secondaries {
+                    if let Ok(rp) = sec.blocking_stat(path, args.clone()) {
+                        return Ok(rp);
+                    }
+                }
+                Err(e)
+            }
+            res => res,
+        }
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rp = self.inner.blocking_delete(path, args)?;
+        self.replicator
+            .blocking_replicate(&Task::Delete(path.to_string()));
+        Ok(rp)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+/// ReplicationReader reads from primary or a secondary after fallback.
+pub enum ReplicationReader<P, S> {
+    Primary(P),
+    Secondary(S),
+}
+
+impl<P: oio::Read, S: oio::Read> oio::Read for ReplicationReader<P, S> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self {
+            Self::Primary(r) => r.poll_read(cx, buf),
+            Self::Secondary(r) => r.poll_read(cx, buf),
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        match self {
+            Self::Primary(r) => r.poll_seek(cx, pos),
+            Self::Secondary(r) => r.poll_seek(cx, pos),
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self {
+            Self::Primary(r) => r.poll_next(cx),
+            Self::Secondary(r) => r.poll_next(cx),
+        }
+    }
+}
+
+impl<P: oio::BlockingRead, S: oio::BlockingRead> oio::BlockingRead for ReplicationReader<P, S> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self {
+            Self::Primary(r) => r.read(buf),
+            Self::Secondary(r) => r.read(buf),
+        }
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        match self {
+            Self::Primary(r) => r.seek(pos),
+            Self::Secondary(r) => r.seek(pos),
+        }
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        match self {
+            Self::Primary(r) => r.next(),
+            Self::Secondary(r) => r.next(),
+        }
+    }
+}
+
+/// ReplicationWriter forwards data to both primary and secondaries in
+/// sync mode, or push a replication task after closed in async mode.
+pub struct ReplicationWriter<A: Accessor, W, S> {
+    inner: W,
+    replicator: Arc<Replicator<A>>,
+    /// Writers of secondaries, writer will be removed after failed.
+    secondaries: Vec<(usize, S)>,
+    queue: Option<mpsc::Sender<Queued>>,
+    path: String,
+}
+
+#[async_trait]
+impl<A: Accessor, W: oio::Write, S: oio::Write> oio::Write for ReplicationWriter<A, W, S> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        if let Err(e) = self.inner.write(bs.clone()).await {
+            self.abort_secondaries().await;
+            return Err(e);
+        }
+
+        let mut secondaries = Vec::with_capacity(self.secondaries.len());
+        for (idx, mut w) in self.secondaries.drain(..) {
+            match w.write(bs.clone()).await {
+                Ok(()) => secondaries.push((idx, w)),
+                Err(e) => self
+                    .replicator
+                    .diverge(Operation::Write, &self.path, idx, e),
+            }
+        }
+        self.secondaries = secondaries;
+        Ok(())
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        if let Err(e) = self.inner.append(bs.clone()).await {
+            self.abort_secondaries().await;
+            return Err(e);
+        }
+
+        let mut secondaries = Vec::with_capacity(self.secondaries.len());
+        for (idx, mut w) in self.secondaries.drain(..) {
+            match w.append(bs.clone()).await {
+                Ok(()) => secondaries.push((idx, w)),
+                Err(e) => self
+                    .replicator
+                    .diverge(Operation::Write, &self.path, idx, e),
+            }
+        }
+        self.secondaries = secondaries;
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        let res = self.inner.abort().await;
+        self.abort_secondaries().await;
+        res
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Err(e) = self.inner.close().await {
+            self.abort_secondaries().await;
+            return Err(e);
+        }
+
+        for (idx, mut w) in self.secondaries.drain(..) {
+            if let Err(e) = w.close().await {
+                self.replicator
+                    .diverge(Operation::Write, &self.path, idx, e);
+            }
+        }
+
+        if let Some(tx) = &self.queue {
+            self.replicator
+                .send(tx, Task::Write(self.path.clone()))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+impl<A: Accessor, W, S: oio::Write> ReplicationWriter<A, W, S> {
+    /// Abort all secondaries so that partial uploads will not be left
+    /// behind after primary failed.
+    async fn abort_secondaries(&mut self) {
+        for (_, mut w) in self.secondaries.drain(..) {
+            // Secondaries could have been failed before, so errors are ignored.
+            let _ = w.abort().await;
+        }
+    }
+}
+
+impl<A: Accessor, W: oio::BlockingWrite, S: oio::BlockingWrite> oio::BlockingWrite
+    for ReplicationWriter<A, W, S>
+{
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs.clone())?;
+
+        let mut secondaries = Vec::with_capacity(self.secondaries.len());
+        for (idx, mut w) in self.secondaries.drain(..) {
+            match w.write(bs.clone()) {
+                Ok(()) => secondaries.push((idx, w)),
+                Err(e) => self
+                    .replicator
+                    .diverge(Operation::BlockingWrite, &self.path, idx, e),
+            }
+        }
+        self.secondaries = secondaries;
+        Ok(())
+    }
+
+    fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.append(bs.clone())?;
+
+        let mut secondaries = Vec::with_capacity(self.secondaries.len());
+        for (idx, mut w) in self.secondaries.drain(..) {
+            match w.append(bs.clone()) {
+                Ok(()) => secondaries.push((idx, w)),
+                Err(e) => self
+                    .replicator
+                    .diverge(Operation::BlockingWrite, &self.path, idx, e),
+            }
+        }
+        self.secondaries = secondaries;
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()?;
+
+        for (idx, mut w) in self.secondaries.drain(..) {
+            if let Err(e) = w.close() {
+                self.replicator
+                    .diverge(Operation::BlockingWrite, &self.path, idx, e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::layers::PermissionLayer;
+    use crate::services::Memory;
+
+    fn memory() -> Operator {
+        Operator::new(Memory::default()).unwrap().finish()
+    }
+
+    #[tokio::test]
+    async fn test_sync_replication() {
+        let secondary = memory();
+        let op = memory().layer(ReplicationLayer::new(vec![secondary.clone()]));
+
+        let mut w = op.writer("test").await.unwrap();
+        w.append("Hello, ").await.unwrap();
+        w.append("World!").await.unwrap();
+        w.close().await.unwrap();
+        op.create_dir("dir/").await.unwrap();
+        op.blocking().write("blocking", "Hello").unwrap();
+
+        assert_eq!(secondary.read("test").await.unwrap(), b"Hello, World!");
+        assert!(secondary.is_exist("dir/").await.unwrap());
+        assert_eq!(secondary.read("blocking").await.unwrap(), b"Hello");
+
+        op.delete("test").await.unwrap();
+        assert!(!secondary.is_exist("test").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_async_replication() {
+        let secondary = memory();
+        let layer = ReplicationLayer::new(vec![secondary.clone()])
+            .with_mode(ReplicationMode::Async { queue_size: 4 });
+        let op = memory().layer(layer.clone());
+
+        for i in 0..16 {
+            op.write(&format!("test-{i}"), "Hello, World!")
+                .await
+                .unwrap();
+        }
+        op.delete("test-0").await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(10), layer.flush())
+            .await
+            .expect("flush must finish");
+        assert!(!secondary.is_exist("test-0").await.unwrap());
+        for i in 1..16 {
+            assert_eq!(
+                secondary.read(&format!("test-{i}")).await.unwrap(),
+                b"Hello, World!"
+            );
+        }
+    }
+
+    #[test]
+    fn test_async_replication_without_runtime() {
+        let secondary = memory();
+        let op = memory().layer(
+            ReplicationLayer::new(vec![secondary.clone()])
+                .with_mode(ReplicationMode::Async { queue_size: 4 }),
+        );
+
+        futures::executor::block_on(async {
+            op.write("test", "Hello, World!").await.unwrap();
+            op.create_dir("dir/").await.unwrap();
+
+            assert_eq!(secondary.read("test").await.unwrap(), b"Hello, World!");
+            assert!(secondary.is_exist("dir/").await.unwrap());
+        });
+    }
+
+    #[test]
+    fn test_async_replication_after_runtime_shutdown() {
+        let divergences = Arc::new(Mutex::new(Vec::new()));
+        let ds = divergences.clone();
+
+        let secondary = memory();
+        let layer = ReplicationLayer::new(vec![secondary.clone()])
+            .with_mode(ReplicationMode::Async { queue_size: 4 })
+            .with_divergence_handler(move |d| ds.lock().push(d));
+        let op = memory().layer(layer.clone());
+
+        // The background task is spawned on this runtime and dropped with it.
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        rt.block_on(op.write("first", "Hello, World!")).unwrap();
+        drop(rt);
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            op.write("second", "Hello, World!").await.unwrap();
+            op.create_dir("dir/").await.unwrap();
+
+            tokio::time::timeout(Duration::from_secs(10), layer.flush())
+                .await
+                .expect("flush must finish");
+
+            assert_eq!(secondary.read("second").await.unwrap(), b"Hello, World!");
+            assert!(secondary.is_exist("dir/").await.unwrap());
+
+            // The first task is either replicated or reported as divergence.
+            let reported = divergences.lock().iter().any(|d| d.path == "first");
+            assert!(reported || secondary.is_exist("first").await.unwrap());
+        });
+    }
+
+    #[tokio::test]
+    async fn test_read_fallback() {
+        let secondary = memory();
+        secondary.write("test", "Hello, World!").await.unwrap();
+
+        let op = memory().layer(ReplicationLayer::new(vec![secondary.clone()]));
+        assert_eq!(op.read("test").await.unwrap(), b"Hello, World!");
+        assert_eq!(op.stat("test").await.unwrap().content_length(), 13);
+
+        let op = memory().layer(
+            ReplicationLayer::new(vec![secondary]).with_read_fallback(ReadFallback::Disabled),
+        );
+        let err = op.read("test").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_divergence() {
+        let divergences = Arc::new(Mutex::new(Vec::new()));
+        let ds = divergences.clone();
+
+        let broken = memory().layer(PermissionLayer::read_only());
+        let secondary = memory();
+        let op = memory().layer(
+            ReplicationLayer::new(vec![broken, secondary.clone()])
+                .with_divergence_handler(move |d| ds.lock().push(d)),
+        );
+
+        op.write("test", "Hello, World!").await.unwrap();
+        assert_eq!(secondary.read("test").await.unwrap(), b"Hello, World!");
+
+        let divergences = divergences.lock();
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].operation, Operation::Write);
+        assert_eq!(divergences[0].path, "test");
+        assert_eq!(divergences[0].secondary, 0);
+        assert_eq!(divergences[0].error.kind(), ErrorKind::PermissionDenied);
+    }
+}
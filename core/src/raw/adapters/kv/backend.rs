@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp::min;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -189,14 +190,14 @@ where
     fn apply_range(&self, mut bs: Vec<u8>, br: BytesRange) -> Vec<u8> {
         match (br.offset(), br.size()) {
             (Some(offset), Some(size)) => {
-                let mut bs = bs.split_off(offset as usize);
+                let mut bs = bs.split_off(min(offset as usize, bs.len()));
                 if (size as usize) < bs.len() {
                     let _ = bs.split_off(size as usize);
                 }
                 bs
             }
-            (Some(offset), None) => bs.split_off(offset as usize),
-            (None, Some(size)) => bs.split_off(bs.len() - size as usize),
+            (Some(offset), None) => bs.split_off(min(offset as usize, bs.len())),
+            (None, Some(size)) => bs.split_off(bs.len() - min(size as usize, bs.len())),
             (None, None) => bs,
         }
     }
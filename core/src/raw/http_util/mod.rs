@@ -45,6 +45,7 @@ pub use header::parse_last_modified;
 pub use header::parse_location;
 
 mod uri;
+pub use uri::percent_decode_path;
 pub use uri::percent_encode_path;
 
 mod error;
@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use bytes::Bytes;
+use bytes::BytesMut;
+
+/// The default size of chunks split by [`AppendBuffer`].
+///
+/// Services like s3 require every appended part except the last one to be
+/// at least 5MiB, and allow at most 10000 parts. 8MiB leaves a margin and
+/// still allows appending about 80GiB.
+pub const DEFAULT_APPEND_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// AppendBuffer buffers small bytes and splits them into chunks that are
+/// large enough to be appended as parts.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use opendal::raw::oio::AppendBuffer;
+///
+/// let mut buf = AppendBuffer::with_chunk_size(4);
+/// assert_eq!(buf.push(Bytes::from("abc")), None);
+/// assert_eq!(buf.push(Bytes::from("de")), Some(Bytes::from("abcde")));
+/// assert_eq!(buf.push(Bytes::from("f")), None);
+/// assert_eq!(buf.finish(), Some(Bytes::from("f")));
+/// assert_eq!(buf.finish(), None);
+/// ```
+#[derive(Debug)]
+pub struct AppendBuffer {
+    buf: BytesMut,
+    chunk_size: usize,
+}
+
+impl Default for AppendBuffer {
+    fn default() -> Self {
+        Self::with_chunk_size(DEFAULT_APPEND_CHUNK_SIZE)
+    }
+}
+
+impl AppendBuffer {
+    /// Create a new buffer with [`DEFAULT_APPEND_CHUNK_SIZE`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new buffer that splits chunks of at least `chunk_size`.
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            buf: BytesMut::new(),
+            chunk_size,
+        }
+    }
+
+    /// Returns the size of buffered bytes.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns `true` if there are no buffered bytes.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Push bytes into the buffer, returns all buffered bytes as a chunk
+    /// once they reach the chunk size.
+    ///
+    /// Bytes larger than the chunk size will be returned without copying
+    /// if nothing is buffered.
+    pub fn push(&mut self, bs: Bytes) -> Option<Bytes> {
+        if self.buf.is_empty() && bs.len() >= self.chunk_size {
+            return Some(bs);
+        }
+
+        self.buf.extend_from_slice(&bs);
+        if self.buf.len() >= self.chunk_size {
+            Some(self.buf.split().freeze())
+        } else {
+            None
+        }
+    }
+
+    /// Take the rest buffered bytes as the last chunk, returns `None` if
+    /// nothing is buffered.
+    pub fn finish(&mut self) -> Option<Bytes> {
+        if self.buf.is_empty() {
+            None
+        } else {
+            Some(self.buf.split().freeze())
+        }
+    }
+
+    /// Drop all buffered bytes.
+    pub fn clear(&mut self) {
+        self.buf.clear()
+    }
+}
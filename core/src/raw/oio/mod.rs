@@ -44,6 +44,10 @@ pub use write::Writer;
 mod cursor;
 pub use cursor::Cursor;
 
+mod append_buffer;
+pub use append_buffer::AppendBuffer;
+pub use append_buffer::DEFAULT_APPEND_CHUNK_SIZE;
+
 mod into_streamable;
 pub use into_streamable::into_streamable_reader;
 pub use into_streamable::IntoStreamableReader;
@@ -369,9 +369,11 @@ impl Accessor for FsBackend {
         let br = args.range();
         let (start, end) = match (br.offset(), br.size()) {
             // Read a specific range.
-            (Some(offset), Some(size)) => (offset, min(offset + size, total_length)),
+            (Some(offset), Some(size)) => {
+                (min(offset, total_length), min(offset + size, total_length))
+            }
             // Read from offset.
-            (Some(offset), None) => (offset, total_length),
+            (Some(offset), None) => (min(offset, total_length), total_length),
             // Read the last size bytes.
             (None, Some(size)) => (
                 if total_length > size {
@@ -498,7 +500,12 @@ impl Accessor for FsBackend {
         let f = match tokio::fs::read_dir(&p).await {
             Ok(rd) => rd,
             Err(e) => {
-                return if e.kind() == io::ErrorKind::NotFound {
+                // Listing a file as dir is the same as listing a non-exist dir.
+                let is_file = match fs::metadata(&p).await {
+                    Ok(meta) => meta.is_file(),
+                    Err(_) => false,
+                };
+                return if e.kind() == io::ErrorKind::NotFound || is_file {
                     Ok((RpList::default(), None))
                 } else {
                     Err(parse_io_error(e))
@@ -555,9 +562,11 @@ impl Accessor for FsBackend {
         let br = args.range();
         let (start, end) = match (br.offset(), br.size()) {
             // Read a specific range.
-            (Some(offset), Some(size)) => (offset, min(offset + size, total_length)),
+            (Some(offset), Some(size)) => {
+                (min(offset, total_length), min(offset + size, total_length))
+            }
             // Read from offset.
-            (Some(offset), None) => (offset, total_length),
+            (Some(offset), None) => (min(offset, total_length), total_length),
             // Read the last size bytes.
             (None, Some(size)) => (
                 if total_length > size {
@@ -680,10 +689,15 @@ impl Accessor for FsBackend {
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let f = match std::fs::read_dir(p) {
+        let f = match std::fs::read_dir(&p) {
             Ok(rd) => rd,
             Err(e) => {
-                return if e.kind() == io::ErrorKind::NotFound {
+                // Listing a file as dir is the same as listing a non-exist dir.
+                let is_file = match std::fs::metadata(&p) {
+                    Ok(meta) => meta.is_file(),
+                    Err(_) => false,
+                };
+                return if e.kind() == io::ErrorKind::NotFound || is_file {
                     Ok((RpList::default(), None))
                 } else {
                     Err(parse_io_error(e))
@@ -75,11 +75,13 @@ impl oio::Write for FsWriter<tokio::fs::File> {
         Ok(())
     }
 
+    /// # Notes
+    ///
+    /// The file has been truncated while creating the writer, so we can
+    /// remove it directly no matter whether atomic write is enabled.
     async fn abort(&mut self) -> Result<()> {
-        Err(Error::new(
-            ErrorKind::Unsupported,
-            "output writer doesn't support abort",
-        ))
+        let path = self.tmp_path.as_ref().unwrap_or(&self.target_path);
+        tokio::fs::remove_file(path).await.map_err(parse_io_error)
     }
 
     async fn close(&mut self) -> Result<()> {
@@ -313,9 +313,12 @@ impl Accessor for FtpBackend {
     type BlockingPager = ();
 
     fn info(&self) -> AccessorInfo {
+        let scheme = if self.enable_secure { "ftps" } else { "ftp" };
+
         let mut am = AccessorInfo::default();
         am.set_scheme(Scheme::Ftp)
             .set_root(&self.root)
+            .set_name(&format!("{scheme}://{}", self.endpoint))
             .set_capabilities(
                 AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
             );
@@ -203,6 +203,7 @@ impl Builder for HdfsBuilder {
         debug!("backend build finished: {:?}", &self);
         Ok(HdfsBackend {
             root,
+            name_node: name_node.clone(),
             client: Arc::new(client),
         })
     }
@@ -212,6 +213,7 @@ impl Builder for HdfsBuilder {
 #[derive(Debug, Clone)]
 pub struct HdfsBackend {
     root: String,
+    name_node: String,
     client: Arc<hdrs::Client>,
 }
 
@@ -232,6 +234,7 @@ impl Accessor for HdfsBackend {
         let mut am = AccessorInfo::default();
         am.set_scheme(Scheme::Hdfs)
             .set_root(&self.root)
+            .set_name(&self.name_node)
             .set_capabilities(
                 AccessorCapability::Read
                     | AccessorCapability::Write
@@ -257,6 +257,7 @@ impl Accessor for HttpBackend {
         let mut ma = AccessorInfo::default();
         ma.set_scheme(Scheme::Http)
             .set_root(&self.root)
+            .set_name(&self.endpoint)
             .set_capabilities(AccessorCapability::Read)
             .set_hints(AccessorHint::ReadStreamable);
 
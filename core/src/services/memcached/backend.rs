@@ -230,7 +230,7 @@ impl kv::Adapter for Adapter {
     fn metadata(&self) -> kv::Metadata {
         kv::Metadata::new(
             Scheme::Memcached,
-            "memcached",
+            &format!("tcp://{}", self.endpoint),
             AccessorCapability::Read | AccessorCapability::Write,
         )
     }
@@ -89,6 +89,11 @@ mod moka;
 #[cfg(feature = "services-moka")]
 pub use self::moka::Moka;
 
+#[cfg(feature = "services-mount")]
+mod mount;
+#[cfg(feature = "services-mount")]
+pub use self::mount::Mount;
+
 #[cfg(feature = "services-obs")]
 mod obs;
 #[cfg(feature = "services-obs")]
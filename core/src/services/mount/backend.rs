@@ -0,0 +1,747 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::mem;
+
+use async_trait::async_trait;
+use flagset::FlagSet;
+
+use super::pager::MountPager;
+use crate::ops::*;
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// Mount multiple operators into one virtual tree.
+///
+/// # Capabilities
+///
+/// This service can be used to:
+///
+/// - [x] read
+/// - [x] write
+/// - [x] copy
+/// - [x] rename
+/// - [x] list
+/// - [x] scan
+/// - [x] presign
+/// - [x] blocking
+///
+/// Capabilities are the union of all mounted operators, operations on a
+/// mount point that doesn't support them will return
+/// [`ErrorKind::Unsupported`].
+///
+/// # Notes
+///
+/// - Operations are dispatched to the mount point with the longest
+///   matching prefix, and the prefix will be stripped from the path.
+/// - Mounting on `/` makes the operator the fallback for all paths that
+///   not under other mount points.
+/// - Parent dirs of mount points are virtual dirs: they always exist and
+///   their listing contains the mount points under them.
+/// - `copy` and `rename` across mount points are implemented by streaming
+///   data from source to destination. The destination will be aborted if
+///   the async copy failed.
+/// - Entries of a mount point under another mount point are shadowed, they
+///   will not be returned by list and scan.
+///
+/// # Configuration
+///
+/// Mount can't be configured by map or env, please use [`MountBuilder::mount`].
+/// Building a mount service from map returns [`ErrorKind::Unsupported`].
+///
+/// # Example
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::services::Memory;
+/// use opendal::services::Mount;
+/// use opendal::Operator;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     let hot = Operator::new(Memory::default())?.finish();
+///     let warm = Operator::new(Memory::default())?.finish();
+///
+///     let mut builder = Mount::default();
+///     builder.mount("/hot/", hot).mount("/warm/", warm);
+///
+///     let op: Operator = Operator::new(builder)?.finish();
+///     op.write("hot/data", "Hello, World!").await?;
+///     op.copy("hot/data", "warm/data").await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Default)]
+pub struct MountBuilder {
+    mounts: Vec<(String, Operator)>,
+    /// The builder is created by `from_map`, which is not supported.
+    from_map: bool,
+}
+
+impl Debug for MountBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("MountBuilder");
+        for (path, op) in &self.mounts {
+            ds.field(path, &op.info().scheme());
+        }
+        ds.finish()
+    }
+}
+
+impl MountBuilder {
+    /// Mount operator at given path.
+    ///
+    /// Path will be normalized into a dir like `/path/to/dir/`.
+    pub fn mount(&mut self, path: &str, op: Operator) -> &mut Self {
+        self.mounts.push((path.to_string(), op));
+        self
+    }
+}
+
+impl Builder for MountBuilder {
+    const SCHEME: Scheme = Scheme::Mount;
+    type Accessor = MountBackend;
+
+    fn from_map(_: HashMap<String, String>) -> Self {
+        Self {
+            from_map: true,
+            ..Default::default()
+        }
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        if self.from_map {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "mount can't be configured by map, please use MountBuilder::mount",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Mount));
+        }
+        if self.mounts.is_empty() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "no mount point is specified")
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Mount),
+            );
+        }
+
+        let mut mounts: Vec<MountPoint> = Vec::with_capacity(self.mounts.len());
+        for (path, op) in mem::take(&mut self.mounts) {
+            // Mount point is stored without leading `/`, root will be empty.
+            let prefix = normalize_root(&path)[1..].to_string();
+            if mounts.iter().any(|m| m.prefix == prefix) {
+                return Err(
+                    Error::new(ErrorKind::ConfigInvalid, "mount point is duplicated")
+                        .with_operation("Builder::build")
+                        .with_context("service", Scheme::Mount)
+                        .with_context("path", path),
+                );
+            }
+
+            mounts.push(MountPoint {
+                prefix,
+                accessor: op.into_inner(),
+            });
+        }
+        // Sort by prefix length so that the longest prefix will be matched first.
+        mounts.sort_by_key(|m| Reverse(m.prefix.len()));
+
+        Ok(MountBackend { mounts })
+    }
+}
+
+struct MountPoint {
+    /// Prefix like `path/to/dir/`, empty for root.
+    prefix: String,
+    accessor: FusedAccessor,
+}
+
+/// Backend for mount services.
+pub struct MountBackend {
+    mounts: Vec<MountPoint>,
+}
+
+impl Debug for MountBackend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut ds = f.debug_struct("MountBackend");
+        for m in &self.mounts {
+            ds.field(&format!("/{}", m.prefix), &m.accessor);
+        }
+        ds.finish()
+    }
+}
+
+impl MountBackend {
+    /// Find the mount point of given path and return the path relative to it.
+    fn route(&self, path: &str) -> Option<(&MountPoint, String)> {
+        let path = if path == "/" { "" } else { path };
+
+        self.mounts.iter().find_map(|m| {
+            path.strip_prefix(&m.prefix).map(|rel| {
+                let rel = if rel.is_empty() { "/" } else { rel };
+                (m, rel.to_string())
+            })
+        })
+    }
+
+    fn route_or_else(
+        &self,
+        op: Operation,
+        path: &str,
+        kind: ErrorKind,
+    ) -> Result<(&MountPoint, String)> {
+        self.route(path).ok_or_else(|| {
+            Error::new(kind, "path is not under any mount point")
+                .with_operation(op)
+                .with_context("service", Scheme::Mount)
+                .with_context("path", path)
+        })
+    }
+
+    /// Check if given path is a dir that exists because of mount points:
+    /// the root of a mount point or the parent dirs of mount points.
+    fn is_virtual_dir(&self, path: &str) -> bool {
+        let path = if path == "/" { "" } else { path };
+        if !path.is_empty() && !path.ends_with('/') {
+            return false;
+        }
+
+        self.mounts.iter().any(|m| m.prefix.starts_with(path))
+    }
+
+    /// Returns the virtual dirs directly under given dir.
+    fn child_dirs(&self, path: &str) -> Vec<oio::Entry> {
+        let path = if path == "/" { "" } else { path };
+
+        let dirs: BTreeSet<String> = self
+            .mounts
+            .iter()
+            .filter(|m| m.prefix.len() > path.len())
+            .filter_map(|m| m.prefix.strip_prefix(path))
+            .filter_map(|rel| rel.split_once('/'))
+            .map(|(name, _)| format!("{path}{name}/"))
+            .collect();
+
+        dirs.into_iter()
+            .map(|p| oio::Entry::new(&p, Metadata::new(EntryMode::DIR)))
+            .collect()
+    }
+
+    /// Returns all virtual dirs and mount points under given dir.
+    fn descendant_dirs(&self, path: &str) -> (Vec<oio::Entry>, Vec<&MountPoint>) {
+        let path = if path == "/" { "" } else { path };
+
+        let mut dirs = BTreeSet::new();
+        let mut mounts = Vec::new();
+        for m in &self.mounts {
+            if m.prefix.len() <= path.len() || !m.prefix.starts_with(path) {
+                continue;
+            }
+            mounts.push(m);
+
+            let mut idx = path.len();
+            while let Some(pos) = m.prefix[idx..].find('/') {
+                idx += pos + 1;
+                dirs.insert(m.prefix[..idx].to_string());
+            }
+        }
+
+        let dirs = dirs
+            .into_iter()
+            .map(|p| oio::Entry::new(&p, Metadata::new(EntryMode::DIR)))
+            .collect();
+        (dirs, mounts)
+    }
+
+    /// Returns the prefixes of all mount points, entries under them will
+    /// be skipped by pagers of their parent mount points.
+    fn prefixes(&self) -> Vec<String> {
+        self.mounts.iter().map(|m| m.prefix.clone()).collect()
+    }
+
+    /// Copy file across mount points by streaming.
+    ///
+    /// The writer will be aborted if copy failed.
+    async fn stream_copy(&self, from: (&MountPoint, &str), to: (&MountPoint, &str)) -> Result<()> {
+        let (_, mut r) = from.0.accessor.read(from.1, OpRead::new()).await?;
+        let (_, mut w) =
+            to.0.accessor
+                .write(to.1, OpWrite::new().with_append())
+                .await?;
+
+        let mut buf = oio::AppendBuffer::new();
+        let res = async {
+            while let Some(bs) = r.next().await {
+                if let Some(chunk) = buf.push(bs?) {
+                    w.append(chunk).await?;
+                }
+            }
+            if let Some(chunk) = buf.finish() {
+                w.append(chunk).await?;
+            }
+            w.close().await
+        }
+        .await;
+
+        if res.is_err() {
+            // Ignore the abort error, the copy error is more important.
+            let _ = w.abort().await;
+        }
+        res
+    }
+
+    fn blocking_stream_copy(
+        &self,
+        from: (&MountPoint, &str),
+        to: (&MountPoint, &str),
+    ) -> Result<()> {
+        let (_, r) = from.0.accessor.blocking_read(from.1, OpRead::new())?;
+        let (_, mut w) =
+            to.0.accessor
+                .blocking_write(to.1, OpWrite::new().with_append())?;
+
+        let mut buf = oio::AppendBuffer::new();
+        for bs in r {
+            if let Some(chunk) = buf.push(bs?) {
+                w.append(chunk)?;
+            }
+        }
+        if let Some(chunk) = buf.finish() {
+            w.append(chunk)?;
+        }
+        w.close()
+    }
+}
+
+#[async_trait]
+impl Accessor for MountBackend {
+    type Reader = oio::Reader;
+    type BlockingReader = oio::BlockingReader;
+    type Writer = oio::Writer;
+    type BlockingWriter = oio::BlockingWriter;
+    type Pager = MountPager<oio::Pager>;
+    type BlockingPager = MountPager<oio::BlockingPager>;
+
+    fn info(&self) -> AccessorInfo {
+        let mut capabilities = FlagSet::default();
+        for m in &self.mounts {
+            capabilities |= m.accessor.info().capabilities();
+        }
+
+        let mut am = AccessorInfo::default();
+        am.set_scheme(Scheme::Mount)
+            .set_root("/")
+            .set_capabilities(capabilities)
+            // Mounted operators are completed, so their readers are
+            // always seekable and streamable.
+            .set_hints(AccessorHint::ReadSeekable | AccessorHint::ReadStreamable);
+
+        am
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        if self.is_virtual_dir(path) {
+            return Ok(RpCreate::default());
+        }
+
+        let (m, rel) = self.route_or_else(Operation::CreateDir, path, ErrorKind::Unsupported)?;
+        m.accessor.create_dir(&rel, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let (m, rel) = self.route_or_else(Operation::Read, path, ErrorKind::NotFound)?;
+        m.accessor.read(&rel, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let (m, rel) = self.route_or_else(Operation::Write, path, ErrorKind::Unsupported)?;
+        m.accessor.write(&rel, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let (fm, frel) = self.route_or_else(Operation::Copy, from, ErrorKind::NotFound)?;
+        let (tm, trel) = self.route_or_else(Operation::Copy, to, ErrorKind::Unsupported)?;
+
+        if fm.prefix == tm.prefix {
+            match fm.accessor.copy(&frel, &trel, args).await {
+                Err(e) if e.kind() == ErrorKind::Unsupported => {}
+                res => return res,
+            }
+        }
+
+        self.stream_copy((fm, &frel), (tm, &trel)).await?;
+        Ok(RpCopy::default())
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let (fm, frel) = self.route_or_else(Operation::Rename, from, ErrorKind::NotFound)?;
+        let (tm, trel) = self.route_or_else(Operation::Rename, to, ErrorKind::Unsupported)?;
+
+        if fm.prefix == tm.prefix {
+            match fm.accessor.rename(&frel, &trel, args).await {
+                Err(e) if e.kind() == ErrorKind::Unsupported => {}
+                res => return res,
+            }
+        }
+
+        self.stream_copy((fm, &frel), (tm, &trel)).await?;
+        fm.accessor.delete(&frel, OpDelete::new()).await?;
+        Ok(RpRename::default())
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if self.is_virtual_dir(path) {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let (m, rel) = self.route_or_else(Operation::Stat, path, ErrorKind::NotFound)?;
+        m.accessor.stat(&rel, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        // Virtual dirs can't be deleted, and not existing paths are
+        // treated as deleted.
+        if self.is_virtual_dir(path) {
+            return Ok(RpDelete::default());
+        }
+
+        match self.route(path) {
+            Some((m, rel)) => m.accessor.delete(&rel, args).await,
+            None => Ok(RpDelete::default()),
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let dirs = self.child_dirs(path);
+
+        let mut pagers = VecDeque::new();
+        if let Some((m, rel)) = self.route(path) {
+            let (_, p) = m.accessor.list(&rel, args).await?;
+            pagers.push_back((m.prefix.clone(), p));
+        }
+
+        Ok((
+            RpList::default(),
+            MountPager::new(dirs, pagers, self.prefixes()),
+        ))
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        let (dirs, mounts) = self.descendant_dirs(path);
+
+        let mut pagers = VecDeque::new();
+        if let Some((m, rel)) = self.route(path) {
+            let (_, p) = m.accessor.scan(&rel, args.clone()).await?;
+            pagers.push_back((m.prefix.clone(), p));
+        }
+        for m in mounts {
+            let (_, p) = m.accessor.scan("/", args.clone()).await?;
+            pagers.push_back((m.prefix.clone(), p));
+        }
+
+        Ok((
+            RpScan::default(),
+            MountPager::new(dirs, pagers, self.prefixes()),
+        ))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let mut results = Vec::with_capacity(args.operation().len());
+        let mut groups: HashMap<&str, (&MountPoint, Vec<(String, BatchOperation)>)> =
+            HashMap::new();
+
+        for (path, op) in args.into_operation() {
+            match self.route(&path) {
+                Some((m, rel)) => groups
+                    .entry(m.prefix.as_str())
+                    .or_insert_with(|| (m, vec![]))
+                    .1
+                    .push((rel, op)),
+                // Deleting not existing path is treated as succeeded.
+                None => results.push((path, Ok(RpDelete::default().into()))),
+            }
+        }
+
+        for (prefix, (m, ops)) in groups {
+            if m.accessor
+                .info()
+                .capabilities()
+                .contains(AccessorCapability::Batch)
+            {
+                let rp = m.accessor.batch(OpBatch::new(ops)).await?;
+                results.extend(
+                    rp.into_results()
+                        .into_iter()
+                        .map(|(rel, res)| (format!("{prefix}{rel}"), res)),
+                );
+                continue;
+            }
+
+            for (rel, op) in ops {
+                let res = match op {
+                    BatchOperation::Delete(op) => m.accessor.delete(&rel, op).await,
+                };
+                results.push((format!("{prefix}{rel}"), res.map(|v| v.into())));
+            }
+        }
+
+        Ok(RpBatch::new(results))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let (m, rel) = self.route_or_else(Operation::Presign, path, ErrorKind::NotFound)?;
+        m.accessor.presign(&rel, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        if self.is_virtual_dir(path) {
+            return Ok(RpCreate::default());
+        }
+
+        let (m, rel) =
+            self.route_or_else(Operation::BlockingCreateDir, path, ErrorKind::Unsupported)?;
+        m.accessor.blocking_create_dir(&rel, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let (m, rel) = self.route_or_else(Operation::BlockingRead, path, ErrorKind::NotFound)?;
+        m.accessor.blocking_read(&rel, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let (m, rel) =
+            self.route_or_else(Operation::BlockingWrite, path, ErrorKind::Unsupported)?;
+        m.accessor.blocking_write(&rel, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let (fm, frel) = self.route_or_else(Operation::BlockingCopy, from, ErrorKind::NotFound)?;
+        let (tm, trel) = self.route_or_else(Operation::BlockingCopy, to, ErrorKind::Unsupported)?;
+
+        if fm.prefix == tm.prefix {
+            match fm.accessor.blocking_copy(&frel, &trel, args) {
+                Err(e) if e.kind() == ErrorKind::Unsupported => {}
+                res => return res,
+            }
+        }
+
+        self.blocking_stream_copy((fm, &frel), (tm, &trel))?;
+        Ok(RpCopy::default())
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let (fm, frel) = self.route_or_else(Operation::BlockingMove, from, ErrorKind::NotFound)?;
+        let (tm, trel) = self.route_or_else(Operation::BlockingMove, to, ErrorKind::Unsupported)?;
+
+        if fm.prefix == tm.prefix {
+            match fm.accessor.blocking_rename(&frel, &trel, args) {
+                Err(e) if e.kind() == ErrorKind::Unsupported => {}
+                res => return res,
+            }
+        }
+
+        self.blocking_stream_copy((fm, &frel), (tm, &trel))?;
+        fm.accessor.blocking_delete(&frel, OpDelete::new())?;
+        Ok(RpRename::default())
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if self.is_virtual_dir(path) {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        let (m, rel) = self.route_or_else(Operation::BlockingStat, path, ErrorKind::NotFound)?;
+        m.accessor.blocking_stat(&rel, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        if self.is_virtual_dir(path) {
+            return Ok(RpDelete::default());
+        }
+
+        match self.route(path) {
+            Some((m, rel)) => m.accessor.blocking_delete(&rel, args),
+            None => Ok(RpDelete::default()),
+        }
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let dirs = self.child_dirs(path);
+
+        let mut pagers = VecDeque::new();
+        if let Some((m, rel)) = self.route(path) {
+            let (_, p) = m.accessor.blocking_list(&rel, args)?;
+            pagers.push_back((m.prefix.clone(), p));
+        }
+
+        Ok((
+            RpList::default(),
+            MountPager::new(dirs, pagers, self.prefixes()),
+        ))
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        let (dirs, mounts) = self.descendant_dirs(path);
+
+        let mut pagers = VecDeque::new();
+        if let Some((m, rel)) = self.route(path) {
+            let (_, p) = m.accessor.blocking_scan(&rel, args.clone())?;
+            pagers.push_back((m.prefix.clone(), p));
+        }
+        for m in mounts {
+            let (_, p) = m.accessor.blocking_scan("/", args.clone())?;
+            pagers.push_back((m.prefix.clone(), p));
+        }
+
+        Ok((
+            RpScan::default(),
+            MountPager::new(dirs, pagers, self.prefixes()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    fn new_operator() -> (Operator, Operator, Operator) {
+        let hot = Operator::new(Memory::default()).unwrap().finish();
+        let warm = Operator::new(Memory::default()).unwrap().finish();
+
+        let mut builder = MountBuilder::default();
+        builder
+            .mount("/data/hot", hot.clone())
+            .mount("/data/warm/", warm.clone());
+        let op = Operator::new(builder).unwrap().finish();
+
+        (op, hot, warm)
+    }
+
+    async fn list(op: &Operator, path: &str) -> Vec<String> {
+        let mut paths: Vec<String> = op
+            .list(path)
+            .await
+            .unwrap()
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_build_without_mount() {
+        let err = MountBuilder::default().build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_route() {
+        let (op, hot, warm) = new_operator();
+
+        op.write("data/hot/a", "Hello, World!").await.unwrap();
+        op.write("data/warm/b", "Hello, World!").await.unwrap();
+        assert_eq!(hot.read("a").await.unwrap(), b"Hello, World!");
+        assert_eq!(warm.read("b").await.unwrap(), b"Hello, World!");
+
+        let err = op.write("data/cold/c", "Hello, World!").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op.stat("data/cold/c").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        assert!(op.stat("/").await.unwrap().mode().is_dir());
+        assert!(op.stat("data/").await.unwrap().mode().is_dir());
+        assert!(op.stat("data/hot/").await.unwrap().mode().is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_list() {
+        let (op, _, _) = new_operator();
+        op.write("data/hot/a", "Hello, World!").await.unwrap();
+
+        assert_eq!(list(&op, "/").await, vec!["data/"]);
+        assert_eq!(list(&op, "data/").await, vec!["data/hot/", "data/warm/"]);
+        assert_eq!(list(&op, "data/hot/").await, vec!["data/hot/a"]);
+    }
+
+    #[test]
+    fn test_build_from_map() {
+        let err = MountBuilder::from_map(HashMap::new()).build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_shadowed_entries() {
+        let root = Operator::new(Memory::default()).unwrap().finish();
+        let hot = Operator::new(Memory::default()).unwrap().finish();
+        root.write("data/hot/a", "shadowed").await.unwrap();
+        root.write("data/b", "Hello, World!").await.unwrap();
+        hot.write("a", "Hello, World!").await.unwrap();
+
+        let mut builder = MountBuilder::default();
+        builder.mount("/", root).mount("/data/hot/", hot);
+        let op = Operator::new(builder).unwrap().finish();
+
+        assert_eq!(list(&op, "data/").await, vec!["data/b", "data/hot/"]);
+
+        let mut paths: Vec<String> = op
+            .scan("/")
+            .await
+            .unwrap()
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec!["data/", "data/b", "data/hot/", "data/hot/a"],
+            "entries must not be duplicated"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_and_rename_across_mounts() {
+        let (op, hot, warm) = new_operator();
+        op.write("data/hot/a", "Hello, World!").await.unwrap();
+
+        op.copy("data/hot/a", "data/warm/a").await.unwrap();
+        assert_eq!(warm.read("a").await.unwrap(), b"Hello, World!");
+
+        op.rename("data/warm/a", "data/hot/b").await.unwrap();
+        assert_eq!(hot.read("b").await.unwrap(), b"Hello, World!");
+        assert!(!warm.is_exist("a").await.unwrap());
+
+        op.blocking()
+            .copy("data/hot/b", "data/warm/c")
+            .expect("blocking copy must succeed");
+        assert_eq!(warm.read("c").await.unwrap(), b"Hello, World!");
+    }
+}
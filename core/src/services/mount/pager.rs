@@ -0,0 +1,124 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// MountPager will chain the virtual dirs synthesized from mount points
+/// and pagers returned by mounted accessors.
+pub struct MountPager<P> {
+    dirs: Vec<oio::Entry>,
+    pagers: VecDeque<(String, P)>,
+    /// Paths that have been returned as virtual dirs, entries from pagers
+    /// with the same path will be skipped.
+    seen: HashSet<String>,
+    /// Prefixes of all mount points. Entries from a pager under a longer
+    /// prefix are shadowed by that mount point and will be skipped.
+    prefixes: Vec<String>,
+}
+
+impl<P> MountPager<P> {
+    pub fn new(
+        dirs: Vec<oio::Entry>,
+        pagers: VecDeque<(String, P)>,
+        prefixes: Vec<String>,
+    ) -> Self {
+        let seen = dirs.iter().map(|e| e.path().to_string()).collect();
+
+        Self {
+            dirs,
+            pagers,
+            seen,
+            prefixes,
+        }
+    }
+
+    /// Check if the path is served by another mount point under `prefix`.
+    fn is_shadowed(&self, prefix: &str, path: &str) -> bool {
+        self.prefixes
+            .iter()
+            .any(|p| p.len() > prefix.len() && p.starts_with(prefix) && path.starts_with(p))
+    }
+
+    /// Build entries' paths with the prefix of mount point.
+    fn rebuild(&self, prefix: &str, entries: Vec<oio::Entry>) -> Vec<oio::Entry> {
+        entries
+            .into_iter()
+            .filter_map(|mut e| {
+                let path = match e.path() {
+                    "/" => prefix.to_string(),
+                    v => format!("{prefix}{v}"),
+                };
+                if path.is_empty() || self.seen.contains(&path) || self.is_shadowed(prefix, &path) {
+                    return None;
+                }
+                e.set_path(&path);
+                Some(e)
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl oio::Page for MountPager<oio::Pager> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if !self.dirs.is_empty() {
+            return Ok(Some(std::mem::take(&mut self.dirs)));
+        }
+
+        while let Some((prefix, pager)) = self.pagers.front_mut() {
+            match pager.next().await? {
+                Some(entries) => {
+                    let prefix = prefix.clone();
+                    return Ok(Some(self.rebuild(&prefix, entries)));
+                }
+                None => {
+                    self.pagers.pop_front();
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl oio::BlockingPage for MountPager<oio::BlockingPager> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if !self.dirs.is_empty() {
+            return Ok(Some(std::mem::take(&mut self.dirs)));
+        }
+
+        while let Some((prefix, pager)) = self.pagers.front_mut() {
+            match pager.next()? {
+                Some(entries) => {
+                    let prefix = prefix.clone();
+                    return Ok(Some(self.rebuild(&prefix, entries)));
+                }
+                None => {
+                    self.pagers.pop_front();
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
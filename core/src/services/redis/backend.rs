@@ -313,9 +313,14 @@ impl Adapter {
 #[async_trait]
 impl kv::Adapter for Adapter {
     fn metadata(&self) -> kv::Metadata {
+        let name = match &self.client.get_connection_info().addr {
+            ConnectionAddr::Unix(path) => format!("unix://{}", path.display()),
+            addr => format!("tcp://{addr}"),
+        };
+
         kv::Metadata::new(
             Scheme::Redis,
-            &self.client.get_connection_info().addr.to_string(),
+            &name,
             AccessorCapability::Read | AccessorCapability::Write,
         )
     }
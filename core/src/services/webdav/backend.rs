@@ -266,6 +266,7 @@ impl Accessor for WebdavBackend {
         let mut ma = AccessorInfo::default();
         ma.set_scheme(Scheme::Webdav)
             .set_root(&self.root)
+            .set_name(&self.endpoint)
             .set_capabilities(
                 AccessorCapability::Read
                     | AccessorCapability::Write
@@ -625,6 +625,32 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn writer(&self, path: &str) -> Result<BlockingWriter> {
+        self.writer_with(path, OpWrite::default())
+    }
+
+    /// Write multiple bytes into given path with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - Write will make sure all bytes has been written, or an error will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::BlockingOperator;
+    /// use opendal::ops::OpWrite;
+    ///
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// let args = OpWrite::new().with_content_type("application/octet-stream");
+    /// let mut w = op.writer_with("path/to/file", args)?;
+    /// w.append(vec![0; 4096])?;
+    /// w.append(vec![1; 4096])?;
+    /// w.close()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn writer_with(&self, path: &str, args: OpWrite) -> Result<BlockingWriter> {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::FILE) {
@@ -636,8 +662,7 @@ impl BlockingOperator {
             );
         }
 
-        let op = OpWrite::default().with_append();
-        BlockingWriter::create_dir(self.inner().clone(), &path, op)
+        BlockingWriter::create_dir(self.inner().clone(), &path, args.with_append())
     }
 
     /// Delete given path.
@@ -665,6 +690,49 @@ impl BlockingOperator {
         Ok(())
     }
 
+    /// Remove the path and all nested dirs and files recursively.
+    ///
+    /// # Notes
+    ///
+    /// Unlike [`Operator::remove_all`], entries are always deleted one by
+    /// one since there is no blocking batch API.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.remove_all("path/to/dir")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_all(&self, path: &str) -> Result<()> {
+        let meta = match self.stat(path) {
+            // If object exists.
+            Ok(metadata) => metadata,
+
+            // If object not found, return success.
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+
+            // Pass on any other error.
+            Err(e) => return Err(e),
+        };
+
+        if meta.mode() != EntryMode::DIR {
+            return self.delete(path);
+        }
+
+        for entry in self.scan(path)? {
+            self.delete(entry?.path())?;
+        }
+
+        // Remove the directory itself.
+        self.delete(path)?;
+
+        Ok(())
+    }
+
     /// List current dir path.
     ///
     /// This function will create a new handle to list entries.
@@ -43,10 +43,53 @@ impl OperatorInfo {
     ///
     /// - name for `s3` => bucket name
     /// - name for `azblob` => container name
+    /// - name for `http` => endpoint
     pub fn name(&self) -> &str {
         self.0.name()
     }
 
+    /// Uri of operator like `s3://bucket/path/to/dir/`.
+    ///
+    /// Scheme, name and root are included, while the name of services
+    /// located by endpoint like `http` is used as the authority, or as
+    /// the `endpoint` query if it can't be expressed as an authority.
+    /// Other options like credentials are never exposed, so it's safe
+    /// to be logged. Building an operator via [`Operator::from_uri`]
+    /// with the returned uri will get the same scheme, name and root.
+    pub fn uri(&self) -> String {
+        let (scheme, name, root) = (self.scheme(), self.name(), self.root());
+        let root = percent_encode_path(root);
+
+        let (key, default) = match super::builder::uri_endpoint(scheme) {
+            Some(v) => v,
+            None => {
+                let authority = match scheme {
+                    Scheme::S3
+                    | Scheme::Gcs
+                    | Scheme::Oss
+                    | Scheme::Obs
+                    | Scheme::Wasabi
+                    | Scheme::Azblob
+                    | Scheme::Azdfs => name,
+                    _ => "",
+                };
+                return format!("{scheme}://{authority}{root}");
+            }
+        };
+
+        match name.split_once("://") {
+            // The scheme of endpoint is used as uri scheme directly.
+            Some((ep_scheme, host)) if default.is_empty() && !host.contains('/') => {
+                format!("{ep_scheme}://{host}{root}")
+            }
+            Some((ep_scheme, host)) if ep_scheme == default && !host.contains('/') => {
+                format!("{scheme}://{host}{root}")
+            }
+            _ if name.is_empty() => format!("{scheme}://{root}"),
+            _ => format!("{scheme}://{root}?{key}={}", percent_encode_path(name)),
+        }
+    }
+
     /// Check if current backend supports [`Accessor::read`] or not.
     pub fn can_read(&self) -> bool {
         self.0.capabilities().contains(AccessorCapability::Read)
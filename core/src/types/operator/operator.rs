@@ -15,7 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp::max;
+use std::cmp::min;
+use std::ops::Range;
 use std::ops::RangeBounds;
+use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
@@ -63,7 +67,18 @@ use crate::*;
 pub struct Operator {
     accessor: FusedAccessor,
 
+    // Options are rarely changed, keep them behind an `Arc` to make
+    // `Operator` cheap to clone and move.
+    options: Arc<OperatorOptions>,
+}
+
+/// Options of [`Operator`] which are not used by accessor.
+#[derive(Clone, Copy, Debug)]
+struct OperatorOptions {
     limit: usize,
+    merge_gap: u64,
+    merge_max: u64,
+    read_concurrency: usize,
 }
 
 /// # Operator basic API.
@@ -74,25 +89,94 @@ impl Operator {
 
     pub(crate) fn from_inner(accessor: FusedAccessor) -> Self {
         let limit = accessor.info().max_batch_operations().unwrap_or(1000);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            options: Arc::new(OperatorOptions {
+                limit,
+                merge_gap: 1024 * 1024,
+                merge_max: 16 * 1024 * 1024,
+                read_concurrency: 8,
+            }),
+        }
+    }
+
+    /// Carry the options of `other` that don't depend on accessor.
+    pub(super) fn with_options_of(self, other: &Operator) -> Self {
+        let limit = self.options.limit;
+        self.with_options(|opts| {
+            *opts = OperatorOptions {
+                limit,
+                ..*other.options
+            }
+        })
+    }
+
+    fn with_options(&self, f: impl FnOnce(&mut OperatorOptions)) -> Self {
+        let mut options = *self.options;
+        f(&mut options);
+        Self {
+            accessor: self.accessor.clone(),
+            options: Arc::new(options),
+        }
     }
 
-    pub(super) fn into_inner(self) -> FusedAccessor {
+    pub(crate) fn into_inner(self) -> FusedAccessor {
         self.accessor
     }
 
     /// Get current operator's limit
     pub fn limit(&self) -> usize {
-        self.limit
+        self.options.limit
     }
 
     /// Specify the batch limit.
     ///
     /// Default: 1000
     pub fn with_limit(&self, limit: usize) -> Self {
-        let mut op = self.clone();
-        op.limit = limit;
-        op
+        self.with_options(|opts| opts.limit = limit)
+    }
+
+    /// Get current operator's merge gap.
+    pub fn merge_gap(&self) -> u64 {
+        self.options.merge_gap
+    }
+
+    /// Specify the merge gap used by [`Operator::read_ranges`].
+    ///
+    /// Ranges whose distance is not larger than this gap will be merged
+    /// into one request.
+    ///
+    /// Default: 1MiB
+    pub fn with_merge_gap(&self, merge_gap: u64) -> Self {
+        self.with_options(|opts| opts.merge_gap = merge_gap)
+    }
+
+    /// Get current operator's max merged size.
+    pub fn merge_max(&self) -> u64 {
+        self.options.merge_max
+    }
+
+    /// Specify the max size of a merged request in [`Operator::read_ranges`].
+    ///
+    /// Ranges will not be merged if the merged request would be larger
+    /// than this size. Ranges larger than it are still read as is.
+    ///
+    /// Default: 16MiB
+    pub fn with_merge_max(&self, merge_max: u64) -> Self {
+        self.with_options(|opts| opts.merge_max = merge_max)
+    }
+
+    /// Get current operator's read concurrency.
+    pub fn read_concurrency(&self) -> usize {
+        self.options.read_concurrency
+    }
+
+    /// Specify how many merged requests [`Operator::read_ranges`] will
+    /// send concurrently.
+    ///
+    /// Default: 8
+    pub fn with_read_concurrency(&self, read_concurrency: usize) -> Self {
+        self.with_options(|opts| opts.read_concurrency = read_concurrency.max(1))
     }
 
     /// Get information of underlying accessor.
@@ -118,7 +202,7 @@ impl Operator {
     ///
     /// This operation is nearly no cost.
     pub fn blocking(&self) -> BlockingOperator {
-        BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit)
+        BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit())
     }
 }
 
@@ -534,6 +618,59 @@ impl Operator {
         Ok(buffer)
     }
 
+    /// Read multiple ranges of path into bytes.
+    ///
+    /// Ranges that are close to each other (see [`Operator::with_merge_gap`])
+    /// will be merged into one request up to [`Operator::with_merge_max`],
+    /// and merged requests will be sent concurrently (see
+    /// [`Operator::with_read_concurrency`]). The returning bytes are in the
+    /// same order as input ranges.
+    ///
+    /// # Notes
+    ///
+    /// - The returning content's length may be smaller than the range specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = op
+    ///     .read_ranges("path/to/file", vec![0..1024, 4096..8192])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_ranges(&self, path: &str, ranges: Vec<Range<u64>>) -> Result<Vec<Bytes>> {
+        let merged = merge_ranges(&ranges, self.merge_gap(), self.merge_max());
+
+        let bufs: Vec<Bytes> = stream::iter(merged.clone())
+            .map(|range| async move { self.range_read(path, range).await.map(Bytes::from) })
+            .buffered(self.read_concurrency())
+            .try_collect()
+            .await?;
+
+        Ok(ranges
+            .into_iter()
+            .map(|range| {
+                if range.start >= range.end {
+                    return Bytes::new();
+                }
+
+                // Merged ranges are sorted by start, and the merged range
+                // containing this range is the last one starting before it.
+                let idx = merged.partition_point(|v| v.start <= range.start) - 1;
+                let (start, buf) = (merged[idx].start, &bufs[idx]);
+
+                let end = min((range.end - start) as usize, buf.len());
+                let begin = min((range.start - start) as usize, end);
+                buf.slice(begin..end)
+            })
+            .collect())
+    }
+
     /// Create a new reader which can read the whole path.
     ///
     /// # Examples
@@ -958,7 +1095,7 @@ impl Operator {
         } else {
             input
                 .map(Ok)
-                .try_for_each_concurrent(self.limit, |path| async move {
+                .try_for_each_concurrent(self.limit(), |path| async move {
                     let _ = self.inner().delete(&path, OpDelete::default()).await?;
                     Ok::<(), Error>(())
                 })
@@ -1317,3 +1454,118 @@ impl Operator {
         Ok(rp.into_presigned_request())
     }
 }
+
+/// Merge ranges whose distance is not larger than `gap` into ranges not
+/// larger than `max_size`.
+///
+/// Empty ranges will be skipped, and the returning ranges are sorted by
+/// start. Every input range is covered by the last returning range that
+/// starts before it.
+fn merge_ranges(ranges: &[Range<u64>], gap: u64, max_size: u64) -> Vec<Range<u64>> {
+    let mut ranges: Vec<_> = ranges.iter().filter(|v| v.start < v.end).collect();
+    ranges.sort_unstable_by_key(|v| v.start);
+
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            // Ranges covered by the last one are always merged, others are
+            // merged only if the merged size doesn't exceed `max_size`.
+            Some(last)
+                if range.end <= last.end
+                    || (range.start <= last.end.saturating_add(gap)
+                        && range.end - last.start <= max_size) =>
+            {
+                last.end = max(last.end, range.end)
+            }
+            _ => merged.push(range.clone()),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_ranges() {
+        let cases = vec![
+            ("empty", vec![], 0, vec![]),
+            (
+                "skip empty range",
+                vec![0..0, Range { start: 4, end: 2 }],
+                8,
+                vec![],
+            ),
+            ("disjoint", vec![0..1, 4..5], 0, vec![0..1, 4..5]),
+            (
+                "adjacent",
+                vec![0..1, 1..5],
+                0,
+                vec![Range { start: 0, end: 5 }],
+            ),
+            (
+                "overlapped",
+                vec![0..4, 1..2, 3..8],
+                0,
+                vec![Range { start: 0, end: 8 }],
+            ),
+            (
+                "within gap",
+                vec![6..8, 0..1, 4..5],
+                3,
+                vec![Range { start: 0, end: 8 }],
+            ),
+            ("beyond gap", vec![0..1, 5..6], 3, vec![0..1, 5..6]),
+        ];
+
+        for (name, input, gap, expected) in cases {
+            assert_eq!(merge_ranges(&input, gap, u64::MAX), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_merge_ranges_max_size() {
+        let cases = vec![
+            (
+                "within max",
+                vec![0..2, 3..4],
+                4,
+                vec![Range { start: 0, end: 4 }],
+            ),
+            ("beyond max", vec![0..2, 3..5], 4, vec![0..2, 3..5]),
+            ("large range", vec![0..8, 9..10], 4, vec![0..8, 9..10]),
+            (
+                "covered range",
+                vec![0..8, 2..3],
+                4,
+                vec![Range { start: 0, end: 8 }],
+            ),
+            (
+                "overlapped beyond max",
+                vec![0..3, 2..6],
+                4,
+                vec![0..3, 2..6],
+            ),
+        ];
+
+        for (name, input, max_size, expected) in cases {
+            assert_eq!(merge_ranges(&input, 1, max_size), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_layer_keeps_read_options() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .finish()
+            .with_merge_gap(42)
+            .with_merge_max(4096)
+            .with_read_concurrency(2)
+            .layer(layers::LoggingLayer::default());
+
+        assert_eq!(op.merge_gap(), 42);
+        assert_eq!(op.merge_max(), 4096);
+        assert_eq!(op.read_concurrency(), 2);
+    }
+}
@@ -59,6 +59,8 @@ pub enum Scheme {
     Memory,
     /// [moka][crate::services::Moka]: moka backend support.
     Moka,
+    /// [mount][crate::services::Mount]: Mount multiple operators into one.
+    Mount,
     /// [obs][crate::services::Obs]: Huawei Cloud OBS services.
     Obs,
     /// [oss][crate::services::Oss]: Aliyun Object Storage Services
@@ -125,6 +127,7 @@ impl FromStr for Scheme {
             "memcached" => Ok(Scheme::Memcached),
             "memory" => Ok(Scheme::Memory),
             "moka" => Ok(Scheme::Moka),
+            "mount" => Ok(Scheme::Mount),
             "obs" => Ok(Scheme::Obs),
             "redis" => Ok(Scheme::Redis),
             "rocksdb" => Ok(Scheme::Rocksdb),
@@ -156,6 +159,7 @@ impl From<Scheme> for &'static str {
             Scheme::Memcached => "memcached",
             Scheme::Memory => "memory",
             Scheme::Moka => "moka",
+            Scheme::Mount => "mount",
             Scheme::Obs => "obs",
             Scheme::Redis => "redis",
             Scheme::Rocksdb => "rocksdb",
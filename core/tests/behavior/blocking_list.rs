@@ -71,7 +71,9 @@ macro_rules! behavior_blocking_list_tests {
 
                 test_list_dir,
                 test_list_non_exist_dir,
+                test_list_file_as_dir,
                 test_scan,
+                test_remove_all,
             );
         )*
     };
@@ -120,6 +122,28 @@ pub fn test_list_non_exist_dir(op: BlockingOperator) -> Result<()> {
     Ok(())
 }
 
+/// List a file as dir should return empty like a non exist dir.
+pub fn test_list_file_as_dir(op: BlockingOperator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).expect("write must succeed");
+
+    let obs = op.list(&format!("{}/", path))?;
+    let mut objects = HashMap::new();
+    for de in obs {
+        let de = de?;
+        objects.insert(de.path().to_string(), de);
+    }
+    debug!("got objects: {:?}", objects);
+
+    assert_eq!(objects.len(), 0, "dir should only return empty");
+
+    op.delete(&path).expect("delete must succeed");
+    Ok(())
+}
+
 // Walk top down should output as expected
 pub fn test_scan(op: BlockingOperator) -> Result<()> {
     let expected = vec![
@@ -147,3 +171,27 @@ pub fn test_scan(op: BlockingOperator) -> Result<()> {
     assert!(actual.contains("x/x/x/y"));
     Ok(())
 }
+
+// Remove all should remove all in this path.
+pub fn test_remove_all(op: BlockingOperator) -> Result<()> {
+    let expected = vec![
+        "x/", "x/y", "x/x/", "x/x/y", "x/x/x/", "x/x/x/y", "x/x/x/x/",
+    ];
+    for path in expected.iter() {
+        if path.ends_with('/') {
+            op.create_dir(path)?;
+        } else {
+            op.write(path, "test_remove_all")?;
+        }
+    }
+
+    op.remove_all("x/")?;
+
+    for path in expected.iter() {
+        if path.ends_with('/') {
+            continue;
+        }
+        assert!(!op.is_exist(path)?, "{path} should be removed")
+    }
+    Ok(())
+}
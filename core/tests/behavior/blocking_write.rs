@@ -84,6 +84,7 @@ macro_rules! behavior_blocking_write_tests {
                 test_read_range,
                 test_read_large_range,
                 test_read_not_exist,
+                test_read_out_of_range,
                 test_fuzz_range_reader,
                 test_fuzz_offset_reader,
                 test_fuzz_part_reader,
@@ -302,6 +303,28 @@ pub fn test_read_not_exist(op: BlockingOperator) -> Result<()> {
     Ok(())
 }
 
+/// Read range out of the content should not panic.
+///
+/// Services could either return an error or empty content.
+pub fn test_read_out_of_range(op: BlockingOperator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content).expect("write must succeed");
+
+    let size = size as u64;
+    if let Ok(bs) = op.range_read(&path, size + 1..size + 1024) {
+        assert!(bs.is_empty(), "read out of range must return empty");
+    }
+    if let Ok(bs) = op.range_read(&path, size + 1..) {
+        assert!(bs.is_empty(), "read out of range must return empty");
+    }
+
+    op.delete(&path).expect("delete must succeed");
+    Ok(())
+}
+
 pub fn test_fuzz_range_reader(op: BlockingOperator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
     debug!("Generate a random file: {}", &path);
@@ -78,6 +78,7 @@ macro_rules! behavior_list_tests {
                 test_list_rich_dir,
                 test_list_empty_dir,
                 test_list_non_exist_dir,
+                test_list_file_as_dir,
                 test_list_sub_dir,
                 test_list_nested_dir,
                 test_list_dir_with_file_path,
@@ -192,6 +193,27 @@ pub async fn test_list_non_exist_dir(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List a file as dir should return empty like a non exist dir.
+pub async fn test_list_file_as_dir(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let mut obs = op.list(&format!("{}/", path)).await?;
+    let mut objects = HashMap::new();
+    while let Some(de) = obs.try_next().await? {
+        objects.insert(de.path().to_string(), de);
+    }
+    debug!("got objects: {:?}", objects);
+
+    assert_eq!(objects.len(), 0, "dir should only return empty");
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// List dir should return correct sub dir.
 pub async fn test_list_sub_dir(op: Operator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
@@ -83,10 +83,12 @@ macro_rules! behavior_write_tests {
                 test_read_full,
                 test_read_range,
                 test_read_large_range,
+                test_read_ranges,
                 test_reader_range,
                 test_reader_from,
                 test_reader_tail,
                 test_read_not_exist,
+                test_read_out_of_range,
                 test_fuzz_range_reader,
                 test_fuzz_offset_reader,
                 test_fuzz_part_reader,
@@ -328,6 +330,45 @@ pub async fn test_read_large_range(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read ranges content should match no matter they are merged or not.
+pub async fn test_read_ranges(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let ranges: Vec<_> = (0..8)
+        .map(|_| {
+            let (offset, length) = gen_offset_length(size);
+            offset..offset + length
+        })
+        .collect();
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    for (gap, max_size) in [(0, u64::MAX), (1024, 4096), (u64::MAX, u64::MAX)] {
+        let bss = op
+            .with_merge_gap(gap)
+            .with_merge_max(max_size)
+            .read_ranges(&path, ranges.clone())
+            .await?;
+        assert_eq!(bss.len(), ranges.len(), "read ranges count");
+        for (bs, range) in bss.iter().zip(ranges.iter()) {
+            assert_eq!(
+                format!("{:x}", Sha256::digest(bs)),
+                format!(
+                    "{:x}",
+                    Sha256::digest(&content[range.start as usize..range.end as usize])
+                ),
+                "read content of range {range:?} with merge gap {gap} and max {max_size}"
+            );
+        }
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Read range content should match.
 pub async fn test_reader_range(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -430,6 +471,28 @@ pub async fn test_read_not_exist(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read range out of the content should not panic.
+///
+/// Services could either return an error or empty content.
+pub async fn test_read_out_of_range(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let size = size as u64;
+    if let Ok(bs) = op.range_read(&path, size + 1..size + 1024).await {
+        assert!(bs.is_empty(), "read out of range must return empty");
+    }
+    if let Ok(bs) = op.range_read(&path, size + 1..).await {
+        assert!(bs.is_empty(), "read out of range must return empty");
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 pub async fn test_fuzz_range_reader(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
     debug!("Generate a random file: {}", &path);